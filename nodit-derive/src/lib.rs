@@ -0,0 +1,333 @@
+//! Derive macros for the [`nodit`] crate, re-exported by it behind its
+//! `derive` feature.
+//!
+//! [`nodit`]: https://docs.rs/nodit
+
+#![allow(clippy::tabs_in_doc_comments)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+	parse_macro_input, parse_quote, Attribute, Data, DataEnum, DeriveInput,
+	Error, Expr, Field, Fields, Ident, Member, Result,
+};
+
+/// Derives `nodit::DiscreteFinite` for a struct with a single field or a
+/// fieldless enum.
+///
+/// On a struct, such as a newtype, every method is forwarded to the field,
+/// which must itself implement `DiscreteFinite`.
+///
+/// On a fieldless enum, the variants in the order they are declared are
+/// the discrete sequence of points, so `up()` on a variant returns the
+/// next variant. This matches a derived `Ord`, which is why enums with
+/// explicit discriminants are rejected: a derived `Ord` compares them by
+/// discriminant, so they could order the variants differently.
+///
+/// The domain can be narrowed with `#[discrete_finite(min = .., max = ..)]`
+/// where both are optional. On a struct they are expressions of the
+/// field's type, which must then also implement `PartialOrd`; on an enum
+/// they are the names of variants.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::{DiscreteFinite, NoditSet};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// #[derive(DiscreteFinite)]
+/// enum Weekday {
+/// 	Mon,
+/// 	Tue,
+/// 	Wed,
+/// 	Thu,
+/// 	Fri,
+/// 	Sat,
+/// 	Sun,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// #[derive(DiscreteFinite)]
+/// #[discrete_finite(min = 1, max = 12)]
+/// struct Month(u8);
+///
+/// assert_eq!(Weekday::Fri.up(), Some(Weekday::Sat));
+/// assert_eq!(Weekday::Sun.up(), None);
+///
+/// let weekend =
+/// 	NoditSet::from_slice_strict([ii(Weekday::Sat, Weekday::Sun)]).unwrap();
+/// assert_eq!(
+/// 	weekend.gaps_untrimmed(..).collect::<Vec<_>>(),
+/// 	[ii(Weekday::Mon, Weekday::Fri)]
+/// );
+///
+/// assert_eq!(Month::MIN, Month(1));
+/// assert_eq!(Month(12).up(), None);
+/// assert_eq!(Month(1).down(), None);
+/// ```
+///
+/// ```compile_fail
+/// use nodit::DiscreteFinite;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// #[derive(DiscreteFinite)]
+/// enum Priority {
+/// 	High = 2,
+/// 	Low = 1,
+/// }
+/// ```
+#[proc_macro_derive(DiscreteFinite, attributes(discrete_finite))]
+pub fn derive_discrete_finite(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	expand(input)
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+/// The custom bounds from the `#[discrete_finite(..)]` attribute.
+#[derive(Default)]
+struct Bounds {
+	min: Option<Expr>,
+	max: Option<Expr>,
+}
+
+fn parse_bounds(attrs: &[Attribute]) -> Result<Bounds> {
+	let mut bounds = Bounds::default();
+
+	for attr in attrs {
+		if !attr.path().is_ident("discrete_finite") {
+			continue;
+		}
+		attr.parse_nested_meta(|meta| {
+			let bound = if meta.path.is_ident("min") {
+				&mut bounds.min
+			} else if meta.path.is_ident("max") {
+				&mut bounds.max
+			} else {
+				return Err(meta.error("expected `min` or `max`"));
+			};
+			if bound.is_some() {
+				return Err(meta.error("duplicate bound"));
+			}
+			*bound = Some(meta.value()?.parse()?);
+			Ok(())
+		})?;
+	}
+
+	Ok(bounds)
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+	let bounds = parse_bounds(&input.attrs)?;
+
+	let (constants, methods) = match input.data.clone() {
+		Data::Struct(data) => {
+			let mut fields = data.fields.into_iter();
+			match (fields.next(), fields.next()) {
+				(Some(field), None) => {
+					expand_struct(&mut input, &field, bounds)
+				}
+				_ => {
+					return Err(Error::new_spanned(
+						&input.ident,
+						"`DiscreteFinite` can only be derived for structs \
+						 with a single field",
+					));
+				}
+			}
+		}
+		Data::Enum(data) => expand_enum(&input.ident, &data, bounds)?,
+		Data::Union(_) => {
+			return Err(Error::new_spanned(
+				&input.ident,
+				"`DiscreteFinite` can't be derived for unions",
+			));
+		}
+	};
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) =
+		input.generics.split_for_impl();
+
+	Ok(quote! {
+		impl #impl_generics ::nodit::DiscreteFinite for #name #ty_generics
+		#where_clause
+		{
+			#constants
+
+			#methods
+		}
+	})
+}
+
+fn expand_struct(
+	input: &mut DeriveInput,
+	field: &Field,
+	bounds: Bounds,
+) -> (TokenStream2, TokenStream2) {
+	let name = input.ident.clone();
+	let ty = &field.ty;
+	let member = match &field.ident {
+		Some(ident) => Member::Named(ident.clone()),
+		None => Member::Unnamed(0.into()),
+	};
+	let wrap = |value: TokenStream2| quote!(#name { #member: #value });
+
+	let where_clause = input.generics.make_where_clause();
+	where_clause
+		.predicates
+		.push(parse_quote!(#ty: ::nodit::DiscreteFinite));
+	if bounds.min.is_some() || bounds.max.is_some() {
+		where_clause
+			.predicates
+			.push(parse_quote!(#ty: ::core::cmp::PartialOrd));
+	}
+
+	let min = wrap(bounds.min.as_ref().map_or_else(
+		|| quote!(<#ty as ::nodit::DiscreteFinite>::MIN),
+		ToTokens::to_token_stream,
+	));
+	let max = wrap(bounds.max.as_ref().map_or_else(
+		|| quote!(<#ty as ::nodit::DiscreteFinite>::MAX),
+		ToTokens::to_token_stream,
+	));
+
+	//custom bounds have to be checked before stepping the field since the
+	//field's own bounds are further out
+	let max_check = bounds.max.is_some().then(|| {
+		quote! {
+			if self.#member >= <Self as ::nodit::DiscreteFinite>::MAX.#member {
+				return ::core::option::Option::None;
+			}
+		}
+	});
+	let min_check = bounds.min.is_some().then(|| {
+		quote! {
+			if self.#member <= <Self as ::nodit::DiscreteFinite>::MIN.#member {
+				return ::core::option::Option::None;
+			}
+		}
+	});
+	let wrap_stepped = wrap(quote!(inner));
+
+	(
+		quote! {
+			const MIN: Self = #min;
+			const MAX: Self = #max;
+		},
+		quote! {
+			fn up(self) -> ::core::option::Option<Self> {
+				#max_check
+				::nodit::DiscreteFinite::up(self.#member)
+					.map(|inner| #wrap_stepped)
+			}
+			fn down(self) -> ::core::option::Option<Self> {
+				#min_check
+				::nodit::DiscreteFinite::down(self.#member)
+					.map(|inner| #wrap_stepped)
+			}
+		},
+	)
+}
+
+fn expand_enum(
+	name: &Ident,
+	data: &DataEnum,
+	bounds: Bounds,
+) -> Result<(TokenStream2, TokenStream2)> {
+	if data.variants.is_empty() {
+		return Err(Error::new_spanned(
+			name,
+			"`DiscreteFinite` can't be derived for enums without variants",
+		));
+	}
+	if let Some(variant) = data
+		.variants
+		.iter()
+		.find(|variant| !matches!(variant.fields, Fields::Unit))
+	{
+		return Err(Error::new_spanned(
+			variant,
+			"`DiscreteFinite` can only be derived for enums without fields",
+		));
+	}
+	if let Some((_, discriminant)) = data
+		.variants
+		.iter()
+		.find_map(|variant| variant.discriminant.as_ref())
+	{
+		return Err(Error::new_spanned(
+			discriminant,
+			"`DiscreteFinite` can't be derived for enums with explicit \
+			 discriminants",
+		));
+	}
+
+	let variants = data
+		.variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+
+	let index_of = |bound: &Option<Expr>, default: usize| match bound {
+		None => Ok(default),
+		Some(expr) => variants
+			.iter()
+			.position(|variant| match expr {
+				Expr::Path(path) => path.path.is_ident(*variant),
+				_ => false,
+			})
+			.ok_or_else(|| {
+				Error::new_spanned(expr, "expected a variant of this enum")
+			}),
+	};
+	let min = index_of(&bounds.min, 0)?;
+	let max = index_of(&bounds.max, variants.len() - 1)?;
+	if min > max {
+		return Err(Error::new_spanned(
+			bounds.min,
+			"`min` must not come after `max`",
+		));
+	}
+
+	let step = |step: fn(usize) -> Option<usize>| {
+		variants.iter().enumerate().map(|(index, variant)| {
+			let next = step(index)
+				.filter(|next| (min..=max).contains(next))
+				.filter(|_| (min..=max).contains(&index))
+				.map_or_else(
+					|| quote!(::core::option::Option::None),
+					|next| {
+						let next = variants[next];
+						quote!(::core::option::Option::Some(Self::#next))
+					},
+				);
+			quote!(Self::#variant => #next,)
+		})
+		.collect::<Vec<_>>()
+	};
+	let up = step(|index| index.checked_add(1));
+	let down = step(|index| index.checked_sub(1));
+	let (min, max) = (variants[min], variants[max]);
+
+	Ok((
+		quote! {
+			const MIN: Self = Self::#min;
+			const MAX: Self = Self::#max;
+		},
+		quote! {
+			fn up(self) -> ::core::option::Option<Self> {
+				match self {
+					#(#up)*
+				}
+			}
+			fn down(self) -> ::core::option::Option<Self> {
+				match self {
+					#(#down)*
+				}
+			}
+		},
+	))
+}
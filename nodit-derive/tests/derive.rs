@@ -0,0 +1,110 @@
+//! Tests for `#[derive(DiscreteFinite)]`.
+
+use std::iter::successors;
+
+use nodit::interval::{ii, uu};
+use nodit::{DiscreteFinite, InclusiveInterval, NoditMap};
+use pretty_assertions::assert_eq;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+enum Weekday {
+	Mon,
+	Tue,
+	Wed,
+	Thu,
+	Fri,
+	Sat,
+	Sun,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+#[discrete_finite(min = Tue, max = Fri)]
+enum Workday {
+	Mon,
+	Tue,
+	Wed,
+	Thu,
+	Fri,
+	Sat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+enum Single {
+	Only,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+struct Id(u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+struct Named {
+	value: i8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+#[discrete_finite(min = 1, max = 12)]
+struct Month(u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+#[discrete_finite(max = 0)]
+struct NonPositive(i8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, DiscreteFinite)]
+struct Wrapper<T>(T);
+
+/// The points of `I` in order, checking the derived `up()` and `down()`
+/// visit the same points.
+fn domain<I>() -> Vec<I>
+where
+	I: DiscreteFinite + Copy + Ord + std::fmt::Debug,
+{
+	let up = successors(Some(I::MIN), |point| point.up()).collect::<Vec<_>>();
+	let down = successors(Some(I::MAX), |point| point.down());
+	assert!(up.windows(2).all(|pair| pair[0] < pair[1]));
+	assert!(up.iter().rev().copied().eq(down));
+	up
+}
+
+#[test]
+fn enum_tests() {
+	use Weekday::*;
+
+	assert_eq!(domain::<Weekday>(), [Mon, Tue, Wed, Thu, Fri, Sat, Sun]);
+	assert_eq!(
+		domain::<Workday>(),
+		[Workday::Tue, Workday::Wed, Workday::Thu, Workday::Fri]
+	);
+	assert_eq!(Workday::Mon.up(), None);
+	assert_eq!(Workday::Sat.down(), None);
+	assert_eq!(domain::<Single>(), [Single::Only]);
+
+	let mut rota = NoditMap::new();
+	rota.insert_strict(ii(Mon, Wed), "alice").unwrap();
+	rota.insert_strict(ii(Thu, Fri), "bob").unwrap();
+	assert_eq!(rota.get_at_point(Thu), Some(&"bob"));
+	assert_eq!(
+		rota.gaps_untrimmed(uu()).collect::<Vec<_>>(),
+		[ii(Sat, Sun)]
+	);
+}
+
+#[test]
+fn struct_tests() {
+	assert_eq!(domain::<Id>().len(), 256);
+	assert_eq!(Id::MIN, Id(0));
+	assert_eq!(Id(7).up(), Some(Id(8)));
+
+	assert_eq!(domain::<Named>().len(), 256);
+	assert_eq!(Named::MIN, Named { value: i8::MIN });
+
+	assert_eq!(domain::<Month>(), (1..=12).map(Month).collect::<Vec<_>>());
+
+	assert_eq!(
+		domain::<NonPositive>(),
+		(i8::MIN..=0).map(NonPositive).collect::<Vec<_>>()
+	);
+
+	assert_eq!(Wrapper::<u16>::MAX, Wrapper(u16::MAX));
+	assert_eq!(Wrapper(Month(3)).up(), Some(Wrapper(Month(4))));
+	assert_eq!(ii(Wrapper(3_u8), Wrapper(4)).start(), Wrapper(3));
+}
@@ -0,0 +1,429 @@
+//! A module containing [`DiscreteFinite`] and [`Countable`] implementations
+//! for the date and time types of the [`chrono`], [`time`] and [`jiff`]
+//! crates, each behind the cargo feature of the same name.
+//!
+//! Dates are used directly as points at a resolution of one day:
+//! [`chrono::NaiveDate`], [`time::Date`] and [`jiff::civil::Date`].
+//!
+//! Date-times need a resolution to be discrete, so they are wrapped in
+//! [`Seconds`] or [`Millis`] which truncate them to that resolution:
+//! `Seconds<chrono::NaiveDateTime>` and `Millis<chrono::NaiveDateTime>`.
+//!
+//! Subtracting two points gives the crate's duration type, so
+//! [`InclusiveInterval::width()`] returns a duration.
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! use chrono::{NaiveDate, TimeDelta};
+//! use nodit::interval::ii;
+//! use nodit::{InclusiveInterval, NoditMap};
+//!
+//! let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+//!
+//! let mut rota = NoditMap::new();
+//! rota.insert_strict(ii(date(1), date(7)), "alice").unwrap();
+//! rota.insert_strict(ii(date(8), date(14)), "bob").unwrap();
+//!
+//! assert_eq!(rota.get_at_point(date(9)), Some(&"bob"));
+//! assert_eq!(ii(date(1), date(7)).width(), TimeDelta::days(6));
+//! assert_eq!(ii(date(1), date(7)).len(), Some(7));
+//! # }
+//! ```
+//!
+//! [`DiscreteFinite`]: crate::DiscreteFinite
+//! [`Countable`]: crate::Countable
+//! [`InclusiveInterval::width()`]: crate::InclusiveInterval::width
+
+/// Implements [`DiscreteFinite`] and [`Countable`] via functions converting
+/// a point to and from its index, such as its number of days since an
+/// epoch.
+macro_rules! discrete_by_index {
+	($t:ty {
+		MIN: $min:expr,
+		MAX: $max:expr,
+		to_index: $to_index:expr,
+		from_index: $from_index:expr $(,)?
+	}) => {
+		impl $crate::DiscreteFinite for $t {
+			const MIN: Self = $min;
+			const MAX: Self = $max;
+
+			fn up(self) -> Option<Self> {
+				let to_index: fn(Self) -> i64 = $to_index;
+				let from_index: fn(i64) -> Option<Self> = $from_index;
+				from_index(to_index(self).checked_add(1)?)
+			}
+			fn down(self) -> Option<Self> {
+				let to_index: fn(Self) -> i64 = $to_index;
+				let from_index: fn(i64) -> Option<Self> = $from_index;
+				from_index(to_index(self).checked_sub(1)?)
+			}
+		}
+
+		impl $crate::Countable for $t {
+			fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+				let to_index: fn(Self) -> i64 = $to_index;
+				(start <= end).then(|| {
+					u128::from(to_index(*start).abs_diff(to_index(*end)))
+				})
+			}
+			fn forward_checked(start: Self, count: u128) -> Option<Self> {
+				let to_index: fn(Self) -> i64 = $to_index;
+				let from_index: fn(i64) -> Option<Self> = $from_index;
+				let count = i64::try_from(count).ok()?;
+				from_index(to_index(start).checked_add(count)?)
+			}
+			fn backward_checked(start: Self, count: u128) -> Option<Self> {
+				let to_index: fn(Self) -> i64 = $to_index;
+				let from_index: fn(i64) -> Option<Self> = $from_index;
+				let count = i64::try_from(count).ok()?;
+				from_index(to_index(start).checked_sub(count)?)
+			}
+		}
+	};
+}
+
+#[cfg(feature = "chrono")]
+pub use self::chrono_impls::{Millis, Seconds};
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+	use core::ops::Sub;
+
+	use chrono::{
+		Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike,
+	};
+
+	discrete_by_index!(NaiveDate {
+		MIN: NaiveDate::MIN,
+		MAX: NaiveDate::MAX,
+		to_index: |date| i64::from(date.num_days_from_ce()),
+		from_index: |index| {
+			NaiveDate::from_num_days_from_ce_opt(i32::try_from(index).ok()?)
+		},
+	});
+
+	/// A date-time truncated to a resolution of one second, so that it can
+	/// be used as a point.
+	///
+	/// Leap seconds are truncated to the end of the previous second.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::{NaiveDate, TimeDelta};
+	/// use nodit::datetime::Seconds;
+	/// use nodit::interval::ie;
+	/// use nodit::{DiscreteFinite, InclusiveInterval};
+	///
+	/// let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+	/// let time = |h, m, s, ms| date.and_hms_milli_opt(h, m, s, ms).unwrap();
+	///
+	/// let start = Seconds::new(time(9, 0, 0, 500));
+	/// let end = Seconds::new(time(17, 0, 0, 0));
+	///
+	/// assert_eq!(start.get(), time(9, 0, 0, 0));
+	/// assert_eq!(start.up().unwrap().get(), time(9, 0, 1, 0));
+	/// //the excluded end becomes 16:59:59
+	/// assert_eq!(
+	/// 	ie(start, end).width(),
+	/// 	TimeDelta::hours(8) - TimeDelta::seconds(1)
+	/// );
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub struct Seconds<T>(T);
+
+	/// A date-time truncated to a resolution of one millisecond, so that it
+	/// can be used as a point.
+	///
+	/// Leap seconds are truncated to the end of the previous second.
+	///
+	/// # Examples
+	/// ```
+	/// use chrono::NaiveDate;
+	/// use nodit::datetime::Millis;
+	/// use nodit::interval::ii;
+	/// use nodit::InclusiveInterval;
+	///
+	/// let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+	/// let start =
+	/// 	Millis::new(date.and_hms_micro_opt(9, 0, 0, 1_500).unwrap());
+	///
+	/// assert_eq!(start.get(), date.and_hms_milli_opt(9, 0, 0, 1).unwrap());
+	/// assert_eq!(ii(start, start).len(), Some(1));
+	/// ```
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+	pub struct Millis<T>(T);
+
+	impl Seconds<NaiveDateTime> {
+		/// Makes a new `Seconds` by truncating `datetime` to the second.
+		pub fn new(datetime: NaiveDateTime) -> Self {
+			//every index of a valid date-time is itself valid
+			Seconds(seconds_from_index(seconds_to_index(datetime)).unwrap())
+		}
+		/// Returns the truncated date-time.
+		pub fn get(self) -> NaiveDateTime {
+			self.0
+		}
+	}
+
+	impl Millis<NaiveDateTime> {
+		/// Makes a new `Millis` by truncating `datetime` to the millisecond.
+		pub fn new(datetime: NaiveDateTime) -> Self {
+			//every index of a valid date-time is itself valid
+			Millis(millis_from_index(millis_to_index(datetime)).unwrap())
+		}
+		/// Returns the truncated date-time.
+		pub fn get(self) -> NaiveDateTime {
+			self.0
+		}
+	}
+
+	impl From<Seconds<NaiveDateTime>> for NaiveDateTime {
+		fn from(seconds: Seconds<NaiveDateTime>) -> Self {
+			seconds.0
+		}
+	}
+	impl From<Millis<NaiveDateTime>> for NaiveDateTime {
+		fn from(millis: Millis<NaiveDateTime>) -> Self {
+			millis.0
+		}
+	}
+
+	impl Sub for Seconds<NaiveDateTime> {
+		type Output = TimeDelta;
+
+		fn sub(self, rhs: Self) -> TimeDelta {
+			self.0 - rhs.0
+		}
+	}
+	impl Sub for Millis<NaiveDateTime> {
+		type Output = TimeDelta;
+
+		fn sub(self, rhs: Self) -> TimeDelta {
+			self.0 - rhs.0
+		}
+	}
+
+	//indexes count from `NaiveDateTime::MIN` so that they are never
+	//negative and truncating them rounds down, leap seconds are clamped
+	//first so they don't spill into the next second
+	fn seconds_to_index(datetime: NaiveDateTime) -> i64 {
+		(clamp_leap_second(datetime) - NaiveDateTime::MIN).num_seconds()
+	}
+	fn seconds_from_index(index: i64) -> Option<NaiveDateTime> {
+		NaiveDateTime::MIN.checked_add_signed(TimeDelta::try_seconds(index)?)
+	}
+	fn millis_to_index(datetime: NaiveDateTime) -> i64 {
+		(clamp_leap_second(datetime) - NaiveDateTime::MIN).num_milliseconds()
+	}
+	fn millis_from_index(index: i64) -> Option<NaiveDateTime> {
+		NaiveDateTime::MIN
+			.checked_add_signed(TimeDelta::try_milliseconds(index)?)
+	}
+	fn clamp_leap_second(datetime: NaiveDateTime) -> NaiveDateTime {
+		//nanoseconds below one second are always valid
+		datetime
+			.with_nanosecond(datetime.nanosecond().min(999_999_999))
+			.unwrap()
+	}
+
+	const LAST_SECOND: NaiveTime = match NaiveTime::from_hms_opt(23, 59, 59) {
+		Some(time) => time,
+		None => panic!(),
+	};
+	const LAST_MILLI: NaiveTime =
+		match NaiveTime::from_hms_milli_opt(23, 59, 59, 999) {
+			Some(time) => time,
+			None => panic!(),
+		};
+
+	discrete_by_index!(Seconds<NaiveDateTime> {
+		MIN: Seconds(NaiveDateTime::MIN),
+		MAX: Seconds(NaiveDate::MAX.and_time(LAST_SECOND)),
+		to_index: |seconds| seconds_to_index(seconds.0),
+		from_index: |index| seconds_from_index(index).map(Seconds),
+	});
+
+	discrete_by_index!(Millis<NaiveDateTime> {
+		MIN: Millis(NaiveDateTime::MIN),
+		MAX: Millis(NaiveDate::MAX.and_time(LAST_MILLI)),
+		to_index: |millis| millis_to_index(millis.0),
+		from_index: |index| millis_from_index(index).map(Millis),
+	});
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+	use time::Date;
+
+	discrete_by_index!(Date {
+		MIN: Date::MIN,
+		MAX: Date::MAX,
+		to_index: |date| i64::from(date.to_julian_day()),
+		from_index: |index| {
+			Date::from_julian_day(i32::try_from(index).ok()?).ok()
+		},
+	});
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_impls {
+	use jiff::civil::Date;
+	use jiff::Span;
+
+	//indexes count the days from `Date::MIN`, every date is within the
+	//range of days a span can hold
+	discrete_by_index!(Date {
+		MIN: Date::MIN,
+		MAX: Date::MAX,
+		to_index: |date| i64::from((date - Date::MIN).get_days()),
+		from_index: |index| {
+			Date::MIN.checked_add(Span::new().try_days(index).ok()?).ok()
+		},
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use core::fmt::Debug;
+
+	use pretty_assertions::assert_eq;
+
+	use crate::{Countable, DiscreteFinite};
+
+	/// Checks `up()`, `down()` and the [`Countable`] methods around each of
+	/// the given points and at the edges of the domain.
+	fn check_points<I>(points: &[I])
+	where
+		I: Ord + Copy + Debug + Countable,
+	{
+		assert_eq!(I::MIN.down(), None);
+		assert_eq!(I::MAX.up(), None);
+		assert_eq!(I::forward_checked(I::MIN, 0), Some(I::MIN));
+
+		let span = I::steps_between(&I::MIN, &I::MAX).unwrap();
+		assert_eq!(I::forward_checked(I::MIN, span), Some(I::MAX));
+		assert_eq!(I::forward_checked(I::MIN, span + 1), None);
+		assert_eq!(I::backward_checked(I::MAX, span), Some(I::MIN));
+		assert_eq!(I::backward_checked(I::MAX, span + 1), None);
+
+		for point in points.iter().copied() {
+			let up = point.up().unwrap();
+			let down = point.down().unwrap();
+			assert!(down < point && point < up);
+			assert_eq!(up.down(), Some(point));
+			assert_eq!(down.up(), Some(point));
+			assert_eq!(I::steps_between(&down, &up), Some(2));
+			assert_eq!(I::steps_between(&up, &down), None);
+			assert_eq!(I::forward_checked(down, 2), Some(up));
+			assert_eq!(I::backward_checked(up, 2), Some(down));
+
+			let from_min = I::steps_between(&I::MIN, &point).unwrap();
+			assert_eq!(I::backward_checked(point, from_min), Some(I::MIN));
+		}
+	}
+
+	#[cfg(feature = "chrono")]
+	#[test]
+	fn chrono_tests() {
+		use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+
+		use super::{Millis, Seconds};
+		use crate::interval::ii;
+		use crate::InclusiveInterval;
+
+		let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+		check_points(&[date(2024, 2, 29), date(1, 1, 1), date(-1, 12, 31)]);
+		assert_eq!(date(2024, 2, 28).up(), Some(date(2024, 2, 29)));
+		assert_eq!(date(2023, 12, 31).up(), Some(date(2024, 1, 1)));
+		assert_eq!(ii(date(2024, 1, 1), date(2024, 12, 31)).len(), Some(366));
+
+		let datetime = |h, m, s, ms| {
+			date(2024, 6, 30).and_hms_milli_opt(h, m, s, ms).unwrap()
+		};
+		let epoch = date(1970, 1, 1).and_hms_opt(0, 0, 0).unwrap();
+		check_points(&[
+			Seconds::new(datetime(0, 0, 0, 0)),
+			Seconds::new(epoch),
+		]);
+		check_points(&[Millis::new(datetime(0, 0, 0, 0)), Millis::new(epoch)]);
+
+		assert_eq!(
+			Seconds::new(datetime(23, 59, 59, 999)).up(),
+			Some(Seconds::new(date(2024, 7, 1).and_hms_opt(0, 0, 0).unwrap()))
+		);
+		assert_eq!(
+			Seconds::new(datetime(12, 0, 0, 999)),
+			Seconds::new(datetime(12, 0, 0, 0))
+		);
+		//a leap second
+		assert_eq!(
+			Seconds::new(datetime(23, 59, 59, 1_500)),
+			Seconds::new(datetime(23, 59, 59, 0))
+		);
+		assert_eq!(
+			Millis::new(datetime(23, 59, 59, 1_500)),
+			Millis::new(datetime(23, 59, 59, 999))
+		);
+		assert_eq!(
+			Seconds::new(NaiveDateTime::MAX),
+			<Seconds<NaiveDateTime> as DiscreteFinite>::MAX
+		);
+		assert_eq!(
+			Millis::new(NaiveDateTime::MAX),
+			<Millis<NaiveDateTime> as DiscreteFinite>::MAX
+		);
+		assert_eq!(
+			ii(
+				Millis::new(datetime(9, 0, 0, 0)),
+				Millis::new(datetime(9, 0, 1, 250))
+			)
+			.width(),
+			TimeDelta::milliseconds(1_250)
+		);
+	}
+
+	#[cfg(feature = "time")]
+	#[test]
+	fn time_tests() {
+		use time::{Date, Duration, Month};
+
+		use crate::interval::ii;
+		use crate::InclusiveInterval;
+
+		let date = |y, m, d| Date::from_calendar_date(y, m, d).unwrap();
+		check_points(&[
+			date(2024, Month::February, 29),
+			date(1, Month::January, 1),
+			date(-1, Month::December, 31),
+		]);
+		assert_eq!(
+			date(2023, Month::December, 31).up(),
+			Some(date(2024, Month::January, 1))
+		);
+		assert_eq!(
+			ii(date(2024, Month::March, 1), date(2024, Month::March, 8))
+				.width(),
+			Duration::days(7)
+		);
+	}
+
+	#[cfg(feature = "jiff")]
+	#[test]
+	fn jiff_tests() {
+		use jiff::civil::date;
+
+		use crate::interval::ii;
+		use crate::InclusiveInterval;
+
+		check_points(&[date(2024, 2, 29), date(1, 1, 1), date(-1, 12, 31)]);
+		assert_eq!(date(2023, 12, 31).up(), Some(date(2024, 1, 1)));
+		assert_eq!(ii(date(2024, 1, 1), date(2024, 12, 31)).len(), Some(366));
+		assert_eq!(
+			ii(date(2024, 3, 1), date(2024, 3, 8)).width().get_days(),
+			7
+		);
+	}
+}
@@ -1,5 +1,65 @@
-//! A module containing the [`DiscreteFinite`] trait and trait impls for the
-//! primitive integer datatypes.
+//! A module containing the [`DiscreteFinite`], [`Countable`],
+//! [`SmallCountable`] and [`CheckedArithmetic`] traits and trait impls for
+//! the primitive integer datatypes.
+//!
+//! [`DiscreteFinite`] and [`Countable`] are also implemented for `char`,
+//! `bool`, the [`NonZero`] integers, [`Ipv4Addr`] and [`Ipv6Addr`], and
+//! [`DiscreteFinite`] for [`IpAddr`], so a
+//! [`NoditSet<char, _>`] can be used as a Unicode character class and a
+//! [`NoditMap<Ipv4Addr, _, _>`] as an IP-range lookup table.
+//!
+//! [`DiscreteFinite`] is also implemented for pairs, triples and arrays of
+//! [`DiscreteFinite`] types, ordered lexicographically like their [`Ord`]
+//! implementations, so that stepping past the end of the last component
+//! carries into the one before it like an odometer.
+//!
+//! # Examples
+//! ```
+//! use std::net::Ipv4Addr;
+//!
+//! use nodit::interval::ii;
+//! use nodit::{DiscreteFinite, NoditMap, NoditSet};
+//!
+//! let hex_digits =
+//! 	NoditSet::from_slice_strict([ii('0', '9'), ii('A', 'F'), ii('a', 'f')])
+//! 		.unwrap();
+//!
+//! assert!(hex_digits.contains_point('c'));
+//! assert!(!hex_digits.contains_point('g'));
+//!
+//! let networks = NoditMap::from_slice_strict([
+//! 	(
+//! 		ii(Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 255, 255, 255)),
+//! 		"a",
+//! 	),
+//! 	(
+//! 		ii(Ipv4Addr::new(192, 168, 0, 0), Ipv4Addr::new(192, 168, 0, 255)),
+//! 		"b",
+//! 	),
+//! ])
+//! .unwrap();
+//!
+//! assert_eq!(networks.get_at_point(Ipv4Addr::new(10, 1, 2, 3)), Some(&"a"));
+//! assert_eq!(networks.get_at_point(Ipv4Addr::new(11, 0, 0, 0)), None);
+//!
+//! let shards =
+//! 	NoditMap::from_slice_strict([(ii((0_u8, 100_u32), (2, 50)), "a")])
+//! 		.unwrap();
+//!
+//! assert_eq!(shards.get_at_point((1, u32::MAX)), Some(&"a"));
+//! assert_eq!(shards.get_at_point((2, 51)), None);
+//! assert_eq!((0_u8, u32::MAX).up(), Some((1, 0)));
+//! ```
+//!
+//! [`NonZero`]: core::num::NonZero
+//! [`NoditSet<char, _>`]: crate::NoditSet
+//! [`NoditMap<Ipv4Addr, _, _>`]: crate::NoditMap
+
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::num::{
+	NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128,
+	NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 
 /// A trait for things which are both discrete and finite datatypes. See the
 /// top-level module documentation for more detailed descriptions on
@@ -40,3 +100,594 @@ macro_rules! foo {
 }
 
 foo!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize,);
+
+/// A trait for [`DiscreteFinite`] types whose points can be counted, used
+/// by [`InclusiveInterval::len()`] and [`InclusiveInterval::points()`].
+///
+/// [`InclusiveInterval::len()`]: crate::InclusiveInterval::len
+/// [`InclusiveInterval::points()`]: crate::InclusiveInterval::points
+pub trait Countable: DiscreteFinite {
+	/// The number of times [`DiscreteFinite::up()`] must be applied to
+	/// `start` to reach `end`, or `None` if `start` is greater than `end`.
+	fn steps_between(start: &Self, end: &Self) -> Option<u128>;
+	/// The result of applying [`DiscreteFinite::up()`] to `start` `count`
+	/// times, or `None` if that would go past [`DiscreteFinite::MAX`].
+	fn forward_checked(start: Self, count: u128) -> Option<Self>
+	where
+		Self: Sized;
+	/// The result of applying [`DiscreteFinite::down()`] to `start` `count`
+	/// times, or `None` if that would go past [`DiscreteFinite::MIN`].
+	fn backward_checked(start: Self, count: u128) -> Option<Self>
+	where
+		Self: Sized;
+}
+
+macro_rules! countable {
+	($($t:ident as $u:ident with $add:ident, $sub:ident),*) => {
+		$(
+			impl Countable for $t {
+				fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+					(start <= end)
+						.then(|| end.wrapping_sub(*start) as $u as u128)
+				}
+				fn forward_checked(start: Self, count: u128) -> Option<Self> {
+					$u::try_from(count).ok().and_then(|count| start.$add(count))
+				}
+				fn backward_checked(start: Self, count: u128) -> Option<Self> {
+					$u::try_from(count).ok().and_then(|count| start.$sub(count))
+				}
+			}
+		)*
+	};
+}
+
+countable!(
+	u8 as u8 with checked_add, checked_sub,
+	i8 as u8 with checked_add_unsigned, checked_sub_unsigned,
+	u16 as u16 with checked_add, checked_sub,
+	i16 as u16 with checked_add_unsigned, checked_sub_unsigned,
+	u32 as u32 with checked_add, checked_sub,
+	i32 as u32 with checked_add_unsigned, checked_sub_unsigned,
+	u64 as u64 with checked_add, checked_sub,
+	i64 as u64 with checked_add_unsigned, checked_sub_unsigned,
+	u128 as u128 with checked_add, checked_sub,
+	i128 as u128 with checked_add_unsigned, checked_sub_unsigned,
+	usize as usize with checked_add, checked_sub
+);
+
+impl DiscreteFinite for char {
+	const MIN: Self = '\0';
+	const MAX: Self = char::MAX;
+
+	//the surrogate code points `U+D800..=U+DFFF` aren't valid chars so
+	//they are skipped over
+	fn up(self) -> Option<Self> {
+		if self == '\u{D7FF}' {
+			Some('\u{E000}')
+		} else {
+			char::from_u32(self as u32 + 1)
+		}
+	}
+	fn down(self) -> Option<Self> {
+		if self == '\u{E000}' {
+			Some('\u{D7FF}')
+		} else {
+			(self as u32).checked_sub(1).and_then(char::from_u32)
+		}
+	}
+}
+
+impl DiscreteFinite for bool {
+	const MIN: Self = false;
+	const MAX: Self = true;
+
+	fn up(self) -> Option<Self> {
+		(!self).then_some(true)
+	}
+	fn down(self) -> Option<Self> {
+		self.then_some(false)
+	}
+}
+
+impl DiscreteFinite for Ipv4Addr {
+	const MIN: Self = Ipv4Addr::new(0, 0, 0, 0);
+	const MAX: Self = Ipv4Addr::new(255, 255, 255, 255);
+
+	fn up(self) -> Option<Self> {
+		u32::from(self).checked_add(1).map(Ipv4Addr::from)
+	}
+	fn down(self) -> Option<Self> {
+		u32::from(self).checked_sub(1).map(Ipv4Addr::from)
+	}
+}
+
+impl DiscreteFinite for Ipv6Addr {
+	const MIN: Self = Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0);
+	const MAX: Self = Ipv6Addr::new(
+		0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+	);
+
+	fn up(self) -> Option<Self> {
+		u128::from(self).checked_add(1).map(Ipv6Addr::from)
+	}
+	fn down(self) -> Option<Self> {
+		u128::from(self).checked_sub(1).map(Ipv6Addr::from)
+	}
+}
+
+//every IPv4 address comes before every IPv6 address, as in the `Ord`
+//implementation of `IpAddr`
+impl DiscreteFinite for IpAddr {
+	const MIN: Self = IpAddr::V4(<Ipv4Addr as DiscreteFinite>::MIN);
+	const MAX: Self = IpAddr::V6(<Ipv6Addr as DiscreteFinite>::MAX);
+
+	fn up(self) -> Option<Self> {
+		match self {
+			IpAddr::V4(address) => Some(address.up().map_or(
+				IpAddr::V6(<Ipv6Addr as DiscreteFinite>::MIN),
+				IpAddr::V4,
+			)),
+			IpAddr::V6(address) => address.up().map(IpAddr::V6),
+		}
+	}
+	fn down(self) -> Option<Self> {
+		match self {
+			IpAddr::V4(address) => address.down().map(IpAddr::V4),
+			IpAddr::V6(address) => Some(address.down().map_or(
+				IpAddr::V4(<Ipv4Addr as DiscreteFinite>::MAX),
+				IpAddr::V6,
+			)),
+		}
+	}
+}
+
+//tuples and arrays are ordered lexicographically, so stepping carries
+//into the previous component like an odometer
+impl<A, B> DiscreteFinite for (A, B)
+where
+	A: DiscreteFinite,
+	B: DiscreteFinite,
+{
+	const MIN: Self = (A::MIN, B::MIN);
+	const MAX: Self = (A::MAX, B::MAX);
+
+	fn up(self) -> Option<Self> {
+		let (a, b) = self;
+		match b.up() {
+			Some(b) => Some((a, b)),
+			None => Some((a.up()?, B::MIN)),
+		}
+	}
+	fn down(self) -> Option<Self> {
+		let (a, b) = self;
+		match b.down() {
+			Some(b) => Some((a, b)),
+			None => Some((a.down()?, B::MAX)),
+		}
+	}
+}
+
+impl<A, B, C> DiscreteFinite for (A, B, C)
+where
+	A: DiscreteFinite,
+	B: DiscreteFinite,
+	C: DiscreteFinite,
+{
+	const MIN: Self = (A::MIN, B::MIN, C::MIN);
+	const MAX: Self = (A::MAX, B::MAX, C::MAX);
+
+	fn up(self) -> Option<Self> {
+		let (a, b, c) = self;
+		let ((a, b), c) = ((a, b), c).up()?;
+		Some((a, b, c))
+	}
+	fn down(self) -> Option<Self> {
+		let (a, b, c) = self;
+		let ((a, b), c) = ((a, b), c).down()?;
+		Some((a, b, c))
+	}
+}
+
+impl<T, const N: usize> DiscreteFinite for [T; N]
+where
+	T: DiscreteFinite + Copy,
+{
+	const MIN: Self = [T::MIN; N];
+	const MAX: Self = [T::MAX; N];
+
+	fn up(mut self) -> Option<Self> {
+		for component in self.iter_mut().rev() {
+			match component.up() {
+				Some(next) => {
+					*component = next;
+					return Some(self);
+				}
+				None => *component = T::MIN,
+			}
+		}
+		None
+	}
+	fn down(mut self) -> Option<Self> {
+		for component in self.iter_mut().rev() {
+			match component.down() {
+				Some(next) => {
+					*component = next;
+					return Some(self);
+				}
+				None => *component = T::MAX,
+			}
+		}
+		None
+	}
+}
+
+/// Implements [`Countable`] via functions converting a point to and from
+/// its index in the domain, that is the number of points below it.
+macro_rules! countable_by_index {
+	($($t:ty: $to_index:ident, $from_index:ident;)*) => {
+		$(
+			impl Countable for $t {
+				fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+					(start <= end).then(|| $to_index(*end) - $to_index(*start))
+				}
+				fn forward_checked(start: Self, count: u128) -> Option<Self> {
+					$to_index(start).checked_add(count).and_then($from_index)
+				}
+				fn backward_checked(start: Self, count: u128) -> Option<Self> {
+					$to_index(start).checked_sub(count).and_then($from_index)
+				}
+			}
+		)*
+	};
+}
+
+countable_by_index!(
+	char: char_to_index, char_from_index;
+	bool: bool_to_index, bool_from_index;
+	Ipv4Addr: ipv4_to_index, ipv4_from_index;
+	Ipv6Addr: ipv6_to_index, ipv6_from_index;
+);
+
+const SURROGATES: u32 = 0xE000 - 0xD800;
+
+fn char_to_index(c: char) -> u128 {
+	let c = c as u32;
+	u128::from(if c >= 0xE000 { c - SURROGATES } else { c })
+}
+fn char_from_index(index: u128) -> Option<char> {
+	let index = u32::try_from(index).ok()?;
+	char::from_u32(if index >= 0xD800 {
+		index.checked_add(SURROGATES)?
+	} else {
+		index
+	})
+}
+fn bool_to_index(b: bool) -> u128 {
+	u128::from(b)
+}
+fn bool_from_index(index: u128) -> Option<bool> {
+	match index {
+		0 => Some(false),
+		1 => Some(true),
+		_ => None,
+	}
+}
+fn ipv4_to_index(address: Ipv4Addr) -> u128 {
+	u128::from(u32::from(address))
+}
+fn ipv4_from_index(index: u128) -> Option<Ipv4Addr> {
+	u32::try_from(index).ok().map(Ipv4Addr::from)
+}
+fn ipv6_to_index(address: Ipv6Addr) -> u128 {
+	u128::from(address)
+}
+fn ipv6_from_index(index: u128) -> Option<Ipv6Addr> {
+	Some(Ipv6Addr::from(index))
+}
+
+macro_rules! non_zero_unsigned {
+	($($t:ident as $u:ident),*) => {
+		$(
+			impl DiscreteFinite for $t {
+				const MIN: Self = $t::MIN;
+				const MAX: Self = $t::MAX;
+
+				fn up(self) -> Option<Self> {
+					self.checked_add(1)
+				}
+				fn down(self) -> Option<Self> {
+					$t::new(self.get() - 1)
+				}
+			}
+
+			impl Countable for $t {
+				fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+					$u::steps_between(&start.get(), &end.get())
+				}
+				fn forward_checked(start: Self, count: u128) -> Option<Self> {
+					$u::forward_checked(start.get(), count).and_then($t::new)
+				}
+				fn backward_checked(start: Self, count: u128) -> Option<Self> {
+					$u::backward_checked(start.get(), count).and_then($t::new)
+				}
+			}
+		)*
+	};
+}
+
+non_zero_unsigned!(
+	NonZeroU8 as u8,
+	NonZeroU16 as u16,
+	NonZeroU32 as u32,
+	NonZeroU64 as u64,
+	NonZeroU128 as u128,
+	NonZeroUsize as usize
+);
+
+macro_rules! non_zero_signed {
+	($($t:ident as $i:ident),*) => {
+		$(
+			//zero is skipped over when stepping between -1 and 1
+			impl DiscreteFinite for $t {
+				const MIN: Self = $t::MIN;
+				const MAX: Self = $t::MAX;
+
+				fn up(self) -> Option<Self> {
+					$t::new(self.get().checked_add(1)?).or($t::new(1))
+				}
+				fn down(self) -> Option<Self> {
+					$t::new(self.get().checked_sub(1)?).or($t::new(-1))
+				}
+			}
+
+			impl Countable for $t {
+				fn steps_between(start: &Self, end: &Self) -> Option<u128> {
+					let (start, end) = (start.get(), end.get());
+					$i::steps_between(&start, &end)
+						.map(|steps| steps - u128::from(start < 0 && end > 0))
+				}
+				fn forward_checked(start: Self, count: u128) -> Option<Self> {
+					let start = start.get();
+					let skip = $i::steps_between(&start, &-1)
+						.is_some_and(|to_zero| count > to_zero);
+					let count = count.checked_add(u128::from(skip))?;
+					$i::forward_checked(start, count).and_then($t::new)
+				}
+				fn backward_checked(start: Self, count: u128) -> Option<Self> {
+					let start = start.get();
+					let skip = $i::steps_between(&1, &start)
+						.is_some_and(|to_zero| count > to_zero);
+					let count = count.checked_add(u128::from(skip))?;
+					$i::backward_checked(start, count).and_then($t::new)
+				}
+			}
+		)*
+	};
+}
+
+non_zero_signed!(
+	NonZeroI8 as i8,
+	NonZeroI16 as i16,
+	NonZeroI32 as i32,
+	NonZeroI64 as i64,
+	NonZeroI128 as i128
+);
+
+/// A marker trait for [`Countable`] types with few enough points that the
+/// number of points in any interval of them fits in a `usize`, which makes
+/// [`Points`] an [`ExactSizeIterator`].
+///
+/// It is implemented for the 8-bit integers and `bool` on every target, the
+/// 16-bit integers and `char` on 32-bit and 64-bit targets, and the 32-bit
+/// integers on 64-bit targets.
+///
+/// [`Points`]: crate::interval::Points
+pub trait SmallCountable: Countable {}
+
+macro_rules! small_countable {
+	($($t:ty),*) => {
+		$(
+			impl SmallCountable for $t {}
+		)*
+	};
+}
+
+small_countable!(u8, i8, bool, NonZeroU8, NonZeroI8);
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+small_countable!(u16, i16, char, NonZeroU16, NonZeroI16);
+#[cfg(target_pointer_width = "64")]
+small_countable!(u32, i32, NonZeroU32, NonZeroI32);
+
+/// A trait for point types with overflow-checked arithmetic, used by
+/// [`InclusiveInterval::checked_translate()`] and friends.
+///
+/// [`InclusiveInterval::checked_translate()`]: crate::InclusiveInterval::checked_translate
+pub trait CheckedArithmetic: Sized {
+	/// `self + rhs`, or `None` if it would overflow.
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+	/// `self - rhs`, or `None` if it would overflow.
+	fn checked_sub(self, rhs: Self) -> Option<Self>;
+	/// `self + rhs`, clamped to the minimum or maximum value if it would
+	/// overflow.
+	fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! checked_arithmetic {
+	($($t:ident),*) => {
+		$(
+			impl CheckedArithmetic for $t {
+				fn checked_add(self, rhs: Self) -> Option<Self> {
+					$t::checked_add(self, rhs)
+				}
+				fn checked_sub(self, rhs: Self) -> Option<Self> {
+					$t::checked_sub(self, rhs)
+				}
+				fn saturating_add(self, rhs: Self) -> Self {
+					$t::saturating_add(self, rhs)
+				}
+			}
+		)*
+	};
+}
+
+checked_arithmetic!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize);
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+	use core::fmt::Debug;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::InclusiveInterval;
+	use crate::interval::uu;
+	use crate::utils::test_helpers::walk_domain;
+
+	#[test]
+	fn exhaustive_tests() {
+		check_domain(&[false, true]);
+		check_domain(&[0_u8, 1, 127, 128, 255]);
+		check_domain(&[i8::MIN, -1, 0, 1, i8::MAX]);
+		check_domain(&[NonZeroU8::MIN, NonZeroU8::new(128).unwrap()]);
+		check_domain(&[
+			NonZeroI8::MIN,
+			NonZeroI8::new(-2).unwrap(),
+			NonZeroI8::new(-1).unwrap(),
+			NonZeroI8::new(1).unwrap(),
+			NonZeroI8::new(2).unwrap(),
+			NonZeroI8::MAX,
+		]);
+		check_domain(&['\0', 'a', '\u{D7FF}', '\u{E000}', char::MAX]);
+	}
+
+	#[test]
+	fn edge_tests() {
+		assert_eq!('\u{D7FF}'.up(), Some('\u{E000}'));
+		assert_eq!('\u{E000}'.down(), Some('\u{D7FF}'));
+		assert_eq!(char::MAX.up(), None);
+		assert_eq!('\0'.down(), None);
+		assert_eq!(char::steps_between(&'\u{D7FF}', &'\u{E000}'), Some(1));
+		assert_eq!(
+			char::forward_checked('\0', 0x10FFFF - 0x800),
+			Some(char::MAX)
+		);
+		assert_eq!(char::forward_checked('\0', 0x10FFFF - 0x7FF), None);
+
+		assert_eq!(NonZeroI128::new(-1).unwrap().up(), NonZeroI128::new(1));
+		assert_eq!(NonZeroI128::new(1).unwrap().down(), NonZeroI128::new(-1));
+		assert_eq!(NonZeroI128::MAX.up(), None);
+		assert_eq!(NonZeroI128::MIN.down(), None);
+		assert_eq!(
+			NonZeroI128::steps_between(&NonZeroI128::MIN, &NonZeroI128::MAX),
+			Some(u128::MAX - 1)
+		);
+		assert_eq!(
+			NonZeroI128::forward_checked(NonZeroI128::MIN, u128::MAX - 1),
+			Some(NonZeroI128::MAX)
+		);
+		assert_eq!(
+			NonZeroI128::forward_checked(NonZeroI128::MIN, u128::MAX),
+			None
+		);
+		assert_eq!(NonZeroU128::MIN.down(), None);
+		assert_eq!(NonZeroU128::MAX.up(), None);
+
+		assert_eq!(
+			Ipv4Addr::new(10, 0, 0, 255).up(),
+			Some(Ipv4Addr::new(10, 0, 1, 0))
+		);
+		assert_eq!(Ipv4Addr::new(255, 255, 255, 255).up(), None);
+		assert_eq!(Ipv4Addr::new(0, 0, 0, 0).down(), None);
+		assert_eq!(
+			Ipv4Addr::steps_between(&Ipv4Addr::MIN, &Ipv4Addr::MAX),
+			Some(u128::from(u32::MAX))
+		);
+		assert_eq!(
+			IpAddr::V4(Ipv4Addr::BROADCAST).up(),
+			Some(IpAddr::V6(Ipv6Addr::UNSPECIFIED))
+		);
+		assert_eq!(
+			IpAddr::V6(Ipv6Addr::UNSPECIFIED).down(),
+			Some(IpAddr::V4(Ipv4Addr::BROADCAST))
+		);
+		assert_eq!(IpAddr::MIN.down(), None);
+		assert_eq!(IpAddr::MAX.up(), None);
+		assert_eq!(Ipv6Addr::MAX.up(), None);
+		assert_eq!(Ipv6Addr::MIN.down(), None);
+		assert_eq!(
+			Ipv6Addr::forward_checked(Ipv6Addr::MIN, u128::MAX),
+			Some(Ipv6Addr::MAX)
+		);
+	}
+
+	#[test]
+	fn lexicographic_tests() {
+		//the first points of the domain in lexicographic order
+		let bools = [false, true];
+		let mut expected = Vec::new();
+		for a in 0..3_u8 {
+			for b in bools {
+				for c in bools {
+					expected.push((a, b, c));
+				}
+			}
+		}
+		let triples = walk_domain::<(u8, bool, bool)>();
+		assert_eq!(triples.len(), 256 * 4);
+		assert_eq!(triples[..expected.len()], expected);
+
+		let pairs = walk_domain::<(bool, u8)>();
+		assert_eq!(pairs.len(), 512);
+		assert_eq!(pairs[255..257], [(false, 255), (true, 0)]);
+
+		let arrays = walk_domain::<[bool; 3]>();
+		assert_eq!(arrays.len(), 8);
+		assert_eq!(arrays[3..5], [[false, true, true], [true, false, false]]);
+		assert_eq!(walk_domain::<[u8; 0]>(), [[]]);
+
+		assert_eq!((i8::MAX, u8::MAX).up(), None);
+		assert_eq!((i8::MIN, u8::MIN).down(), None);
+		assert_eq!((0_i8, u8::MIN).down(), Some((-1, u8::MAX)));
+		assert_eq!([1_u8, 255, 255].up(), Some([2, 0, 0]));
+		assert_eq!([1_u8, 0, 0].down(), Some([0, 255, 255]));
+		assert_eq!([u8::MAX; 4].up(), None);
+	}
+
+	// Test Helper Functions
+	//======================
+	/// Walks the whole domain of `I` and checks `up()`, `down()` and the
+	/// [`Countable`] methods agree with it between each pair of `samples`.
+	fn check_domain<I>(samples: &[I])
+	where
+		I: Ord + Copy + Debug + Countable,
+	{
+		let domain = walk_domain::<I>();
+		assert!(uu::<I>().points().eq(domain.iter().copied()));
+
+		let index = |point: &I| domain.binary_search(point).unwrap();
+		let len = domain.len() as u128;
+		for start in samples {
+			let i = index(start) as u128;
+			for end in samples {
+				let j = index(end) as u128;
+				assert_eq!(
+					I::steps_between(start, end),
+					(i <= j).then(|| j - i)
+				);
+			}
+			for count in [0, 1, 2, len - 1 - i, len - i, len] {
+				assert_eq!(
+					I::forward_checked(*start, count),
+					(i + count < len).then(|| domain[(i + count) as usize])
+				);
+			}
+			for count in [0, 1, 2, i, i + 1] {
+				assert_eq!(
+					I::backward_checked(*start, count),
+					(count <= i).then(|| domain[(i - count) as usize])
+				);
+			}
+		}
+	}
+}
@@ -1,15 +1,23 @@
 //! A module containing the `gqdit` data-structure.
 //!
 //! `gqdit` stands for Gap Query Discrete Interval Tree
+//!
+//! See the [`persistent`] module for [`PersistentGqdit`](persistent::PersistentGqdit)
+//! whose clones share their structure.
+
+pub mod persistent;
 
 use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
 
 use itertools::Itertools;
 
-use crate::interval::{ii, iu, ui, uu};
-use crate::utils::invalid_interval_panic;
-use crate::{Interval, IntervalType, NoditMap, PointType};
+use crate::interval::{iu, ui, uu};
+use crate::utils::{invalid_interval_panic, query_interval};
+use crate::{
+	Interval, IntervalType, IntoInterval, InvariantError, NoditMap,
+	PersistentNoditMap, PointType,
+};
 
 /// The marker trait for valid id types, a blanket implementation is provided for all types
 /// which implement this traits' super-traits so you shouln't need to implement this yourself.
@@ -89,16 +97,9 @@ where
 	/// Returns a [`Vec`] of intervals which are equivalent to those found
 	/// by finding the gaps in the discrete interval tree formed by the
 	/// intersection of all the intervals associated with every identifier
-	/// inside the structure except the given identifier whose intervals
-	/// are ignored.
-	///
-	/// See [`Gqdit::gaps_no_identifier`] to not ignore any identifiers.
+	/// inside the structure.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
+	/// This is the same as [`Gqdit::gaps_ignoring`] with no identifiers.
 	///
 	/// # Examples
 	/// ```
@@ -118,38 +119,21 @@ where
 	/// 	[ii(7, 9), iu(41)]
 	/// );
 	/// ```
-	pub fn gaps_no_identifier<Q>(&self, interval: Q) -> Vec<K>
+	pub fn gaps_no_identifier<Q, M>(&self, interval: Q) -> Vec<K>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		self.inner
-			.overlapping(interval)
-			.filter_map(|(inner_interval, other_specifiers)| {
-				if other_specifiers.is_empty() {
-					Some(inner_interval)
-				} else {
-					None
-				}
-			})
-			.copied()
-			.collect()
+		self.gaps_ignoring(&BTreeSet::new(), interval)
 	}
 
 	/// Returns a [`Vec`] of intervals which are equivalent to those found
 	/// by finding the gaps in the discrete interval tree formed by the
 	/// intersection of all the intervals associated with every identifier
-	/// inside the structure.
-	///
-	/// See [`Gqdit::gaps_with_identifier`] to ignore intervals from a
-	/// specific identifier.
-	///
-	/// # Panics
+	/// inside the structure except the given identifier whose intervals
+	/// are ignored.
 	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
+	/// This is the same as [`Gqdit::gaps_ignoring`] with just the given
+	/// identifier.
 	///
 	/// # Examples
 	/// ```
@@ -169,60 +153,102 @@ where
 	/// 	[ii(5, 9), iu(41)]
 	/// );
 	/// ```
-	pub fn gaps_with_identifier<Q>(&self, identifier: D, interval: Q) -> Vec<K>
+	pub fn gaps_with_identifier<Q, M>(
+		&self,
+		identifier: D,
+		interval: Q,
+	) -> Vec<K>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		let valid_gaps = self
-			.inner
-			.overlapping(interval)
-			.filter_map(move |(inner_interval, other_identifiers)| {
-				if valid_identifier(Some(identifier), other_identifiers) {
-					Some(inner_interval)
-				} else {
-					None
-				}
-			})
-			.copied();
-		//we don't want end ones as they are
-		//handled separately
-		let non_end_gaps = valid_gaps.filter(|gap| {
-			!gap.contains_point(interval.start())
-				&& !gap.contains_point(interval.end())
-		});
-
-		//instead of using possibly-partial end gaps we will
-		//replace them with completely_iterated gaps
-		//expanded on both sides outwardly only not inwardly
-		let mut left_gap =
-			self.expand_gaps_at_point_left(identifier, interval.start());
-		let mut right_gap =
-			self.expand_gaps_at_point_right(identifier, interval.end());
-		//if they refer to the save gap then merge them
-		if let (Some(left), Some(right)) = (left_gap.as_mut(), right_gap) {
-			if overlaps_ordered(*left, right) {
-				*left = K::from(merge_ordered(*left, right));
-				right_gap = None;
-			}
-		}
+		self.gaps_ignoring(&BTreeSet::from([identifier]), interval)
+	}
 
-		//then we need to chain these iterators together and
-		//progressively merge touching gaps
-		let all_non_merged_gaps =
-			left_gap.into_iter().chain(non_end_gaps).chain(right_gap);
-
-		//the final proper merged result
-		all_non_merged_gaps
-			.coalesce(|x, y| {
-				if touches_ordered(x, y) {
-					Ok(K::from(merge_ordered(x, y)))
-				} else {
-					Err((x, y))
-				}
-			})
-			.collect()
+	/// Returns a [`Vec`] of intervals which are equivalent to those found
+	/// by finding the gaps in the discrete interval tree formed by the
+	/// intersection of all the intervals associated with every identifier
+	/// inside the structure except the given identifiers whose intervals
+	/// are ignored.
+	///
+	/// See [`Gqdit::gaps_where`] to choose which identifiers to ignore
+	/// with a predicate instead.
+	///
+	/// # Examples
+	/// ```
+	/// use std::collections::BTreeSet;
+	///
+	/// use nodit::interval::{ii, iu, ui};
+	/// use nodit::Gqdit;
+	///
+	/// let mut map = Gqdit::new();
+	///
+	/// map.insert(BTreeSet::from([0_u8]), ii(0, 4));
+	/// map.insert(BTreeSet::from([2_u8]), ii(2, 6));
+	/// map.insert(BTreeSet::from([4_u8]), ii(10, 40));
+	///
+	/// assert_eq!(
+	/// 	map.gaps_ignoring(&BTreeSet::from([0, 2]), ii(0, 100)),
+	/// 	[ui(9), iu(41)]
+	/// );
+	/// ```
+	pub fn gaps_ignoring<Q, M>(
+		&self,
+		identifiers: &BTreeSet<D>,
+		interval: Q,
+	) -> Vec<K>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.gaps_where(
+			|other_identifiers| {
+				valid_identifiers(identifiers, other_identifiers)
+			},
+			interval,
+		)
+	}
+
+	/// Returns a [`Vec`] of intervals which are equivalent to those found
+	/// by finding the gaps in the discrete interval tree formed by the
+	/// intersection of all the intervals associated with every identifier
+	/// inside the structure, where a point counts as a gap if the given
+	/// predicate returns `true` for the set of identifiers with intervals
+	/// overlapping it.
+	///
+	/// The predicate is also called with the empty set for the points not
+	/// in any identifier's intervals.
+	///
+	/// # Examples
+	/// ```
+	/// use std::collections::BTreeSet;
+	///
+	/// use nodit::interval::{ii, iu, ui};
+	/// use nodit::Gqdit;
+	///
+	/// let mut map = Gqdit::new();
+	///
+	/// map.insert(BTreeSet::from([0_u8]), ii(0, 4));
+	/// map.insert(BTreeSet::from([2_u8]), ii(2, 6));
+	/// map.insert(BTreeSet::from([4_u8]), ii(10, 40));
+	///
+	/// //ignore the even identifiers below 4
+	/// assert_eq!(
+	/// 	map.gaps_where(|ids| ids.iter().all(|id| *id < 4), ii(0, 100)),
+	/// 	[ui(9), iu(41)]
+	/// );
+	/// //points with at most one identifier are gaps
+	/// assert_eq!(
+	/// 	map.gaps_where(|ids| ids.len() <= 1, ii(0, 100)),
+	/// 	[ui(1), iu(5)]
+	/// );
+	/// ```
+	pub fn gaps_where<F, Q, M>(&self, predicate: F, interval: Q) -> Vec<K>
+	where
+		F: Fn(&BTreeSet<D>) -> bool,
+		Q: IntoInterval<I, M>,
+	{
+		query_interval(interval).map_or_else(Vec::new, |interval| {
+			gaps_where(&self.inner, predicate, interval)
+		})
 	}
 
 	/// Cuts the given `interval` out of all the interval sets associated with the given
@@ -250,39 +276,15 @@ where
 	/// 	[ii(5, 20), iu(41)]
 	/// );
 	/// ```
-	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	pub fn cut_with_identifiers<Q>(
+	pub fn cut_with_identifiers<Q, M>(
 		&mut self,
 		identifiers: BTreeSet<D>,
 		interval: Q,
 	) where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		if identifiers.is_empty() {
-			return;
-		}
-
-		for (cut_interval, mut cut_identifiers) in self
-			.inner
-			.cut(interval)
-			//to soothe the borrow checker
-			.collect::<Vec<_>>()
-		{
-			cut_identifiers.retain(|i| !identifiers.contains(i));
-
-			self.inner
-				.insert_merge_touching_if_values_equal(
-					cut_interval,
-					cut_identifiers,
-				)
-				.unwrap_or_else(|_| panic!());
+		if let Some(interval) = query_interval(interval) {
+			cut_with_identifiers(&mut self.inner, identifiers, interval);
 		}
 	}
 
@@ -308,32 +310,12 @@ where
 	/// 	[ii(0, 20), iu(41)]
 	/// );
 	/// ```
-	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	pub fn cut_all_identifiers<Q>(&mut self, interval: Q)
+	pub fn cut_all_identifiers<Q, M>(&mut self, interval: Q)
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		for (cut_interval, mut cut_identifiers) in self
-			.inner
-			.cut(interval)
-			//to soothe the borrow checker
-			.collect::<Vec<_>>()
-		{
-			cut_identifiers.clear();
-
-			self.inner
-				.insert_merge_touching_if_values_equal(
-					cut_interval,
-					cut_identifiers,
-				)
-				.unwrap_or_else(|_| panic!());
+		if let Some(interval) = query_interval(interval) {
+			cut_all_identifiers(&mut self.inner, interval);
 		}
 	}
 
@@ -365,50 +347,17 @@ where
 	/// map.insert(BTreeSet::from([0_u8]), ii(0, 4));
 	/// ```
 	pub fn insert(&mut self, identifiers: BTreeSet<D>, interval: K) {
-		invalid_interval_panic(interval);
-
-		if identifiers.is_empty() {
-			return;
-		}
-
-		//first we extend the overlapping partial
-		//intervals with the
-		//other_specifiers and then insert them
-		//back into the data_structure with
-		//insert_merge_touching_if_values_equal to prevent
-		//fragmentation
-		//
-		//optimisation: do this without cutting and re-inserting
-		//using overlapping_mut or something
-		let cut = self
-			.inner
-			.cut(interval)
-			//to soothe the borrow checker
-			.collect::<Vec<_>>()
-			.into_iter();
-
-		let extended_cut = cut.map(|(cut_interval, mut cut_identifiers)| {
-			cut_identifiers.extend(identifiers.clone());
-			(cut_interval, cut_identifiers)
-		});
-
-		for (extended_interval, extended_identifiers) in extended_cut {
-			self.inner
-				.insert_merge_touching_if_values_equal(
-					extended_interval,
-					extended_identifiers,
-				)
-				.unwrap_or_else(|_| panic!());
-		}
+		insert(&mut self.inner, identifiers, interval);
 	}
 
-	/// Appends all the intervals from `other` to `self`.
+	/// Appends all the intervals from `other` to `self`, leaving `other`
+	/// with no identifiers.
 	///
 	/// # Examples
 	/// ```
 	/// use std::collections::BTreeSet;
 	///
-	/// use nodit::interval::ii;
+	/// use nodit::interval::{ii, uu};
 	/// use nodit::Gqdit;
 	///
 	/// let mut map = Gqdit::new();
@@ -420,11 +369,15 @@ where
 	/// map.append(&mut other);
 	///
 	/// assert_eq!(map.gaps_no_identifier(ii(0, 10)), [ii(5, 5)]);
+	/// assert_eq!(other.gaps_no_identifier(ii(0, 10)), [uu()]);
+	/// assert_eq!(other.check_invariants(), Ok(()));
 	/// ```
 	pub fn append(&mut self, other: &mut Self) {
 		for (interval, identifiers) in other.inner.remove_overlapping(uu()) {
 			self.insert(identifiers, interval);
 		}
+		//the drained store no longer covers the whole domain
+		*other = Self::default();
 	}
 
 	/// Return all the identifiers with intervals overlapping the given
@@ -448,63 +401,367 @@ where
 	/// assert_eq!(map.identifiers_at_point(10), BTreeSet::from([]));
 	/// ```
 	pub fn identifiers_at_point(&self, point: I) -> BTreeSet<D> {
-		self.inner
-			.get_at_point(point)
-			.cloned()
-			.unwrap_or(BTreeSet::new())
+		identifiers_at_point(&self.inner, point)
+	}
+
+	/// Checks that the internal invariants of the structure hold,
+	/// returning the first [`InvariantError`] found if not.
+	///
+	/// As well as the invariants checked by
+	/// [`NoditMap::check_invariants()`] on the underlying map, the
+	/// intervals must cover every point in the domain of the point type,
+	/// with the points not in any identifier's intervals stored with an
+	/// empty set of identifiers.
+	///
+	/// # Examples
+	/// ```
+	/// use std::collections::BTreeSet;
+	///
+	/// use nodit::interval::ii;
+	/// use nodit::Gqdit;
+	///
+	/// let mut map = Gqdit::new();
+	///
+	/// map.insert(BTreeSet::from([0_u8]), ii(0, 4));
+	/// map.insert(BTreeSet::from([2_u8]), ii(2, 6));
+	///
+	/// assert_eq!(map.check_invariants(), Ok(()));
+	/// ```
+	pub fn check_invariants(&self) -> Result<(), InvariantError<I>> {
+		self.inner.check_invariants()?;
+
+		check_coverage(&self.inner)
 	}
+}
 
-	fn expand_gaps_at_point_right(&self, identifier: D, point: I) -> Option<K> {
-		let overlapping_right = self.inner.overlapping(iu(point));
+/// The operations [`Gqdit`] and [`persistent::PersistentGqdit`] need from the map
+/// their intervals are stored in, so that they can share the same
+/// algorithms.
+trait Store<I, K, D> {
+	fn overlapping<'a>(
+		&'a self,
+		interval: Interval<I>,
+	) -> impl DoubleEndedIterator<Item = (&'a K, &'a BTreeSet<D>)>
+	where
+		K: 'a,
+		D: 'a;
+	fn cut(&mut self, interval: Interval<I>) -> Vec<(K, BTreeSet<D>)>;
+	/// Requires that `interval` doesn't overlap any interval in the store
+	fn insert_merging(&mut self, interval: K, identifiers: BTreeSet<D>);
+	fn get_at_point(&self, point: I) -> Option<&BTreeSet<D>>;
+}
 
-		overlapping_right
-			.take_while(|(_, other_identifiers)| {
-				valid_identifier(Some(identifier), other_identifiers)
-			})
-			.map(|(x, _)| *x)
-			.coalesce(|x, y| {
-				//since there are no gaps we know they will always
-				//touch
+impl<I, K, D> Store<I, K, D> for NoditMap<I, K, BTreeSet<D>>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	fn overlapping<'a>(
+		&'a self,
+		interval: Interval<I>,
+	) -> impl DoubleEndedIterator<Item = (&'a K, &'a BTreeSet<D>)>
+	where
+		K: 'a,
+		D: 'a,
+	{
+		NoditMap::overlapping(self, interval)
+	}
+	fn cut(&mut self, interval: Interval<I>) -> Vec<(K, BTreeSet<D>)> {
+		NoditMap::cut(self, interval).collect()
+	}
+	fn insert_merging(&mut self, interval: K, identifiers: BTreeSet<D>) {
+		self.insert_merge_touching_if_values_equal(interval, identifiers)
+			.unwrap_or_else(|_| unreachable!("interval overlaps the store"));
+	}
+	fn get_at_point(&self, point: I) -> Option<&BTreeSet<D>> {
+		NoditMap::get_at_point(self, point)
+	}
+}
+
+impl<I, K, D> Store<I, K, D> for PersistentNoditMap<I, K, BTreeSet<D>>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	fn overlapping<'a>(
+		&'a self,
+		interval: Interval<I>,
+	) -> impl DoubleEndedIterator<Item = (&'a K, &'a BTreeSet<D>)>
+	where
+		K: 'a,
+		D: 'a,
+	{
+		PersistentNoditMap::overlapping(self, interval)
+	}
+	fn cut(&mut self, interval: Interval<I>) -> Vec<(K, BTreeSet<D>)> {
+		PersistentNoditMap::cut(self, interval).collect()
+	}
+	fn insert_merging(&mut self, interval: K, identifiers: BTreeSet<D>) {
+		self.insert_merge_touching_if_values_equal(interval, identifiers)
+			.unwrap_or_else(|_| unreachable!("interval overlaps the store"));
+	}
+	fn get_at_point(&self, point: I) -> Option<&BTreeSet<D>> {
+		PersistentNoditMap::get_at_point(self, point)
+	}
+}
+
+fn gaps_where<I, K, D, S, F>(
+	store: &S,
+	predicate: F,
+	interval: Interval<I>,
+) -> Vec<K>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	S: Store<I, K, D>,
+	F: Fn(&BTreeSet<D>) -> bool,
+{
+	let valid_gaps = store
+		.overlapping(interval)
+		.filter_map(|(inner_interval, other_identifiers)| {
+			if predicate(other_identifiers) {
+				Some(inner_interval)
+			} else {
+				None
+			}
+		})
+		.copied();
+	//we don't want end ones as they are
+	//handled separately
+	let non_end_gaps = valid_gaps.filter(|gap| {
+		!gap.contains_point(interval.start())
+			&& !gap.contains_point(interval.end())
+	});
+
+	//instead of using possibly-partial end gaps we will
+	//replace them with completely_iterated gaps
+	//expanded on both sides outwardly only not inwardly
+	let mut left_gap =
+		expand_gaps_at_point_left(store, &predicate, interval.start());
+	let mut right_gap =
+		expand_gaps_at_point_right(store, &predicate, interval.end());
+	//if they refer to the save gap then merge them
+	if let (Some(left), Some(right)) = (left_gap.as_mut(), right_gap) {
+		if overlaps_ordered(*left, right) {
+			*left = K::from(merge_ordered(*left, right));
+			right_gap = None;
+		}
+	}
+
+	//then we need to chain these iterators together and
+	//progressively merge touching gaps
+	let all_non_merged_gaps =
+		left_gap.into_iter().chain(non_end_gaps).chain(right_gap);
+
+	//the final proper merged result
+	all_non_merged_gaps
+		.coalesce(|x, y| {
+			if touches_ordered(x, y) {
 				Ok(K::from(merge_ordered(x, y)))
-			})
-			.next()
+			} else {
+				Err((x, y))
+			}
+		})
+		.collect()
+}
+
+fn cut_with_identifiers<I, K, D, S>(
+	store: &mut S,
+	identifiers: BTreeSet<D>,
+	interval: Interval<I>,
+) where
+	I: PointType,
+	D: IdType,
+	S: Store<I, K, D>,
+{
+	if identifiers.is_empty() {
+		return;
 	}
-	fn expand_gaps_at_point_left(&self, identifier: D, point: I) -> Option<K> {
-		//we are going in reverse since we are going left
-		let overlapping_left = self.inner.overlapping(ui(point)).rev();
 
-		overlapping_left
-			.take_while(|(_, other_identifiers)| {
-				valid_identifier(Some(identifier), other_identifiers)
-			})
-			.map(|(x, _)| *x)
-			.coalesce(|x, y| {
-				//since we are going from right to left these will
-				//be reversed too
-				//
-				//since there are no gaps we know they will always
-				//touch
-				Ok(K::from(merge_ordered(y, x)))
-			})
-			.next()
+	update_identifiers(store, interval, |cut_identifiers| {
+		cut_identifiers.retain(|i| !identifiers.contains(i))
+	});
+}
+
+fn cut_all_identifiers<I, K, D, S>(store: &mut S, interval: Interval<I>)
+where
+	I: PointType,
+	S: Store<I, K, D>,
+{
+	update_identifiers(store, interval, BTreeSet::clear);
+}
+
+fn insert<I, K, D, S>(store: &mut S, identifiers: BTreeSet<D>, interval: K)
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+	S: Store<I, K, D>,
+{
+	invalid_interval_panic(interval);
+
+	if identifiers.is_empty() {
+		return;
 	}
+
+	let interval = Interval {
+		start: interval.start(),
+		end: interval.end(),
+	};
+	update_identifiers(store, interval, |cut_identifiers| {
+		cut_identifiers.extend(identifiers.iter().copied())
+	});
+}
+
+/// Applies `update` to the identifiers of every part of the store inside
+/// `interval`.
+fn update_identifiers<I, K, D, S, F>(
+	store: &mut S,
+	interval: Interval<I>,
+	update: F,
+) where
+	S: Store<I, K, D>,
+	F: Fn(&mut BTreeSet<D>),
+{
+	//we cut out the overlapping partial intervals, update their
+	//identifiers and then insert them back into the store with
+	//insert_merge_touching_if_values_equal to prevent fragmentation
+	//
+	//optimisation: do this without cutting and re-inserting
+	//using overlapping_mut or something
+	for (cut_interval, mut cut_identifiers) in store.cut(interval) {
+		update(&mut cut_identifiers);
+
+		store.insert_merging(cut_interval, cut_identifiers);
+	}
+}
+
+fn identifiers_at_point<I, K, D, S>(store: &S, point: I) -> BTreeSet<D>
+where
+	D: IdType,
+	S: Store<I, K, D>,
+{
+	store
+		.get_at_point(point)
+		.cloned()
+		.unwrap_or(BTreeSet::new())
+}
+
+/// Checks that the intervals in the store cover every point in the
+/// domain of the point type.
+fn check_coverage<I, K, D, S>(store: &S) -> Result<(), InvariantError<I>>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	S: Store<I, K, D>,
+{
+	//the last interval, which the next one should start just after
+	let mut previous: Option<Interval<I>> = None;
+	for (interval, _) in store.overlapping(Interval::from(uu())) {
+		let interval = Interval {
+			start: interval.start(),
+			end: interval.end(),
+		};
+		let expected = match previous {
+			Some(previous) if interval.start() <= previous.end() => {
+				return Err(InvariantError::Overlap {
+					first: previous,
+					second: interval,
+				});
+			}
+			Some(previous) => previous
+				.end()
+				.up()
+				.expect("the interval starts after the previous one"),
+			None => I::MIN,
+		};
+		//the store is sorted so interval starts after expected
+		if interval.start() != expected {
+			return Err(InvariantError::Gap {
+				gap: Interval {
+					start: expected,
+					end: interval
+						.start()
+						.down()
+						.expect("the interval starts after expected"),
+				},
+			});
+		}
+		previous = Some(interval);
+	}
+
+	match previous.map_or(Some(I::MIN), |previous| previous.end().up()) {
+		Some(start) => Err(InvariantError::Gap {
+			gap: Interval {
+				start,
+				end: I::MAX,
+			},
+		}),
+		None => Ok(()),
+	}
+}
+
+fn expand_gaps_at_point_right<I, K, D, S, F>(
+	store: &S,
+	predicate: &F,
+	point: I,
+) -> Option<K>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	S: Store<I, K, D>,
+	F: Fn(&BTreeSet<D>) -> bool,
+{
+	let overlapping_right = store.overlapping(Interval::from(iu(point)));
+
+	overlapping_right
+		.take_while(|(_, other_identifiers)| predicate(other_identifiers))
+		.map(|(x, _)| *x)
+		.coalesce(|x, y| {
+			//since there are no gaps we know they will always
+			//touch
+			Ok(K::from(merge_ordered(x, y)))
+		})
+		.next()
+}
+fn expand_gaps_at_point_left<I, K, D, S, F>(
+	store: &S,
+	predicate: &F,
+	point: I,
+) -> Option<K>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	S: Store<I, K, D>,
+	F: Fn(&BTreeSet<D>) -> bool,
+{
+	//we are going in reverse since we are going left
+	let overlapping_left = store.overlapping(Interval::from(ui(point))).rev();
+
+	overlapping_left
+		.take_while(|(_, other_identifiers)| predicate(other_identifiers))
+		.map(|(x, _)| *x)
+		.coalesce(|x, y| {
+			//since we are going from right to left these will
+			//be reversed too
+			//
+			//since there are no gaps we know they will always
+			//touch
+			Ok(K::from(merge_ordered(y, x)))
+		})
+		.next()
 }
 
-fn valid_identifier<I>(
-	with_identifier: Option<I>,
+fn valid_identifiers<I>(
+	ignored_identifiers: &BTreeSet<I>,
 	other_identifiers: &BTreeSet<I>,
 ) -> bool
 where
 	I: Eq + Ord,
 {
-	match with_identifier {
-		Some(identifier) => {
-			other_identifiers.is_empty()
-				|| (other_identifiers.len() == 1
-					&& other_identifiers.contains(&identifier))
-		}
-		None => other_identifiers.is_empty(),
-	}
+	other_identifiers.is_subset(ignored_identifiers)
 }
 /// Requires that self comes before other
 fn merge_ordered<I, A, B>(a: A, b: B) -> Interval<I>
@@ -513,7 +770,10 @@ where
 	A: IntervalType<I>,
 	B: IntervalType<I>,
 {
-	ii(a.start(), b.end())
+	Interval {
+		start: a.start(),
+		end: b.end(),
+	}
 }
 /// Requires that self comes before other
 fn overlaps_ordered<I, A, B>(a: A, b: B) -> bool
@@ -531,7 +791,7 @@ where
 	A: IntervalType<I>,
 	B: IntervalType<I>,
 {
-	a.end() == b.start().down().unwrap()
+	a.end().up() == Some(b.start())
 }
 
 impl<I, K, D> PartialEq for Gqdit<I, K, D>
@@ -552,7 +812,7 @@ where
 {
 	fn default() -> Self {
 		let mut map = NoditMap::new();
-		map.insert_strict(K::from(uu()), BTreeSet::new())
+		map.insert_strict(K::from(Interval::from(uu())), BTreeSet::new())
 			.unwrap_or_else(|_| panic!());
 		Self { inner: map }
 	}
@@ -561,6 +821,7 @@ where
 #[cfg(feature = "serde")]
 mod serde {
 	use alloc::collections::BTreeSet;
+	use core::fmt;
 
 	use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -584,7 +845,7 @@ mod serde {
 	impl<'de, I, K, D> Deserialize<'de> for Gqdit<I, K, D>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 		D: IdType,
 		BTreeSet<D>: Deserialize<'de>,
 	{
@@ -596,3 +857,129 @@ mod serde {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ii, uu};
+	use crate::utils::test_helpers::{I8_EDGES, U8_EDGES, edge_intervals};
+	use crate::{Countable, InclusiveInterval};
+
+	#[test]
+	fn empty_query_tests() {
+		let mut map: Gqdit<u8, Interval<u8>, u8> = Gqdit::new();
+		map.insert(BTreeSet::from([0]), ii(0, 4).into());
+
+		assert!(map.gaps_no_identifier(5..5).is_empty());
+		assert!(map.gaps_with_identifier(0, ..0).is_empty());
+		map.cut_with_identifiers(BTreeSet::from([0]), 2..2);
+		map.cut_all_identifiers(..0);
+		assert_eq!(map.gaps_no_identifier(uu()), [ii(5, u8::MAX)]);
+		assert_eq!(map.check_invariants(), Ok(()));
+	}
+
+	#[test]
+	fn check_invariants_tests() {
+		let mut map: Gqdit<u8, Interval<u8>, u8> = Gqdit::new();
+		assert_eq!(map.check_invariants(), Ok(()));
+
+		map.insert(BTreeSet::from([0]), ii(0, 4).into());
+		map.insert(BTreeSet::from([1]), ii(2, 6).into());
+		map.cut_with_identifiers(BTreeSet::from([0]), ii(1, 1));
+		assert_eq!(map.check_invariants(), Ok(()));
+
+		let _ = map.inner.remove_overlapping(ii(5, 6)).count();
+		assert_eq!(
+			map.check_invariants(),
+			Err(InvariantError::Gap {
+				gap: ii(5, 6).into()
+			})
+		);
+
+		let _ = map.inner.remove_overlapping(uu()).count();
+		assert_eq!(
+			map.check_invariants(),
+			Err(InvariantError::Gap { gap: uu().into() })
+		);
+	}
+
+	#[test]
+	fn domain_edge_tests() {
+		domain_edge_tests_for(&U8_EDGES);
+		domain_edge_tests_for(&I8_EDGES);
+	}
+	fn domain_edge_tests_for<I>(edges: &[I])
+	where
+		I: PointType + Countable + core::fmt::Debug,
+	{
+		let domain = uu::<I>().points().collect::<Vec<_>>();
+		//the points of interval where the predicate holds for the
+		//identifiers at that point
+		let gap_points = |identifiers: &[BTreeSet<u8>],
+		                  predicate: &dyn Fn(&BTreeSet<u8>) -> bool,
+		                  interval: Interval<I>| {
+			domain
+				.iter()
+				.zip(identifiers)
+				.filter(|(point, identifiers)| {
+					interval.contains_point(**point) && predicate(identifiers)
+				})
+				.map(|(point, _)| *point)
+				.collect::<Vec<_>>()
+		};
+		let trimmed = |gaps: Vec<Interval<I>>, interval: Interval<I>| {
+			domain
+				.iter()
+				.copied()
+				.filter(|point| {
+					interval.contains_point(*point)
+						&& gaps.iter().any(|gap| gap.contains_point(*point))
+				})
+				.collect::<Vec<_>>()
+		};
+
+		let intervals = edge_intervals(edges);
+
+		for a in intervals.iter().copied() {
+			for b in intervals.iter().copied() {
+				let mut map = Gqdit::new();
+				map.insert(BTreeSet::from([0]), a);
+				map.insert(BTreeSet::from([1]), b);
+				assert_eq!(map.check_invariants(), Ok(()));
+
+				let identifiers = domain
+					.iter()
+					.map(|point| map.identifiers_at_point(*point))
+					.collect::<Vec<_>>();
+				for c in intervals.iter().copied() {
+					assert_eq!(
+						trimmed(map.gaps_no_identifier(c), c),
+						gap_points(&identifiers, &|ids| ids.is_empty(), c)
+					);
+					assert_eq!(
+						trimmed(map.gaps_with_identifier(0, c), c),
+						gap_points(&identifiers, &|ids| !ids.contains(&1), c)
+					);
+					let both = BTreeSet::from([0, 1]);
+					assert_eq!(
+						trimmed(map.gaps_ignoring(&both, c), c),
+						gap_points(&identifiers, &|_| true, c)
+					);
+					assert_eq!(
+						trimmed(map.gaps_where(|ids| ids.len() == 1, c), c),
+						gap_points(&identifiers, &|ids| ids.len() == 1, c)
+					);
+				}
+
+				map.cut_with_identifiers(BTreeSet::from([1]), a);
+				assert_eq!(map.check_invariants(), Ok(()));
+				map.cut_all_identifiers(b);
+				assert_eq!(map.check_invariants(), Ok(()));
+			}
+		}
+
+		assert!(!touches_ordered(ii(I::MIN, I::MIN), ii(I::MIN, I::MAX)));
+	}
+}
@@ -0,0 +1,271 @@
+//! A module containing [`PersistentGqdit`].
+//!
+//! Since [`PersistentGqdit`] has the same semantics as [`Gqdit`], most of
+//! the methods' docs will point towards the equivalent method's docs on
+//! [`Gqdit`] to prevent inconsistency.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use super::{
+	check_coverage, cut_all_identifiers, cut_with_identifiers, gaps_where,
+	identifiers_at_point, insert, valid_identifiers,
+};
+use crate::interval::uu;
+use crate::utils::query_interval;
+use crate::{
+	Gqdit, IdType, IntervalType, IntoInterval, InvariantError, NoditMap,
+	PersistentNoditMap, PointType,
+};
+
+/// A persistent version of [`Gqdit`] whose clones share their structure.
+///
+/// Cloning a [`PersistentGqdit`] is `O(1)` and every update only copies
+/// the parts of the underlying [`PersistentNoditMap`] it changes, leaving
+/// any other clones untouched. This is useful for searches such as the
+/// AGV time-pathing search [`Gqdit`] was developed for, which would
+/// otherwise have to clone the whole structure at every search node.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is a interval over.
+///
+/// `K` is the generic type parameter for the interval type stored in the data-structure.
+///
+/// `D` is the generic type parameter for the identifiers associated with the
+/// interval sets in the data-structure.
+///
+/// Phrasing it another way: `I` is the point type, `K` is the interval type, and `D` is the identifier type.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use nodit::interval::{ii, iu};
+/// use nodit::PersistentGqdit;
+///
+/// let mut base = PersistentGqdit::new();
+/// base.insert(BTreeSet::from([0_u8]), ii(0, 4));
+///
+/// let mut branch = base.clone();
+/// branch.insert(BTreeSet::from([1_u8]), ii(10, 40));
+///
+/// assert_eq!(base.gaps_no_identifier(ii(0, 100)), [iu(5)]);
+/// assert_eq!(
+/// 	branch.gaps_no_identifier(ii(0, 100)),
+/// 	[ii(5, 9), iu(41)]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PersistentGqdit<I, K, D> {
+	inner: PersistentNoditMap<I, K, BTreeSet<D>>,
+}
+
+impl<I, K, D> PersistentGqdit<I, K, D>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	/// See [`Gqdit::new()`] for more details.
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// See [`Gqdit::gaps_no_identifier()`] for more details.
+	pub fn gaps_no_identifier<Q, M>(&self, interval: Q) -> Vec<K>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.gaps_ignoring(&BTreeSet::new(), interval)
+	}
+	/// See [`Gqdit::gaps_with_identifier()`] for more details.
+	pub fn gaps_with_identifier<Q, M>(
+		&self,
+		identifier: D,
+		interval: Q,
+	) -> Vec<K>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.gaps_ignoring(&BTreeSet::from([identifier]), interval)
+	}
+	/// See [`Gqdit::gaps_ignoring()`] for more details.
+	pub fn gaps_ignoring<Q, M>(
+		&self,
+		identifiers: &BTreeSet<D>,
+		interval: Q,
+	) -> Vec<K>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.gaps_where(
+			|other_identifiers| {
+				valid_identifiers(identifiers, other_identifiers)
+			},
+			interval,
+		)
+	}
+	/// See [`Gqdit::gaps_where()`] for more details.
+	pub fn gaps_where<F, Q, M>(&self, predicate: F, interval: Q) -> Vec<K>
+	where
+		F: Fn(&BTreeSet<D>) -> bool,
+		Q: IntoInterval<I, M>,
+	{
+		query_interval(interval).map_or_else(Vec::new, |interval| {
+			gaps_where(&self.inner, predicate, interval)
+		})
+	}
+	/// See [`Gqdit::cut_with_identifiers()`] for more details.
+	pub fn cut_with_identifiers<Q, M>(
+		&mut self,
+		identifiers: BTreeSet<D>,
+		interval: Q,
+	) where
+		Q: IntoInterval<I, M>,
+	{
+		if let Some(interval) = query_interval(interval) {
+			cut_with_identifiers(&mut self.inner, identifiers, interval);
+		}
+	}
+	/// See [`Gqdit::cut_all_identifiers()`] for more details.
+	pub fn cut_all_identifiers<Q, M>(&mut self, interval: Q)
+	where
+		Q: IntoInterval<I, M>,
+	{
+		if let Some(interval) = query_interval(interval) {
+			cut_all_identifiers(&mut self.inner, interval);
+		}
+	}
+	/// See [`Gqdit::insert()`] for more details.
+	pub fn insert(&mut self, identifiers: BTreeSet<D>, interval: K) {
+		insert(&mut self.inner, identifiers, interval);
+	}
+	/// See [`Gqdit::append()`] for more details.
+	pub fn append(&mut self, other: &mut Self) {
+		for (interval, identifiers) in other.inner.remove_overlapping(uu()) {
+			self.insert(identifiers, interval);
+		}
+		//the drained store no longer covers the whole domain
+		*other = Self::default();
+	}
+	/// See [`Gqdit::identifiers_at_point()`] for more details.
+	pub fn identifiers_at_point(&self, point: I) -> BTreeSet<D> {
+		identifiers_at_point(&self.inner, point)
+	}
+	/// Checks that the intervals cover every point in the domain of the
+	/// point type, returning the first [`InvariantError`] found if not.
+	///
+	/// See [`Gqdit::check_invariants()`] for more details.
+	pub fn check_invariants(&self) -> Result<(), InvariantError<I>> {
+		check_coverage(&self.inner)
+	}
+}
+
+// Trait Impls ==========================
+
+impl<I, K, D> Clone for PersistentGqdit<I, K, D>
+where
+	I: PointType,
+{
+	fn clone(&self) -> Self {
+		PersistentGqdit {
+			inner: self.inner.clone(),
+		}
+	}
+}
+
+impl<I, K, D> PartialEq for PersistentGqdit<I, K, D>
+where
+	I: PointType,
+	K: PartialEq,
+	D: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<I, K, D> Default for PersistentGqdit<I, K, D>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	fn default() -> Self {
+		Gqdit::default().into()
+	}
+}
+
+impl<I, K, D> From<Gqdit<I, K, D>> for PersistentGqdit<I, K, D>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	fn from(gqdit: Gqdit<I, K, D>) -> Self {
+		PersistentGqdit {
+			inner: PersistentNoditMap::from(gqdit.inner),
+		}
+	}
+}
+
+impl<I, K, D> From<PersistentGqdit<I, K, D>> for Gqdit<I, K, D>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	D: IdType,
+{
+	fn from(persistent: PersistentGqdit<I, K, D>) -> Self {
+		Gqdit {
+			inner: NoditMap::from(persistent.inner),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ii, iu, ui};
+	use crate::Interval;
+
+	#[test]
+	fn clone_and_modify_tests() {
+		let mut base: PersistentGqdit<u8, Interval<u8>, u8> =
+			PersistentGqdit::new();
+		base.insert(BTreeSet::from([0]), ii(0, 4).into());
+		base.insert(BTreeSet::from([1]), ii(2, 6).into());
+		let snapshot = Gqdit::from(base.clone());
+
+		let mut branch = base.clone();
+		branch.insert(BTreeSet::from([2]), ii(10, 40).into());
+		branch.cut_with_identifiers(BTreeSet::from([0]), ii(0, 1));
+		branch.cut_all_identifiers(ii(5, 5));
+		let mut other = PersistentGqdit::new();
+		other.insert(BTreeSet::from([3]), ii(50, 60).into());
+		branch.append(&mut other);
+		assert_eq!(other.gaps_no_identifier(uu()), [uu()]);
+		assert_eq!(other.check_invariants(), Ok(()));
+
+		//the original is unaffected by the changes to the clone
+		assert_eq!(Gqdit::from(base.clone()), snapshot);
+		assert_eq!(base.gaps_no_identifier(uu()), [iu(7)]);
+		assert_eq!(base.identifiers_at_point(0), BTreeSet::from([0]));
+		assert_eq!(base.check_invariants(), Ok(()));
+
+		assert_eq!(
+			branch.gaps_no_identifier(uu()),
+			[ui(1), ii(5, 5), ii(7, 9), ii(41, 49), iu(61)]
+		);
+		assert_eq!(branch.identifiers_at_point(0), BTreeSet::new());
+		assert_eq!(branch.check_invariants(), Ok(()));
+
+		//and gives the same results as a Gqdit with the same changes
+		let mut gqdit = snapshot.clone();
+		gqdit.insert(BTreeSet::from([2]), ii(10, 40).into());
+		gqdit.cut_with_identifiers(BTreeSet::from([0]), ii(0, 1));
+		gqdit.cut_all_identifiers(ii(5, 5));
+		gqdit.insert(BTreeSet::from([3]), ii(50, 60).into());
+		assert_eq!(Gqdit::from(branch), gqdit);
+	}
+}
@@ -5,29 +5,45 @@
 //! which is a bit annoying as you can't import associated function in rust
 //! yet. If you would still like the associated versions I would be happy to
 //! add them as well, just open a PR/Issue.
+//!
+//! The constructors create a [`BoundedInterval`], which unlike
+//! [`Interval`] remembers whether its end-points are included, excluded or
+//! unbounded. Convert it with [`Interval::from()`] if you want an
+//! [`Interval`] instead.
+
+pub mod bounded;
 
-use core::ops::{Bound, Range, RangeBounds, RangeInclusive};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{
+	Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+	RangeToInclusive,
+};
 
-use crate::utils::{invalid_interval_panic, sorted_config, SortedConfig};
-use crate::{IntervalType, PointType};
+use crate::parse::{parse_bounds, ParseError, ParseErrorKind};
+use crate::discrete_finite::{CheckedArithmetic, Countable, SmallCountable};
+use crate::utils::{cut_interval, sorted_config, SortedConfig};
+use crate::{BoundedInterval, IntervalType, PointType};
 
 /// An inclusive interval, only valid intervals can be constructed.
 ///
-/// This interval struct is used throughout this crate for the examples and
-/// tests but can also be used by library users if they don't wish to create
-/// their own interval types.
+/// This interval struct can be used by library users if they don't wish to
+/// create their own interval types and don't need to remember the bounds
+/// of their intervals like [`BoundedInterval`] does.
 ///
-/// To create an `InclusiveInterval` use one of the various contrutor
-/// functions which will all panic if you try to create an invalid interval.
-/// See [`Invalid
+/// To create an `Interval` use one of the various contrutor functions and
+/// convert the [`BoundedInterval`] they return with [`Interval::from()`].
+/// The constructors will all panic if you try to create an invalid
+/// interval. See [`Invalid
 /// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
 /// for more details.
 ///
 /// ```
 /// use nodit::interval::{ee, ii};
+/// use nodit::Interval;
 ///
-/// let inclusive_interval = ii(4, 4);
-/// let exclusive_interval = ee(3, 5);
+/// let inclusive_interval = Interval::from(ii(4, 4));
+/// let exclusive_interval = Interval::from(ee(3, 5));
 ///
 /// assert_eq!(inclusive_interval, exclusive_interval);
 /// ```
@@ -53,8 +69,9 @@ where
 	///
 	/// ```
 	/// use nodit::interval::ii;
+	/// use nodit::Interval;
 	///
-	/// assert_eq!(ii(2, 4).start(), 2);
+	/// assert_eq!(Interval::from(ii(2, 4)).start(), 2);
 	/// ```
 	pub fn start(&self) -> I {
 		self.start
@@ -63,8 +80,9 @@ where
 	///
 	/// ```
 	/// use nodit::interval::ii;
+	/// use nodit::Interval;
 	///
-	/// assert_eq!(ii(2, 4).end(), 4);
+	/// assert_eq!(Interval::from(ii(2, 4)).end(), 4);
 	/// ```
 	pub fn end(&self) -> I {
 		self.end
@@ -105,15 +123,23 @@ where
 	I: PointType,
 {
 	fn from(value: RangeInclusive<I>) -> Self {
-		ii(*value.start(), *value.end())
+		Interval::from(ii(*value.start(), *value.end()))
 	}
 }
+/// # Panics
+///
+/// Panics if the interval ends at the maximum point, as that can't be
+/// excluded by a [`Range`].
 impl<I> From<Interval<I>> for Range<I>
 where
 	I: PointType,
 {
 	fn from(value: Interval<I>) -> Self {
-		value.start..value.end.up().unwrap()
+		value.start..value.end.up().unwrap_or_else(|| {
+			panic!(
+				"an interval ending at the maximum point can't be converted into a Range"
+			)
+		})
 	}
 }
 impl<I> From<Range<I>> for Interval<I>
@@ -121,7 +147,63 @@ where
 	I: PointType,
 {
 	fn from(value: Range<I>) -> Self {
-		ie(value.start, value.end)
+		Interval::from(ie(value.start, value.end))
+	}
+}
+/// Formats the interval in mathematical interval notation, always with
+/// inclusive end-points such as `[3, 6]`.
+///
+/// ```
+/// use nodit::interval::{ie, ii};
+/// use nodit::Interval;
+///
+/// assert_eq!(Interval::from(ii(4, 4)).to_string(), "[4, 4]");
+/// assert_eq!(Interval::from(ie(3, 7)).to_string(), "[3, 6]");
+/// ```
+impl<I> fmt::Display for Interval<I>
+where
+	I: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "[{}, {}]", self.start, self.end)
+	}
+}
+/// Parses an interval in mathematical interval notation such as `[3, 7)`,
+/// normalizing it to inclusive end-points.
+///
+/// Unbounded end-points can be written as `-∞` and `∞` (or `-inf` and
+/// `inf`), and become [`DiscreteFinite::MIN`] and [`DiscreteFinite::MAX`].
+///
+/// [`DiscreteFinite::MIN`]: crate::DiscreteFinite::MIN
+/// [`DiscreteFinite::MAX`]: crate::DiscreteFinite::MAX
+///
+/// ```
+/// use nodit::interval::{ii, ui};
+/// use nodit::{Interval, ParseError, ParseErrorKind};
+///
+/// assert_eq!("[3, 7)".parse::<Interval<u8>>(), Ok(ii(3, 6).into()));
+/// assert_eq!("(-∞, 5]".parse::<Interval<i8>>(), Ok(ui(5).into()));
+/// assert_eq!(
+/// 	"[4, 4)".parse::<Interval<u8>>(),
+/// 	Err(ParseError {
+/// 		offset: 0,
+/// 		kind: ParseErrorKind::EmptyInterval
+/// 	})
+/// );
+/// ```
+impl<I> core::str::FromStr for Interval<I>
+where
+	I: PointType + core::str::FromStr,
+{
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (start, end) = parse_bounds(s)?;
+
+		from_bounds(start, end).map_err(|_| ParseError {
+			offset: s.len() - s.trim_start().len(),
+			kind: ParseErrorKind::EmptyInterval,
+		})
 	}
 }
 
@@ -135,25 +217,18 @@ where
 ///
 /// ```
 /// use nodit::interval::uu;
-/// use nodit::{InclusiveInterval, Interval};
+/// use nodit::BoundedInterval;
 ///
-/// let interval1: Interval<u8> = uu();
-/// let interval2: Interval<u8> = uu();
+/// let interval1: BoundedInterval<u8> = uu();
+/// let interval2: BoundedInterval<u8> = uu();
 ///
 /// assert_eq!(interval1, interval2)
 /// ```
-pub fn uu<I>() -> Interval<I>
+pub fn uu<I>() -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start: I::MIN,
-		end: I::MAX,
-	};
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Unbounded, Bound::Unbounded)
 }
 /// Create an new Unbounded-Included interval.
 ///
@@ -171,15 +246,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ui<I>(end: I) -> Interval<I>
+pub fn ui<I>(end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval { start: I::MIN, end };
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Unbounded, Bound::Included(end))
 }
 /// Create an new Unbounded-Excluded interval.
 ///
@@ -197,18 +268,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ue<I>(end: I) -> Interval<I>
+pub fn ue<I>(end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start: I::MIN,
-		end: end.down().unwrap(),
-	};
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Unbounded, Bound::Excluded(end))
 }
 /// Create an new Included-Unbounded interval.
 ///
@@ -226,15 +290,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn iu<I>(start: I) -> Interval<I>
+pub fn iu<I>(start: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval { start, end: I::MAX };
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Included(start), Bound::Unbounded)
 }
 /// Create an new Excluded-Unbounded interval.
 ///
@@ -252,18 +312,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn eu<I>(start: I) -> Interval<I>
+pub fn eu<I>(start: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start: start.up().unwrap(),
-		end: I::MAX,
-	};
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Excluded(start), Bound::Unbounded)
 }
 /// Create an new Included-Included interval.
 ///
@@ -281,15 +334,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ii<I>(start: I, end: I) -> Interval<I>
+pub fn ii<I>(start: I, end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval { start, end };
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Included(start), Bound::Included(end))
 }
 /// Create an new Included-Excluded interval.
 ///
@@ -307,18 +356,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ie<I>(start: I, end: I) -> Interval<I>
+pub fn ie<I>(start: I, end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start,
-		end: end.down().unwrap(),
-	};
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Included(start), Bound::Excluded(end))
 }
 /// Create an new Excluded-Included interval.
 ///
@@ -336,18 +378,11 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ei<I>(start: I, end: I) -> Interval<I>
+pub fn ei<I>(start: I, end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start: start.up().unwrap(),
-		end,
-	};
-
-	invalid_interval_panic(interval);
-
-	interval
+	bounded(Bound::Excluded(start), Bound::Included(end))
 }
 /// Create an new Excluded-Excluded interval.
 ///
@@ -365,21 +400,28 @@ where
 ///
 /// assert_ne!(interval1, interval2)
 /// ```
-pub fn ee<I>(start: I, end: I) -> Interval<I>
+pub fn ee<I>(start: I, end: I) -> BoundedInterval<I>
 where
 	I: PointType,
 {
-	let interval = Interval {
-		start: start.up().unwrap(),
-		end: end.down().unwrap(),
-	};
-
-	invalid_interval_panic(interval);
+	bounded(Bound::Excluded(start), Bound::Excluded(end))
+}
 
-	interval
+fn bounded<I>(start: Bound<I>, end: Bound<I>) -> BoundedInterval<I>
+where
+	I: PointType,
+{
+	BoundedInterval::new(start, end).unwrap_or_else(|_| {
+		panic!(
+			"invalid interval given to function see here for more details: https://docs.rs/nodit/latest/nodit/#invalid-intervals"
+		)
+	})
 }
 
 /// A interval that has **Inclusive** end-points.
+//valid intervals always contain at least one point so there's no
+//is_empty() to go with len()
+#[allow(clippy::len_without_is_empty)]
 pub trait InclusiveInterval<I>: Copy + From<Interval<I>> {
 	/// The start of `self`, inclusive.
 	///
@@ -536,6 +578,10 @@ pub trait InclusiveInterval<I>: Copy + From<Interval<I>> {
 
 	/// Move `self` by the given `delta` amount upwards.
 	///
+	/// This can overflow, see [`InclusiveInterval::checked_translate()`]
+	/// and [`InclusiveInterval::saturating_translate()`] for alternatives
+	/// that can't.
+	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ee, ie, ii};
@@ -556,8 +602,64 @@ pub trait InclusiveInterval<I>: Copy + From<Interval<I>> {
 		})
 	}
 
+	/// Move `self` by the given `delta` amount, or return `None` if either
+	/// end-point would overflow.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ii, iu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(4_u8, 6).checked_translate(8), Some(ii(12, 14)));
+	/// assert_eq!(ii(-4_i8, 6).checked_translate(-124), Some(ii(-128, -118)));
+	/// assert_eq!(iu(4_u8).checked_translate(1), None);
+	/// ```
+	fn checked_translate(&self, delta: I) -> Option<Self>
+	where
+		I: PointType + CheckedArithmetic,
+		Self: From<Interval<I>>,
+	{
+		Some(Self::from(Interval {
+			start: self.start().checked_add(delta)?,
+			end: self.end().checked_add(delta)?,
+		}))
+	}
+
+	/// Move `self` by the given `delta` amount, clamping each end-point to
+	/// the minimum or maximum point if it would overflow.
+	///
+	/// This means `self` may lose points if it is moved past either end of
+	/// the domain.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(4_u8, 6).saturating_translate(8), ii(12, 14));
+	/// assert_eq!(ii(250_u8, 252).saturating_translate(4), ii(254, 255));
+	/// assert_eq!(ii(-126_i8, 0).saturating_translate(-4), ii(-128, -4));
+	/// ```
+	fn saturating_translate(&self, delta: I) -> Self
+	where
+		I: PointType + CheckedArithmetic,
+		Self: From<Interval<I>>,
+	{
+		Self::from(Interval {
+			start: self.start().saturating_add(delta),
+			end: self.end().saturating_add(delta),
+		})
+	}
+
 	/// The amount from the start to the end of the `self`.
 	///
+	/// This is whatever subtracting the start from the end returns, so for
+	/// date types such as those in the `datetime` module it's a duration.
+	///
+	/// This can overflow, see [`InclusiveInterval::checked_width()`] for
+	/// an alternative that can't, or [`InclusiveInterval::len()`] for the
+	/// number of points in `self`.
+	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ee, ei, ie, ii};
@@ -568,11 +670,441 @@ pub trait InclusiveInterval<I>: Copy + From<Interval<I>> {
 	/// assert_eq!(ei(4, 6).width(), 1);
 	/// assert_eq!(ee(4, 6).width(), 0);
 	/// ```
-	fn width(&self) -> I
+	fn width(&self) -> I::Output
 	where
 		I: PointType,
-		I: core::ops::Sub<Output = I>,
+		I: core::ops::Sub,
 	{
 		self.end() - self.start()
 	}
+
+	/// The amount from the start to the end of the `self`, or `None` if it
+	/// doesn't fit in the point type, as with `uu::<i8>()`.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ii, uu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(4, 6).checked_width(), Some(2));
+	/// assert_eq!(ii(-100_i8, 100).checked_width(), None);
+	/// assert_eq!(uu::<u8>().checked_width(), Some(255));
+	/// ```
+	fn checked_width(&self) -> Option<I>
+	where
+		I: PointType + CheckedArithmetic,
+	{
+		self.end().checked_sub(self.start())
+	}
+
+	/// The number of points in `self`, or `None` if that doesn't fit in a
+	/// `u128`, which is only the case for `uu::<u128>()`.
+	///
+	/// Unlike [`InclusiveInterval::width()`] this counts both end-points
+	/// and can't overflow the point type.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ee, ii, uu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(4, 6).len(), Some(3));
+	/// assert_eq!(ee(4, 6).len(), Some(1));
+	/// assert_eq!(uu::<i8>().len(), Some(256));
+	/// assert_eq!(uu::<u128>().len(), None);
+	/// ```
+	fn len(&self) -> Option<u128>
+	where
+		I: PointType + Countable,
+	{
+		I::steps_between(&self.start(), &self.end())?.checked_add(1)
+	}
+
+	/// Returns the parts of `self` which are not in `other`, the part
+	/// before `other` and the part after it.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ii, iu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(
+	/// 	ii(2, 8).difference(&ii(4, 5)),
+	/// 	(Some(ii(2, 3)), Some(ii(6, 8)))
+	/// );
+	/// assert_eq!(ii(2, 8).difference(&ii(6, 10)), (Some(ii(2, 5)), None));
+	/// assert_eq!(ii(2, 8).difference(&ii(9, 10)), (Some(ii(2, 8)), None));
+	/// assert_eq!(ii(2, 8).difference(&iu(0)), (None, None));
+	/// ```
+	fn difference<Q>(&self, other: &Q) -> (Option<Self>, Option<Self>)
+	where
+		I: PointType,
+		Q: IntervalType<I>,
+		Self: From<Interval<I>>,
+	{
+		let cut_result = cut_interval(*self, *other);
+
+		(
+			cut_result.before_cut.map(Self::from),
+			cut_result.after_cut.map(Self::from),
+		)
+	}
+
+	/// Returns the smallest interval containing both `self` and `other`.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(2, 4).hull(&ii(8, 10)), ii(2, 10));
+	/// assert_eq!(ii(2, 8).hull(&ii(4, 5)), ii(2, 8));
+	/// ```
+	fn hull<Q>(&self, other: &Q) -> Self
+	where
+		I: PointType,
+		Q: IntervalType<I>,
+		Self: From<Interval<I>>,
+	{
+		Self::from(Interval {
+			start: I::min(self.start(), other.start()),
+			end: I::max(self.end(), other.end()),
+		})
+	}
+
+	/// Returns true if `self` and `other` don't overlap but there are no
+	/// points between them.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ie, ii};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(2, 4).touches(&ii(5, 6)), true);
+	/// assert_eq!(ie(2, 5).touches(&ii(5, 6)), true);
+	/// assert_eq!(ii(5, 6).touches(&ii(2, 4)), true);
+	/// assert_eq!(ii(2, 4).touches(&ii(6, 8)), false);
+	/// assert_eq!(ii(2, 5).touches(&ii(5, 6)), false);
+	/// ```
+	fn touches<Q>(&self, other: &Q) -> bool
+	where
+		I: PointType,
+		Q: IntervalType<I>,
+	{
+		self.end().up() == Some(other.start())
+			|| other.end().up() == Some(self.start())
+	}
+
+	/// Splits `self` into the part before `point` and the part from `point`
+	/// onwards.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ii(2, 8).split_at(5), (Some(ii(2, 4)), Some(ii(5, 8))));
+	/// assert_eq!(ii(2, 8).split_at(2), (None, Some(ii(2, 8))));
+	/// assert_eq!(ii(2, 8).split_at(9), (Some(ii(2, 8)), None));
+	/// ```
+	fn split_at(&self, point: I) -> (Option<Self>, Option<Self>)
+	where
+		I: PointType,
+		Self: From<Interval<I>>,
+	{
+		let left = point.down().filter(|_| point > self.start()).map(|end| {
+			Self::from(Interval {
+				start: self.start(),
+				end: I::min(end, self.end()),
+			})
+		});
+		let right = (point <= self.end()).then(|| {
+			Self::from(Interval {
+				start: I::max(point, self.start()),
+				end: self.end(),
+			})
+		});
+
+		(left, right)
+	}
+
+	/// Returns an iterator over every point in `self` in ascending order.
+	///
+	/// The iterator is double-ended so it can be reversed with
+	/// [`Iterator::rev()`], and skipping points with [`Iterator::nth()`] or
+	/// [`Iterator::step_by()`] doesn't walk the skipped points one at a
+	/// time.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ie, ii, iu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// assert_eq!(ie(2, 6).points().collect::<Vec<_>>(), [2, 3, 4, 5]);
+	/// assert_eq!(ii(2, 4).points().rev().collect::<Vec<_>>(), [4, 3, 2]);
+	/// assert_eq!(
+	/// 	iu(0_u8).points().step_by(100).collect::<Vec<_>>(),
+	/// 	[0, 100, 200]
+	/// );
+	/// assert_eq!(ii(2, 6).points().len(), 5);
+	/// ```
+	fn points(&self) -> Points<I>
+	where
+		I: PointType + Countable,
+	{
+		Points {
+			remaining: Some((self.start(), self.end())),
+		}
+	}
+}
+
+/// An iterator over the points of an interval, see
+/// [`InclusiveInterval::points()`].
+///
+/// This implements [`ExactSizeIterator`] for [`SmallCountable`] point
+/// types. For wider point types the number of points in an interval can be
+/// more than fits in a `usize`, such as for `uu::<u64>().points()`, so use
+/// [`Points::checked_len()`] instead.
+#[derive(Debug, Clone)]
+pub struct Points<I> {
+	//the first and last remaining points, or None once exhausted
+	remaining: Option<(I, I)>,
+}
+
+impl<I> Points<I>
+where
+	I: PointType + Countable,
+{
+	/// The number of points remaining in the iterator, or `None` if that
+	/// doesn't fit in a `usize`.
+	///
+	/// This is for point types which aren't [`SmallCountable`], for which
+	/// [`ExactSizeIterator::len()`] can be used instead.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ii, uu};
+	/// use nodit::InclusiveInterval;
+	///
+	/// let mut points = ii(2_u64, 6).points();
+	/// assert_eq!(points.checked_len(), Some(5));
+	/// points.next();
+	/// assert_eq!(points.checked_len(), Some(4));
+	///
+	/// assert_eq!(uu::<u128>().points().checked_len(), None);
+	/// ```
+	pub fn checked_len(&self) -> Option<usize> {
+		let Some((front, back)) = self.remaining else {
+			return Some(0);
+		};
+		I::steps_between(&front, &back)
+			.and_then(|steps| steps.checked_add(1))
+			.and_then(|count| usize::try_from(count).ok())
+	}
+}
+
+impl<I> Iterator for Points<I>
+where
+	I: PointType + Countable,
+{
+	type Item = I;
+
+	fn next(&mut self) -> Option<I> {
+		let (front, back) = self.remaining?;
+		//front < back so front.up() can't be past the end of the domain
+		self.remaining = (front != back).then(|| (front.up().unwrap(), back));
+		Some(front)
+	}
+
+	fn nth(&mut self, n: usize) -> Option<I> {
+		let (front, back) = self.remaining?;
+		self.remaining = I::forward_checked(front, n as u128)
+			.filter(|front| *front <= back)
+			.map(|front| (front, back));
+		self.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.checked_len() {
+			Some(count) => (count, Some(count)),
+			None => (usize::MAX, None),
+		}
+	}
+
+	fn last(mut self) -> Option<I> {
+		self.next_back()
+	}
+}
+
+impl<I> DoubleEndedIterator for Points<I>
+where
+	I: PointType + Countable,
+{
+	fn next_back(&mut self) -> Option<I> {
+		let (front, back) = self.remaining?;
+		//front < back so back.down() can't be past the start of the domain
+		self.remaining = (front != back).then(|| (front, back.down().unwrap()));
+		Some(back)
+	}
+
+	fn nth_back(&mut self, n: usize) -> Option<I> {
+		let (front, back) = self.remaining?;
+		self.remaining = I::backward_checked(back, n as u128)
+			.filter(|back| *back >= front)
+			.map(|back| (front, back));
+		self.next_back()
+	}
+}
+
+impl<I> ExactSizeIterator for Points<I> where I: PointType + SmallCountable {}
+
+impl<I> FusedIterator for Points<I> where I: PointType + Countable {}
+
+/// The error returned when converting a range into an [`Interval`] with
+/// [`IntoInterval::into_interval()`] fails because the range doesn't
+/// contain any points, such as `5..5` or `..0_u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyIntervalError;
+
+impl fmt::Display for EmptyIntervalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "the interval doesn't contain any points")
+	}
+}
+impl core::error::Error for EmptyIntervalError {}
+
+/// A conversion into an [`Interval`], used by the query and cut methods of
+/// the data-structures in this crate so that they can be passed any of the
+/// std range types directly.
+///
+/// This is implemented for every [`InclusiveInterval`] type, all the std
+/// range types and pairs of [`Bound`]s. If the conversion fails the query
+/// and cut methods treat the interval as one which doesn't overlap
+/// anything, so `map.overlapping(5..5)` returns nothing and
+/// `map.cut(5..5)` leaves the map unchanged.
+///
+/// `M` is a marker type parameter which keeps the implementation for every
+/// [`InclusiveInterval`] type from overlapping with the others, it is
+/// always inferred so you shouldn't need to name it yourself.
+///
+/// # Examples
+/// ```
+/// use core::ops::Bound;
+///
+/// use nodit::interval::{ie, ii, iu, ui, uu};
+/// use nodit::{EmptyIntervalError, IntoInterval};
+///
+/// assert_eq!(ii(3, 7).into_interval(), Ok(ii(3, 7).into()));
+/// assert_eq!((3..7).into_interval(), Ok(ie(3, 7).into()));
+/// assert_eq!((3..=7).into_interval(), Ok(ii(3, 7).into()));
+/// assert_eq!((3..).into_interval(), Ok(iu(3).into()));
+/// assert_eq!((..=9).into_interval(), Ok(ui(9).into()));
+/// assert_eq!(IntoInterval::<u8>::into_interval(..), Ok(uu().into()));
+/// assert_eq!(
+/// 	(Bound::Excluded(2), Bound::Included(7)).into_interval(),
+/// 	Ok(ii(3, 7).into())
+/// );
+///
+/// assert_eq!((..0_u8).into_interval(), Err(EmptyIntervalError));
+/// assert_eq!((5..5).into_interval(), Err(EmptyIntervalError));
+/// ```
+pub trait IntoInterval<I, M = ()> {
+	/// Converts `self` into an [`Interval`], returning an
+	/// [`EmptyIntervalError`] if `self` doesn't contain any points.
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError>;
+}
+
+/// The marker type parameter of the [`IntoInterval`] implementation for
+/// every [`InclusiveInterval`] type.
+pub enum InclusiveIntervalMarker {}
+
+impl<I, K> IntoInterval<I, InclusiveIntervalMarker> for K
+where
+	I: PointType,
+	K: InclusiveInterval<I>,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Included(self.start()), Bound::Included(self.end()))
+	}
+}
+impl<I> IntoInterval<I> for (Bound<I>, Bound<I>)
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(self.0, self.1)
+	}
+}
+impl<I> IntoInterval<I> for Range<I>
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Included(self.start), Bound::Excluded(self.end))
+	}
+}
+impl<I> IntoInterval<I> for RangeInclusive<I>
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		let (start, end) = self.into_inner();
+		from_bounds(Bound::Included(start), Bound::Included(end))
+	}
+}
+impl<I> IntoInterval<I> for RangeFrom<I>
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Included(self.start), Bound::Unbounded)
+	}
+}
+impl<I> IntoInterval<I> for RangeTo<I>
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Unbounded, Bound::Excluded(self.end))
+	}
+}
+impl<I> IntoInterval<I> for RangeToInclusive<I>
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Unbounded, Bound::Included(self.end))
+	}
+}
+impl<I> IntoInterval<I> for RangeFull
+where
+	I: PointType,
+{
+	fn into_interval(self) -> Result<Interval<I>, EmptyIntervalError> {
+		from_bounds(Bound::Unbounded, Bound::Unbounded)
+	}
+}
+
+pub(crate) fn from_bounds<I>(
+	start: Bound<I>,
+	end: Bound<I>,
+) -> Result<Interval<I>, EmptyIntervalError>
+where
+	I: PointType,
+{
+	let start = match start {
+		Bound::Included(start) => Some(start),
+		Bound::Excluded(start) => start.up(),
+		Bound::Unbounded => Some(I::MIN),
+	};
+	let end = match end {
+		Bound::Included(end) => Some(end),
+		Bound::Excluded(end) => end.down(),
+		Bound::Unbounded => Some(I::MAX),
+	};
+
+	match (start, end) {
+		(Some(start), Some(end)) if start <= end => Ok(Interval { start, end }),
+		_ => Err(EmptyIntervalError),
+	}
 }
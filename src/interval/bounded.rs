@@ -0,0 +1,385 @@
+//! A module containing [`BoundedInterval`].
+//!
+//! The constructor functions in the parent module, such as
+//! [`ie()`](crate::interval::ie), create [`BoundedInterval`]s.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Bound, RangeBounds};
+use core::str::FromStr;
+
+use crate::interval::from_bounds;
+use crate::parse::{parse_bounds, ParseError, ParseErrorKind};
+use crate::{EmptyIntervalError, InclusiveInterval, Interval, PointType};
+
+/// An interval which remembers whether each of its end-points is
+/// [`Bound::Included`], [`Bound::Excluded`] or [`Bound::Unbounded`].
+///
+/// Where [`Interval`] always normalizes to inclusive end-points,
+/// `BoundedInterval` keeps the bounds it was created with, so a map using
+/// it as its interval type gives back the same half-open or unbounded
+/// intervals that were inserted. It still implements [`InclusiveInterval`]
+/// by normalizing its end-points on the fly, so `ie(0, 5)` and `ii(0, 4)`
+/// cover the same points and compare as equal even though they are
+/// displayed differently. Use [`RangeBounds`] to tell their bounds apart.
+///
+/// Intervals created by the data-structures themselves, such as the
+/// leftover parts of an interval after a cut, are converted from an
+/// [`Interval`] and so have inclusive end-points. Use
+/// [`BoundedInterval::to_half_open()`] if you need them in half-open form.
+///
+/// To create a `BoundedInterval` use one of the constructor functions in
+/// the [`interval`](crate::interval) module which will all panic if you try
+/// to create an invalid interval. See [`Invalid
+/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
+/// for more details.
+///
+/// # Examples
+/// ```
+/// use core::ops::{Bound, RangeBounds};
+///
+/// use nodit::interval::{ie, iu};
+/// use nodit::NoditMap;
+///
+/// let map =
+/// 	NoditMap::from_slice_strict([(ie(0, 5), true), (iu(5), false)])
+/// 		.unwrap();
+///
+/// assert_eq!(
+/// 	map.first_key_value().unwrap().0.end_bound(),
+/// 	Bound::Excluded(&5)
+/// );
+/// assert_eq!(
+/// 	map.last_key_value().unwrap().0.end_bound(),
+/// 	Bound::Unbounded
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct BoundedInterval<I> {
+	/// The start bound of the interval.
+	pub(crate) start: Bound<I>,
+	/// The end bound of the interval.
+	pub(crate) end: Bound<I>,
+}
+
+impl<I> BoundedInterval<I>
+where
+	I: PointType,
+{
+	/// Makes a new `BoundedInterval` from a pair of bounds, returning an
+	/// [`EmptyIntervalError`] if they don't contain any points.
+	///
+	/// # Examples
+	/// ```
+	/// use core::ops::Bound;
+	///
+	/// use nodit::interval::ei;
+	/// use nodit::{BoundedInterval, EmptyIntervalError};
+	///
+	/// assert_eq!(
+	/// 	BoundedInterval::new(Bound::Excluded(2), Bound::Included(6)),
+	/// 	Ok(ei(2, 6))
+	/// );
+	/// assert_eq!(
+	/// 	BoundedInterval::new(Bound::Excluded(2), Bound::Excluded(3)),
+	/// 	Err(EmptyIntervalError)
+	/// );
+	/// ```
+	pub fn new(
+		start: Bound<I>,
+		end: Bound<I>,
+	) -> Result<Self, EmptyIntervalError> {
+		from_bounds(start, end)?;
+
+		Ok(BoundedInterval { start, end })
+	}
+
+	/// Returns the interval covering the same points with an included
+	/// start and an excluded end, or an unbounded end if it ends at the
+	/// maximum point.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ee, ii};
+	///
+	/// assert_eq!(ii(0, 4).to_half_open().to_string(), "[0, 5)");
+	/// assert_eq!(ee(0, 5).to_half_open().to_string(), "[1, 5)");
+	/// assert_eq!(ii(0, u8::MAX).to_half_open().to_string(), "[0, +∞)");
+	/// ```
+	pub fn to_half_open(&self) -> Self {
+		BoundedInterval {
+			start: Bound::Included(self.start()),
+			end: match self.end().up() {
+				Some(after) => Bound::Excluded(after),
+				None => Bound::Unbounded,
+			},
+		}
+	}
+}
+
+/// Two `BoundedInterval`s are equal if they cover the same points, even if
+/// their bounds are different.
+impl<I> PartialEq for BoundedInterval<I>
+where
+	I: PointType,
+{
+	fn eq(&self, other: &Self) -> bool {
+		(self.start(), self.end()) == (other.start(), other.end())
+	}
+}
+impl<I> Eq for BoundedInterval<I> where I: PointType {}
+/// A `BoundedInterval` is equal to an [`Interval`] covering the same
+/// points.
+impl<I> PartialEq<Interval<I>> for BoundedInterval<I>
+where
+	I: PointType,
+{
+	fn eq(&self, other: &Interval<I>) -> bool {
+		(self.start(), self.end()) == (other.start(), other.end())
+	}
+}
+/// An [`Interval`] is equal to a `BoundedInterval` covering the same
+/// points.
+impl<I> PartialEq<BoundedInterval<I>> for Interval<I>
+where
+	I: PointType,
+{
+	fn eq(&self, other: &BoundedInterval<I>) -> bool {
+		other == self
+	}
+}
+impl<I> Hash for BoundedInterval<I>
+where
+	I: PointType + Hash,
+{
+	fn hash<H>(&self, state: &mut H)
+	where
+		H: Hasher,
+	{
+		(self.start(), self.end()).hash(state);
+	}
+}
+impl<I> RangeBounds<I> for BoundedInterval<I> {
+	fn start_bound(&self) -> Bound<&I> {
+		self.start.as_ref()
+	}
+
+	fn end_bound(&self) -> Bound<&I> {
+		self.end.as_ref()
+	}
+}
+impl<I> InclusiveInterval<I> for BoundedInterval<I>
+where
+	I: PointType,
+{
+	fn start(&self) -> I {
+		match self.start {
+			Bound::Included(start) => start,
+			Bound::Excluded(start) => start.up().unwrap(),
+			Bound::Unbounded => I::MIN,
+		}
+	}
+
+	fn end(&self) -> I {
+		match self.end {
+			Bound::Included(end) => end,
+			Bound::Excluded(end) => end.down().unwrap(),
+			Bound::Unbounded => I::MAX,
+		}
+	}
+}
+impl<I> From<Interval<I>> for BoundedInterval<I> {
+	fn from(value: Interval<I>) -> Self {
+		BoundedInterval {
+			start: Bound::Included(value.start),
+			end: Bound::Included(value.end),
+		}
+	}
+}
+impl<I> From<BoundedInterval<I>> for Interval<I>
+where
+	I: PointType,
+{
+	fn from(value: BoundedInterval<I>) -> Self {
+		Interval {
+			start: value.start(),
+			end: value.end(),
+		}
+	}
+}
+
+/// Formats the interval in mathematical interval notation, keeping its
+/// bounds, such as `[3, 7)` or `(-∞, 5]`.
+///
+/// ```
+/// use nodit::interval::{ie, ui};
+///
+/// assert_eq!(ie(3, 7).to_string(), "[3, 7)");
+/// assert_eq!(ui(5).to_string(), "(-∞, 5]");
+/// ```
+impl<I> fmt::Display for BoundedInterval<I>
+where
+	I: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.start {
+			Bound::Included(start) => write!(f, "[{start}, "),
+			Bound::Excluded(start) => write!(f, "({start}, "),
+			Bound::Unbounded => write!(f, "(-∞, "),
+		}?;
+		match &self.end {
+			Bound::Included(end) => write!(f, "{end}]"),
+			Bound::Excluded(end) => write!(f, "{end})"),
+			Bound::Unbounded => write!(f, "+∞)"),
+		}
+	}
+}
+/// Parses an interval in mathematical interval notation such as `[3, 7)`,
+/// keeping its bounds.
+///
+/// Unbounded end-points can be written as `-∞` and `∞` (or `-inf` and
+/// `inf`).
+///
+/// ```
+/// use nodit::interval::{ie, ui};
+/// use nodit::{BoundedInterval, ParseError, ParseErrorKind};
+///
+/// assert_eq!("[3, 7)".parse::<BoundedInterval<u8>>(), Ok(ie(3, 7)));
+/// assert_eq!("(-∞, 5]".parse::<BoundedInterval<i8>>(), Ok(ui(5)));
+/// assert_eq!(
+/// 	"(3, 4)".parse::<BoundedInterval<u8>>(),
+/// 	Err(ParseError {
+/// 		offset: 0,
+/// 		kind: ParseErrorKind::EmptyInterval
+/// 	})
+/// );
+/// ```
+impl<I> FromStr for BoundedInterval<I>
+where
+	I: PointType + FromStr,
+{
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (start, end) = parse_bounds(s)?;
+
+		BoundedInterval::new(start, end).map_err(|_| ParseError {
+			offset: s.len() - s.trim_start().len(),
+			kind: ParseErrorKind::EmptyInterval,
+		})
+	}
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+	use core::ops::Bound;
+
+	use serde::de::Error;
+	use serde::{Deserialize, Deserializer};
+
+	use crate::{BoundedInterval, PointType};
+
+	/// The same shape as the derived serialization of [`BoundedInterval`],
+	/// deserialized without checking that the bounds contain any points.
+	#[derive(Deserialize)]
+	#[serde(rename = "BoundedInterval")]
+	struct Bounds<I> {
+		start: Bound<I>,
+		end: Bound<I>,
+	}
+
+	impl<'de, I> Deserialize<'de> for BoundedInterval<I>
+	where
+		I: PointType + Deserialize<'de>,
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: Deserializer<'de>,
+		{
+			let Bounds { start, end } = Bounds::deserialize(deserializer)?;
+
+			BoundedInterval::new(start, end).map_err(D::Error::custom)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::string::ToString;
+	use alloc::vec::Vec;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ee, ei, eu, ie, ii, iu, ue, ui, uu};
+	use crate::NoditMap;
+
+	fn bounds<I>(interval: &BoundedInterval<I>) -> (Bound<I>, Bound<I>)
+	where
+		I: Copy,
+	{
+		(interval.start, interval.end)
+	}
+
+	#[test]
+	fn round_trip_tests() {
+		let mut map = NoditMap::from_slice_strict([
+			(ie(0_i8, 5), 1),
+			(ee(5, 10), 2),
+			(iu(10), 3),
+		])
+		.unwrap();
+
+		assert_eq!(
+			map.iter().map(|(key, _)| bounds(key)).collect::<Vec<_>>(),
+			[ie(0, 5), ee(5, 10), iu(10)].iter().map(bounds).collect::<Vec<_>>()
+		);
+		assert_eq!(
+			map.gaps_untrimmed(ie(0, 20)).collect::<Vec<_>>(),
+			[ii(5, 5)]
+		);
+
+		//new intervals made by the map are inclusive
+		assert_eq!(
+			map.cut(ie(2, 8))
+				.map(|(key, value)| (bounds(&key), value))
+				.collect::<Vec<_>>(),
+			[(bounds(&ii(2, 4)), 1), (bounds(&ii(6, 7)), 2)]
+		);
+		assert_eq!(
+			map.iter()
+				.map(|(key, _)| bounds(&key.to_half_open()))
+				.collect::<Vec<_>>(),
+			[ie(0, 2), ie(8, 10), iu(10)].iter().map(bounds).collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn display_tests() {
+		for (interval, display) in [
+			(uu(), "(-∞, +∞)"),
+			(ui(5), "(-∞, 5]"),
+			(ue(5), "(-∞, 5)"),
+			(iu(-5), "[-5, +∞)"),
+			(eu(-5), "(-5, +∞)"),
+			(ii(4, 4), "[4, 4]"),
+			(ie(3, 7), "[3, 7)"),
+			(ei(3, 7), "(3, 7]"),
+			(ee(3, 7), "(3, 7)"),
+		] {
+			assert_eq!(interval.to_string(), display);
+			let parsed = display.parse::<BoundedInterval<i8>>().unwrap();
+			assert_eq!(bounds(&parsed), bounds(&interval));
+		}
+
+		assert_eq!(" [ -inf , inf ) ".parse(), Ok(uu::<i8>()));
+		assert_eq!(
+			"[3, 7".parse::<BoundedInterval<i8>>(),
+			Err(ParseError {
+				offset: 5,
+				kind: ParseErrorKind::Expected("`]` or `)`")
+			})
+		);
+	}
+}
@@ -0,0 +1,627 @@
+//! A module containing [`Cidr`] blocks, [`cidr_blocks()`] for splitting an
+//! interval of IP addresses into them, and [`IpRangeMap`], a longest-prefix
+//! lookup table built on [`NoditMap`].
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+
+use crate::parse::{ParseError, ParseErrorKind};
+use crate::utils::query_interval;
+use crate::{EmptyIntervalError, Interval, IntoInterval, NoditMap, PointType};
+
+/// A trait for the IP address types which can be used in a [`Cidr`],
+/// implemented for [`Ipv4Addr`] and [`Ipv6Addr`].
+pub trait IpAddress:
+	PointType + Into<IpAddr> + FromStr + fmt::Display
+{
+	/// The number of bits in an address.
+	const BITS: u8;
+
+	/// Converts `self` into its bits.
+	fn to_bits(self) -> u128;
+	/// Converts the lowest [`IpAddress::BITS`] bits of `bits` into an
+	/// address.
+	fn from_bits(bits: u128) -> Self;
+}
+
+impl IpAddress for Ipv4Addr {
+	const BITS: u8 = 32;
+
+	fn to_bits(self) -> u128 {
+		u128::from(u32::from(self))
+	}
+	fn from_bits(bits: u128) -> Self {
+		Ipv4Addr::from(bits as u32)
+	}
+}
+
+impl IpAddress for Ipv6Addr {
+	const BITS: u8 = 128;
+
+	fn to_bits(self) -> u128 {
+		u128::from(self)
+	}
+	fn from_bits(bits: u128) -> Self {
+		Ipv6Addr::from(bits)
+	}
+}
+
+/// A block of IP addresses sharing their first `prefix_len` bits, written
+/// `10.0.0.0/8` in CIDR notation.
+///
+/// # Examples
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// use nodit::interval::ii;
+/// use nodit::ip::Cidr;
+///
+/// let cidr = "10.0.0.0/8".parse::<Cidr<Ipv4Addr>>().unwrap();
+///
+/// assert_eq!(cidr.network(), Ipv4Addr::new(10, 0, 0, 0));
+/// assert_eq!(cidr.prefix_len(), 8);
+/// assert_eq!(
+/// 	cidr.interval(),
+/// 	ii(Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 255, 255, 255))
+/// );
+/// assert_eq!(cidr.to_string(), "10.0.0.0/8");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cidr<A> {
+	network: A,
+	prefix_len: u8,
+}
+
+/// The error returned when constructing an invalid [`Cidr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CidrError {
+	/// The prefix length is longer than the address.
+	InvalidPrefixLength,
+	/// The network address has bits set after the prefix, such as
+	/// `10.0.0.1/8`.
+	HostBitsSet,
+}
+
+impl fmt::Display for CidrError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CidrError::InvalidPrefixLength => {
+				write!(f, "the prefix length is longer than the address")
+			}
+			CidrError::HostBitsSet => {
+				write!(f, "the network address has bits set after the prefix")
+			}
+		}
+	}
+}
+impl core::error::Error for CidrError {}
+
+impl<A> Cidr<A>
+where
+	A: IpAddress,
+{
+	/// Makes a new `Cidr` from its network address and prefix length.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::Ipv4Addr;
+	///
+	/// use nodit::ip::{Cidr, CidrError};
+	///
+	/// assert!(Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 8).is_ok());
+	/// assert_eq!(
+	/// 	Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 33),
+	/// 	Err(CidrError::InvalidPrefixLength)
+	/// );
+	/// assert_eq!(
+	/// 	Cidr::new(Ipv4Addr::new(10, 0, 0, 1), 8),
+	/// 	Err(CidrError::HostBitsSet)
+	/// );
+	/// ```
+	pub fn new(network: A, prefix_len: u8) -> Result<Self, CidrError> {
+		if prefix_len > A::BITS {
+			return Err(CidrError::InvalidPrefixLength);
+		}
+		if network.to_bits() & host_mask::<A>(prefix_len) != 0 {
+			return Err(CidrError::HostBitsSet);
+		}
+
+		Ok(Cidr {
+			network,
+			prefix_len,
+		})
+	}
+
+	/// Returns the first address in the block.
+	pub fn network(&self) -> A {
+		self.network
+	}
+
+	/// Returns the number of leading bits shared by the addresses in the
+	/// block.
+	pub fn prefix_len(&self) -> u8 {
+		self.prefix_len
+	}
+
+	/// Returns the last address in the block.
+	pub fn last(&self) -> A {
+		A::from_bits(self.network.to_bits() | host_mask::<A>(self.prefix_len))
+	}
+
+	/// Returns the interval of addresses in the block.
+	pub fn interval(&self) -> Interval<A> {
+		Interval {
+			start: self.network,
+			end: self.last(),
+		}
+	}
+}
+
+/// The bits of an address after a prefix of the given length.
+fn host_mask<A>(prefix_len: u8) -> u128
+where
+	A: IpAddress,
+{
+	let host_bits = u32::from(A::BITS - prefix_len);
+	u128::MAX.checked_shr(128 - host_bits).unwrap_or(0)
+}
+
+impl<A> From<Cidr<A>> for Interval<A>
+where
+	A: IpAddress,
+{
+	fn from(cidr: Cidr<A>) -> Self {
+		cidr.interval()
+	}
+}
+
+impl<A> IntoInterval<A> for Cidr<A>
+where
+	A: IpAddress,
+{
+	fn into_interval(self) -> Result<Interval<A>, EmptyIntervalError> {
+		Ok(self.interval())
+	}
+}
+
+impl<A> fmt::Display for Cidr<A>
+where
+	A: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}/{}", self.network, self.prefix_len)
+	}
+}
+
+impl<A> FromStr for Cidr<A>
+where
+	A: IpAddress,
+{
+	type Err = ParseError;
+
+	/// Parses a `Cidr` in CIDR notation such as `10.0.0.0/8`.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::{Ipv4Addr, Ipv6Addr};
+	///
+	/// use nodit::ip::{Cidr, CidrError};
+	/// use nodit::{ParseError, ParseErrorKind};
+	///
+	/// assert_eq!(
+	/// 	"fd00::/8".parse::<Cidr<Ipv6Addr>>().unwrap().to_string(),
+	/// 	"fd00::/8"
+	/// );
+	/// assert_eq!(
+	/// 	"10.0.0.1/8".parse::<Cidr<Ipv4Addr>>(),
+	/// 	Err(ParseError {
+	/// 		offset: 0,
+	/// 		kind: ParseErrorKind::InvalidCidr(CidrError::HostBitsSet)
+	/// 	})
+	/// );
+	/// ```
+	fn from_str(input: &str) -> Result<Self, ParseError> {
+		let start = input.len() - input.trim_start().len();
+		let end = input.trim_end().len();
+
+		let slash = input.find('/').ok_or(ParseError {
+			offset: end,
+			kind: ParseErrorKind::Expected("`/`"),
+		})?;
+
+		let network = input[start..slash].trim_end().parse().map_err(|_| {
+			ParseError {
+				offset: start,
+				kind: ParseErrorKind::InvalidPoint,
+			}
+		})?;
+		let prefix_len = input[slash + 1..end]
+			.trim_start()
+			.parse::<u32>()
+			.map_err(|_| ParseError {
+				offset: slash + 1,
+				kind: ParseErrorKind::Expected("prefix length"),
+			})?;
+
+		//a prefix length too big for a u8 is too long for any address too
+		let prefix_len = u8::try_from(prefix_len).unwrap_or(u8::MAX);
+		Cidr::new(network, prefix_len).map_err(|error| {
+			ParseError {
+				offset: match error {
+					CidrError::InvalidPrefixLength => slash + 1,
+					CidrError::HostBitsSet => start,
+				},
+				kind: ParseErrorKind::InvalidCidr(error),
+			}
+		})
+	}
+}
+
+/// Splits the given interval of addresses into the smallest list of
+/// [`Cidr`] blocks which cover it exactly, in ascending order.
+///
+/// # Examples
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// use nodit::interval::ii;
+/// use nodit::ip::{cidr_blocks, Cidr};
+///
+/// let blocks = cidr_blocks(ii(
+/// 	Ipv4Addr::new(10, 0, 0, 0),
+/// 	Ipv4Addr::new(10, 0, 2, 127),
+/// ));
+///
+/// assert_eq!(
+/// 	blocks.iter().map(Cidr::to_string).collect::<Vec<_>>(),
+/// 	["10.0.0.0/23", "10.0.2.0/25"]
+/// );
+/// ```
+pub fn cidr_blocks<A, Q, M>(interval: Q) -> Vec<Cidr<A>>
+where
+	A: IpAddress,
+	Q: IntoInterval<A, M>,
+{
+	let Some(interval) = query_interval(interval) else {
+		return Vec::new();
+	};
+	let end = interval.end.to_bits();
+
+	let mut blocks = Vec::new();
+	let mut start = interval.start.to_bits();
+	loop {
+		//the largest block which starts at `start` and doesn't go past
+		//`end`, limited by the alignment of `start` and the number of
+		//addresses left
+		let remaining = end - start;
+		let fits = match remaining.checked_add(1) {
+			Some(count) => 127 - count.leading_zeros(),
+			None => 128,
+		};
+		let host_bits = start.trailing_zeros().min(fits).min(A::BITS.into());
+		//host_bits <= A::BITS so this can't truncate
+		let prefix_len = A::BITS - host_bits as u8;
+
+		blocks.push(Cidr {
+			network: A::from_bits(start),
+			prefix_len,
+		});
+
+		let last = start | host_mask::<A>(prefix_len);
+		if last == end {
+			return blocks;
+		}
+		start = last + 1;
+	}
+}
+
+/// An IP-range lookup table of [`Cidr`] blocks, in which the most specific
+/// block containing an address decides its value.
+///
+/// Both IPv4 and IPv6 blocks can be stored in the same map. The map is a
+/// wrapper around a [`NoditMap`] of [`IpAddr`]s where inserting a block
+/// overwrites, via [`NoditMap::insert_overwrite()`], only the parts of
+/// it which aren't already covered by more specific blocks. This means the
+/// order in which blocks are inserted doesn't matter, except between
+/// identical blocks where the last insert wins.
+///
+/// # Examples
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// use nodit::ip::{Cidr, IpRangeMap};
+///
+/// let mut rules = IpRangeMap::new();
+///
+/// rules.insert("10.1.0.0/16".parse::<Cidr<Ipv4Addr>>().unwrap(), "deny");
+/// rules.insert("10.0.0.0/8".parse::<Cidr<Ipv4Addr>>().unwrap(), "allow");
+///
+/// assert_eq!(rules.lookup(Ipv4Addr::new(10, 1, 2, 3)), Some(&"deny"));
+/// assert_eq!(rules.lookup(Ipv4Addr::new(10, 2, 0, 0)), Some(&"allow"));
+/// assert_eq!(rules.lookup(Ipv4Addr::new(11, 0, 0, 0)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpRangeMap<V> {
+	//each value is stored with the prefix length of the block it came
+	//from so that less specific blocks can't overwrite it
+	inner: NoditMap<IpAddr, Interval<IpAddr>, (u8, V)>,
+}
+
+impl<V> IpRangeMap<V> {
+	/// Makes a new, empty `IpRangeMap`.
+	pub fn new() -> Self {
+		IpRangeMap {
+			inner: NoditMap::new(),
+		}
+	}
+
+	/// Inserts a block into the map, overwriting the parts of any less
+	/// specific blocks, or an identical block, which it covers.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::{Ipv4Addr, Ipv6Addr};
+	///
+	/// use nodit::ip::{Cidr, IpRangeMap};
+	///
+	/// let mut map = IpRangeMap::new();
+	///
+	/// map.insert(Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 24).unwrap(), 1);
+	/// map.insert(Cidr::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap(), 2);
+	/// map.insert(Cidr::new(Ipv6Addr::UNSPECIFIED, 0).unwrap(), 3);
+	///
+	/// assert_eq!(map.lookup(Ipv4Addr::new(10, 0, 0, 7)), Some(&1));
+	/// assert_eq!(map.lookup(Ipv4Addr::new(10, 0, 1, 7)), Some(&2));
+	/// assert_eq!(map.lookup(Ipv6Addr::LOCALHOST), Some(&3));
+	/// ```
+	pub fn insert<A>(&mut self, cidr: Cidr<A>, value: V)
+	where
+		A: IpAddress,
+		V: Clone,
+	{
+		let interval = Interval {
+			start: cidr.network().into(),
+			end: cidr.last().into(),
+		};
+
+		//blocks either contain each other or don't overlap, so any more
+		//specific blocks are inside this one and can be put back on top
+		let more_specific = self
+			.inner
+			.insert_overwrite(interval, (cidr.prefix_len(), value))
+			.filter(|(_, (prefix_len, _))| *prefix_len > cidr.prefix_len())
+			.collect::<Vec<_>>();
+		for (interval, value) in more_specific {
+			let _ = self.inner.insert_overwrite(interval, value);
+		}
+	}
+
+	/// Returns a reference to the value of the most specific block
+	/// containing the given address, if there is one.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::{IpAddr, Ipv4Addr};
+	///
+	/// use nodit::ip::{Cidr, IpRangeMap};
+	///
+	/// let mut map = IpRangeMap::new();
+	///
+	/// map.insert(Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap(), 1);
+	///
+	/// assert_eq!(map.lookup(Ipv4Addr::new(192, 168, 4, 4)), Some(&1));
+	/// assert_eq!(
+	/// 	map.lookup("192.168.4.4".parse::<IpAddr>().unwrap()),
+	/// 	Some(&1)
+	/// );
+	/// assert_eq!(map.lookup(Ipv4Addr::new(192, 169, 0, 0)), None);
+	/// ```
+	pub fn lookup(&self, address: impl Into<IpAddr>) -> Option<&V> {
+		self.inner
+			.get_at_point(address.into())
+			.map(|(_, value)| value)
+	}
+
+	/// Returns an iterator over the intervals of addresses in the map, with
+	/// the value each interval resolves to, in ascending order.
+	///
+	/// Blocks which are split by more specific blocks inside them are
+	/// returned as multiple intervals.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::{IpAddr, Ipv4Addr};
+	///
+	/// use nodit::interval::ii;
+	/// use nodit::ip::{Cidr, IpRangeMap};
+	///
+	/// let mut map = IpRangeMap::new();
+	///
+	/// map.insert(Cidr::new(Ipv4Addr::new(10, 0, 0, 0), 30).unwrap(), 'a');
+	/// map.insert(Cidr::new(Ipv4Addr::new(10, 0, 0, 1), 32).unwrap(), 'b');
+	///
+	/// let ip = |last| IpAddr::V4(Ipv4Addr::new(10, 0, 0, last));
+	/// assert_eq!(
+	/// 	map.iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ii(ip(0), ip(0)).into(), &'a'),
+	/// 		(ii(ip(1), ip(1)).into(), &'b'),
+	/// 		(ii(ip(2), ip(3)).into(), &'a'),
+	/// 	]
+	/// );
+	/// ```
+	pub fn iter(
+		&self,
+	) -> impl DoubleEndedIterator<Item = (Interval<IpAddr>, &V)> {
+		self.inner
+			.iter()
+			.map(|(interval, (_, value))| (*interval, value))
+	}
+
+	/// Returns the number of intervals in the map, see
+	/// [`IpRangeMap::iter()`].
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Returns `true` if the map contains no blocks.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+}
+
+impl<V> Default for IpRangeMap<V> {
+	fn default() -> Self {
+		IpRangeMap::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::string::ToString;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ii, uu};
+	use crate::{DiscreteFinite, InclusiveInterval};
+
+	#[test]
+	fn cidr_blocks_tests() {
+		//every interval of a small sub-domain against a brute-force check
+		//that the blocks are aligned, cover it exactly, and that no two
+		//neighbouring blocks could have been merged
+		for start in 0..=40_u32 {
+			for end in start..=40 {
+				let blocks = cidr_blocks(ii(
+					Ipv4Addr::from(start),
+					Ipv4Addr::from(end),
+				));
+
+				let mut next = start;
+				for block in blocks.iter() {
+					assert_eq!(u32::from(block.network()), next);
+					assert!(
+						Cidr::new(block.network(), block.prefix_len()).is_ok()
+					);
+					next = u32::from(block.last()) + 1;
+				}
+				assert_eq!(next, end + 1);
+
+				for pair in blocks.windows(2) {
+					let merged =
+						Cidr::new(pair[0].network(), pair[0].prefix_len() - 1);
+					assert!(
+						pair[0].prefix_len() != pair[1].prefix_len()
+							|| merged.is_err()
+					);
+				}
+			}
+		}
+
+		assert_eq!(
+			cidr_blocks(uu::<Ipv4Addr>()),
+			[Cidr::new(Ipv4Addr::UNSPECIFIED, 0).unwrap()]
+		);
+		assert_eq!(
+			cidr_blocks(uu::<Ipv6Addr>()),
+			[Cidr::new(Ipv6Addr::UNSPECIFIED, 0).unwrap()]
+		);
+		assert_eq!(
+			cidr_blocks(ii(Ipv6Addr::from(1), Ipv6Addr::from(u128::MAX))).len(),
+			128
+		);
+		assert_eq!(
+			cidr_blocks(ii(Ipv4Addr::BROADCAST, Ipv4Addr::BROADCAST)),
+			[Cidr::new(Ipv4Addr::BROADCAST, 32).unwrap()]
+		);
+		assert_eq!(cidr_blocks(..Ipv4Addr::UNSPECIFIED), []);
+	}
+
+	#[test]
+	fn parse_tests() {
+		let error = |input: &str| {
+			input
+				.parse::<Cidr<Ipv4Addr>>()
+				.map_err(|error| (error.offset, error.kind))
+		};
+
+		assert_eq!(
+			error(" 10.0.0.0/8 ").map(|cidr| cidr.to_string()),
+			Ok("10.0.0.0/8".to_string())
+		);
+		assert_eq!(
+			error("0.0.0.0/0").map(|cidr| cidr.interval()),
+			Ok(uu().into())
+		);
+		assert_eq!(
+			error("10.0.0.0"),
+			Err((8, ParseErrorKind::Expected("`/`")))
+		);
+		assert_eq!(error("10.0.0/8"), Err((0, ParseErrorKind::InvalidPoint)));
+		let invalid_prefix_length =
+			ParseErrorKind::InvalidCidr(CidrError::InvalidPrefixLength);
+		assert_eq!(error("10.0.0.0/33"), Err((9, invalid_prefix_length)));
+		assert_eq!(error("10.0.0.0/300"), Err((9, invalid_prefix_length)));
+		let expected_prefix_length = ParseErrorKind::Expected("prefix length");
+		assert_eq!(error("10.0.0.0/x"), Err((9, expected_prefix_length)));
+		assert_eq!(error("10.0.0.0/"), Err((9, expected_prefix_length)));
+		assert_eq!(
+			error("10.0.0.128/24"),
+			Err((0, ParseErrorKind::InvalidCidr(CidrError::HostBitsSet)))
+		);
+	}
+
+	#[test]
+	fn ip_range_map_tests() {
+		let cidr = |input: &str| input.parse::<Cidr<Ipv4Addr>>().unwrap();
+		let blocks = [
+			(cidr("0.0.0.0/0"), 0),
+			(cidr("10.0.0.0/8"), 8),
+			(cidr("10.1.0.0/16"), 16),
+			(cidr("10.1.2.0/24"), 24),
+			(cidr("10.1.2.3/32"), 32),
+			(cidr("10.200.0.0/16"), 16),
+		];
+		let expected = |address: Ipv4Addr| {
+			blocks
+				.iter()
+				.filter(|(block, _)| block.interval().contains_point(address))
+				.max_by_key(|(block, _)| block.prefix_len())
+				.map(|(_, value)| *value)
+		};
+		let addresses = blocks
+			.iter()
+			.flat_map(|(block, _)| {
+				[block.network(), block.last()]
+					.into_iter()
+					.flat_map(|address| {
+						[address.down(), Some(address), address.up()]
+					})
+			})
+			.flatten()
+			.collect::<Vec<_>>();
+
+		//inserting in any rotation of the blocks gives the same lookups
+		for rotation in 0..blocks.len() {
+			let mut map = IpRangeMap::new();
+			let rotated =
+				blocks.iter().cycle().skip(rotation).take(blocks.len());
+			for (block, value) in rotated {
+				map.insert(*block, *value);
+			}
+			for address in addresses.iter() {
+				assert_eq!(map.lookup(*address).copied(), expected(*address));
+			}
+			assert_eq!(map.lookup(Ipv6Addr::UNSPECIFIED), None);
+		}
+
+		let mut map = IpRangeMap::new();
+		map.insert(cidr("10.0.0.0/8"), 'a');
+		map.insert(cidr("10.0.0.0/8"), 'b');
+		assert_eq!(map.lookup(Ipv4Addr::new(10, 0, 0, 0)), Some(&'b'));
+		assert_eq!(map.len(), 1);
+	}
+}
@@ -0,0 +1,633 @@
+//! A module containing [`LaminarMap`], a map of properly nested intervals
+//! such as profiling spans or the sections of a document.
+//!
+//! A family of intervals is laminar when any two of them are either
+//! disjoint or one is nested within the other, so they never partially
+//! overlap. Such a family forms a forest, where the parent of an interval
+//! is the smallest interval containing it.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::from_fn;
+
+use crate::utils::{invalid_interval_panic, nesting, Nesting};
+use crate::{IntervalType, NoditMap, PointType};
+
+/// A map of properly nested intervals to values, forming a forest where
+/// each interval is a child of the smallest interval containing it.
+///
+/// Unlike a [`NoditMap`], intervals may overlap as long as one is nested
+/// within the other, so [`LaminarMap::insert_strict()`] only refuses an
+/// interval which partially overlaps, or is equal to, an interval already
+/// in the map.
+///
+/// Each level of the forest is stored as a [`NoditMap`] of the sibling
+/// intervals on it, so finding the intervals at a point takes
+/// `O(D * log(N))` where `D` is the depth of the deepest of them.
+///
+/// `I` is the point type, `K` is the interval type, and `V` is the value
+/// type, the same as for [`NoditMap`].
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::LaminarMap;
+///
+/// let mut spans = LaminarMap::new();
+///
+/// spans.insert_strict(ii(0, 100), "main").unwrap();
+/// spans.insert_strict(ii(10, 40), "parse").unwrap();
+/// spans.insert_strict(ii(50, 90), "render").unwrap();
+/// spans.insert_strict(ii(12, 20), "tokenize").unwrap();
+///
+/// assert_eq!(
+/// 	spans
+/// 		.ancestors_at_point(15)
+/// 		.map(|(_, name)| *name)
+/// 		.collect::<Vec<_>>(),
+/// 	["main", "parse", "tokenize"]
+/// );
+/// assert!(spans.insert_strict(ii(30, 60), "crossing").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaminarMap<I, K, V> {
+	//we can't use the roots' len since they only hold the top level
+	len: usize,
+	roots: NoditMap<I, K, Node<I, K, V>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node<I, K, V> {
+	value: V,
+	children: NoditMap<I, K, Node<I, K, V>>,
+}
+
+/// The error returned when inserting an interval into a [`LaminarMap`]
+/// which partially overlaps, or is equal to, an interval already in the
+/// map. Contains the interval and value that were not inserted, along
+/// with the interval they crossed.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::{CrossingError, LaminarMap};
+///
+/// let mut map = LaminarMap::new();
+///
+/// map.insert_strict(ii(0, 10), 'a').unwrap();
+/// map.insert_strict(ii(2, 4), 'b').unwrap();
+///
+/// let error = map.insert_strict(ii(3, 6), 'c').unwrap_err();
+///
+/// assert_eq!(
+/// 	error,
+/// 	CrossingError {
+/// 		interval: ii(3, 6),
+/// 		crossing: ii(2, 4),
+/// 		value: 'c',
+/// 	}
+/// );
+/// assert_eq!(
+/// 	error.to_string(),
+/// 	"interval [3, 6] crosses the existing interval [2, 4]"
+/// );
+/// ```
+#[derive(PartialEq, Debug)]
+pub struct CrossingError<K, V> {
+	/// The interval which was not inserted, because of the crossing.
+	pub interval: K,
+	/// The interval already in the map which `interval` partially
+	/// overlapped or was equal to.
+	pub crossing: K,
+	/// The value which was not inserted, because of the crossing.
+	pub value: V,
+}
+
+impl<K, V> fmt::Display for CrossingError<K, V>
+where
+	K: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"interval {} crosses the existing interval {}",
+			self.interval, self.crossing
+		)
+	}
+}
+impl<K, V> core::error::Error for CrossingError<K, V>
+where
+	K: fmt::Debug + fmt::Display,
+	V: fmt::Debug,
+{
+}
+
+impl<I, K, V> LaminarMap<I, K, V> {
+	/// Makes a new, empty `LaminarMap`.
+	pub fn new() -> Self {
+		LaminarMap {
+			len: 0,
+			roots: NoditMap::new(),
+		}
+	}
+
+	/// Returns the number of intervals in the map, at every depth.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns `true` if the map contains no intervals.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+
+impl<I, K, V> LaminarMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+{
+	/// Inserts an interval and its value into the map, as a child of the
+	/// smallest interval containing it and as the parent of the intervals
+	/// it contains which were on that level.
+	///
+	/// If the interval partially overlaps, or is equal to, any interval
+	/// already in the map then a [`CrossingError`] naming that interval is
+	/// returned instead.
+	///
+	/// # Panics
+	///
+	/// Panics if the given interval is an invalid interval. See [`Invalid
+	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	///
+	/// map.insert_strict(ii(2, 4), 'b').unwrap();
+	/// map.insert_strict(ii(6, 8), 'c').unwrap();
+	/// //an interval around existing intervals becomes their parent
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	///
+	/// assert_eq!(map.parent(ii(6, 8)), Some((&ii(0, 10), &'a')));
+	/// assert!(map.insert_strict(ii(0, 10), 'd').is_err());
+	/// assert!(map.insert_strict(ii(4, 6), 'd').is_err());
+	/// ```
+	pub fn insert_strict(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<(), CrossingError<K, V>> {
+		invalid_interval_panic(interval);
+
+		if let Some(crossing) = self.crossing(interval) {
+			return Err(CrossingError {
+				interval,
+				crossing,
+				value,
+			});
+		}
+
+		let mut level = &mut self.roots;
+		loop {
+			let parent = level
+				.overlapping(interval)
+				.map(|(key, _)| *key)
+				.find(|key| {
+					nesting(*key, interval) == Nesting::LeftContainsRight
+				});
+			match parent {
+				Some(parent) => {
+					level = &mut level
+						.get_at_point_mut(parent.start())
+						.unwrap()
+						.children;
+				}
+				None => break,
+			}
+		}
+
+		//everything left overlapping on this level is inside the interval
+		let mut children = NoditMap::new();
+		for (key, node) in level.remove_overlapping(interval) {
+			children.insert_unchecked(key, node);
+		}
+		level.insert_unchecked(interval, Node { value, children });
+		self.len += 1;
+
+		Ok(())
+	}
+	fn crossing(&self, interval: K) -> Option<K> {
+		let mut level = &self.roots;
+		loop {
+			let mut parent = None;
+			for (key, node) in level.overlapping(interval) {
+				match nesting(*key, interval) {
+					Nesting::LeftContainsRight => parent = Some(node),
+					Nesting::RightContainsLeft => {}
+					Nesting::Equal | Nesting::Crossing => return Some(*key),
+					Nesting::Disjoint => unreachable!(),
+				}
+			}
+			level = &parent?.children;
+		}
+	}
+
+	/// Removes the given interval from the map and returns its value, if
+	/// it was in the map. Its children become children of its parent.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	/// map.insert_strict(ii(2, 8), 'b').unwrap();
+	/// map.insert_strict(ii(4, 6), 'c').unwrap();
+	///
+	/// assert_eq!(map.remove(ii(2, 8)), Some('b'));
+	/// assert_eq!(map.remove(ii(2, 8)), None);
+	/// assert_eq!(map.parent(ii(4, 6)), Some((&ii(0, 10), &'a')));
+	/// ```
+	pub fn remove(&mut self, interval: K) -> Option<V> {
+		let mut level = &mut self.roots;
+		loop {
+			let key = *level.get_key_value_at_point(interval.start()).ok()?.0;
+			match nesting(key, interval) {
+				Nesting::Equal => break,
+				Nesting::LeftContainsRight => {
+					level = &mut level
+						.get_at_point_mut(interval.start())
+						.unwrap()
+						.children;
+				}
+				_ => return None,
+			}
+		}
+
+		//siblings don't overlap so this is the only one
+		let (_, node) = level.remove_overlapping(interval).next().unwrap();
+		for (key, child) in node.children {
+			level.insert_unchecked(key, child);
+		}
+		self.len -= 1;
+
+		Some(node.value)
+	}
+
+	/// Returns a reference to the value of the given interval, if it is in
+	/// the map.
+	pub fn get(&self, interval: K) -> Option<&V> {
+		self.find(interval).map(|(_, node)| &node.value)
+	}
+
+	/// Returns `true` if the given interval is in the map.
+	pub fn contains_interval(&self, interval: K) -> bool {
+		self.find(interval).is_some()
+	}
+
+	/// Returns the parent of the given interval, the smallest interval in
+	/// the map containing it, with its value.
+	///
+	/// Returns `None` if the given interval isn't in the map or is on the
+	/// top level.
+	pub fn parent(&self, interval: K) -> Option<(&K, &V)> {
+		self.find(interval)?.0.map(|(key, node)| (key, &node.value))
+	}
+
+	/// Returns an iterator over the children of the given interval, the
+	/// intervals in the map for which it is the parent, with their values
+	/// in ascending order.
+	///
+	/// The iterator is empty if the given interval isn't in the map.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	/// map.insert_strict(ii(6, 8), 'c').unwrap();
+	/// map.insert_strict(ii(1, 4), 'b').unwrap();
+	/// map.insert_strict(ii(2, 3), 'd').unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.children(ii(0, 10)).collect::<Vec<_>>(),
+	/// 	[(&ii(1, 4), &'b'), (&ii(6, 8), &'c')]
+	/// );
+	/// assert_eq!(map.children(ii(2, 3)).count(), 0);
+	/// assert_eq!(map.children(ii(0, 1)).count(), 0);
+	/// ```
+	pub fn children(&self, interval: K) -> impl Iterator<Item = (&K, &V)> {
+		self.find(interval)
+			.into_iter()
+			.flat_map(|(_, node)| node.children.iter())
+			.map(|(key, node)| (key, &node.value))
+	}
+
+	/// Returns an iterator over the top-level intervals in the map, the
+	/// ones without a parent, with their values in ascending order.
+	pub fn roots(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.roots.iter().map(|(key, node)| (key, &node.value))
+	}
+
+	//returns the parent of the given interval along with its own node
+	#[allow(clippy::type_complexity)]
+	fn find(
+		&self,
+		interval: K,
+	) -> Option<(Option<(&K, &Node<I, K, V>)>, &Node<I, K, V>)> {
+		let mut parent = None;
+		let mut level = &self.roots;
+		loop {
+			let (key, node) =
+				level.get_key_value_at_point(interval.start()).ok()?;
+			match nesting(*key, interval) {
+				Nesting::Equal => return Some((parent, node)),
+				Nesting::LeftContainsRight => {
+					parent = Some((key, node));
+					level = &node.children;
+				}
+				_ => return None,
+			}
+		}
+	}
+
+	/// Returns the stack of intervals in the map containing the given
+	/// point with their values, from the outermost to the innermost.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	/// map.insert_strict(ii(2, 8), 'b').unwrap();
+	/// map.insert_strict(ii(12, 14), 'c').unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.ancestors_at_point(5).collect::<Vec<_>>(),
+	/// 	[(&ii(0, 10), &'a'), (&ii(2, 8), &'b')]
+	/// );
+	/// assert_eq!(
+	/// 	map.ancestors_at_point(13).collect::<Vec<_>>(),
+	/// 	[(&ii(12, 14), &'c')]
+	/// );
+	/// assert_eq!(map.ancestors_at_point(11).count(), 0);
+	/// ```
+	pub fn ancestors_at_point(
+		&self,
+		point: I,
+	) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		let mut result = Vec::new();
+
+		let mut level = &self.roots;
+		while let Ok((key, node)) = level.get_key_value_at_point(point) {
+			result.push((key, &node.value));
+			level = &node.children;
+		}
+
+		result.into_iter()
+	}
+
+	/// Returns an iterator over every interval in the map with its value,
+	/// in depth-first order.
+	///
+	/// Each interval comes before its children, which are in ascending
+	/// order, so this is also ascending order of the intervals' start
+	/// points with longer intervals first.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	/// map.insert_strict(ii(6, 8), 'd').unwrap();
+	/// map.insert_strict(ii(2, 3), 'c').unwrap();
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	/// map.insert_strict(ii(2, 4), 'b').unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.iter().map(|(_, value)| *value).collect::<Vec<_>>(),
+	/// 	['a', 'b', 'c', 'd']
+	/// );
+	/// ```
+	pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.iter_with_depth().map(|(_, key, value)| (key, value))
+	}
+
+	/// The same as [`LaminarMap::iter()`] but also returns the depth of
+	/// each interval, the number of intervals containing it, with the
+	/// top-level intervals at depth `0`.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::LaminarMap;
+	///
+	/// let mut map = LaminarMap::new();
+	/// map.insert_strict(ii(0, 10), 'a').unwrap();
+	/// map.insert_strict(ii(2, 4), 'b').unwrap();
+	/// map.insert_strict(ii(2, 3), 'c').unwrap();
+	/// map.insert_strict(ii(12, 14), 'd').unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.iter_with_depth()
+	/// 		.map(|(depth, _, value)| (depth, *value))
+	/// 		.collect::<Vec<_>>(),
+	/// 	[(0, 'a'), (1, 'b'), (2, 'c'), (0, 'd')]
+	/// );
+	/// ```
+	pub fn iter_with_depth(&self) -> impl Iterator<Item = (usize, &K, &V)> {
+		//the siblings left to visit on each level down to the current one,
+		//so the depth of the next interval is one less than its length
+		let mut stack = Vec::from([self.roots.iter()]);
+
+		from_fn(move || loop {
+			let depth = stack.len().checked_sub(1)?;
+			match stack[depth].next() {
+				Some((key, node)) => {
+					stack.push(node.children.iter());
+					return Some((depth, key, &node.value));
+				}
+				None => {
+					stack.pop();
+				}
+			}
+		})
+	}
+
+	/// Allocates a `LaminarMap` and fills it with the intervals and values
+	/// from the given array using [`LaminarMap::insert_strict()`], stopping
+	/// at the first [`CrossingError`].
+	///
+	/// # Panics
+	///
+	/// Panics if any of the given intervals are an invalid interval. See
+	/// [`Invalid
+	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
+	/// for more details.
+	pub fn from_slice_strict<const N: usize>(
+		slice: [(K, V); N],
+	) -> Result<Self, CrossingError<K, V>> {
+		Self::from_iter_strict(slice.into_iter())
+	}
+
+	/// Allocates a `LaminarMap` and fills it with the intervals and values
+	/// from the given iterator using [`LaminarMap::insert_strict()`],
+	/// stopping at the first [`CrossingError`].
+	///
+	/// # Panics
+	///
+	/// Panics if any of the given intervals are an invalid interval. See
+	/// [`Invalid
+	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
+	/// for more details.
+	pub fn from_iter_strict(
+		iter: impl Iterator<Item = (K, V)>,
+	) -> Result<Self, CrossingError<K, V>> {
+		let mut map = LaminarMap::new();
+		for (interval, value) in iter {
+			map.insert_strict(interval, value)?;
+		}
+		Ok(map)
+	}
+}
+
+impl<I, K, V> Default for LaminarMap<I, K, V> {
+	fn default() -> Self {
+		LaminarMap::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::ii;
+	use crate::{InclusiveInterval, Interval};
+
+	/// Checks the map against the intervals it should contain by brute
+	/// force, with parents found as the smallest containing interval.
+	fn check(
+		map: &LaminarMap<i8, Interval<i8>, u8>,
+		intervals: &[Interval<i8>],
+	) {
+		let parent = |interval: Interval<i8>| {
+			intervals
+				.iter()
+				.filter(|other| {
+					**other != interval
+						&& other.contains_point(interval.start())
+						&& other.contains_point(interval.end())
+				})
+				.min_by_key(|other| other.end() - other.start())
+				.copied()
+		};
+
+		assert_eq!(map.len(), intervals.len());
+		let mut sorted = intervals.to_vec();
+		sorted.sort_by_key(|interval| (interval.start(), -interval.end()));
+		assert_eq!(map.iter().map(|(key, _)| *key).collect::<Vec<_>>(), sorted);
+
+		for interval in intervals {
+			assert!(map.contains_interval(*interval));
+			assert_eq!(
+				map.parent(*interval).map(|(key, _)| *key),
+				parent(*interval)
+			);
+			let mut children = intervals
+				.iter()
+				.filter(|other| parent(**other) == Some(*interval))
+				.copied()
+				.collect::<Vec<_>>();
+			children.sort_by_key(|child| child.start());
+			assert_eq!(
+				map.children(*interval)
+					.map(|(key, _)| *key)
+					.collect::<Vec<_>>(),
+				children
+			);
+		}
+
+		for point in -2..20 {
+			let mut expected = intervals
+				.iter()
+				.filter(|interval| interval.contains_point(point))
+				.copied()
+				.collect::<Vec<_>>();
+			expected.sort_by_key(|interval| interval.start() - interval.end());
+			assert_eq!(
+				map.ancestors_at_point(point)
+					.map(|(key, _)| *key)
+					.collect::<Vec<_>>(),
+				expected
+			);
+		}
+	}
+
+	#[test]
+	fn insert_strict_and_remove_tests() {
+		let intervals = [
+			ii(4, 6),
+			ii(0, 15),
+			ii(9, 9),
+			ii(2, 12),
+			ii(8, 10),
+			ii(13, 14),
+			ii(2, 3),
+		]
+		.map(Interval::from);
+
+		let mut map = LaminarMap::new();
+		for (value, interval) in intervals.into_iter().enumerate() {
+			map.insert_strict(interval, value as u8).unwrap();
+			check(&map, &intervals[..=value]);
+		}
+
+		for (crossing, interval) in [
+			(ii(0, 15), ii(-1, 0)),
+			(ii(2, 12), ii(1, 2)),
+			(ii(8, 10), ii(7, 8)),
+			(ii(2, 3), ii(2, 3)),
+			(ii(0, 15), ii(15, 16)),
+		] {
+			assert_eq!(
+				map.insert_strict(interval.into(), 100),
+				Err(CrossingError {
+					interval: interval.into(),
+					crossing: crossing.into(),
+					value: 100,
+				})
+			);
+		}
+		check(&map, &intervals);
+
+		let mut remaining = intervals.to_vec();
+		for interval in
+			[ii(2, 12), ii(9, 9), ii(0, 15), ii(4, 6)].map(Interval::from)
+		{
+			let index = remaining
+				.iter()
+				.position(|other| *other == interval)
+				.unwrap();
+			remaining.remove(index);
+			assert_eq!(
+				map.remove(interval),
+				Some(intervals.iter().position(|x| *x == interval).unwrap()
+					as u8)
+			);
+			assert_eq!(map.remove(interval), None);
+			check(&map, &remaining);
+		}
+	}
+}
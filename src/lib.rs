@@ -5,17 +5,42 @@
 
 extern crate alloc;
 
+pub(crate) mod parse;
 pub(crate) mod utils;
 
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub mod datetime;
 pub mod discrete_finite;
 pub mod gqdit;
 pub mod interval;
+pub mod ip;
+pub mod laminar;
 pub mod nodit;
+pub mod rect;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod zosdit;
 
-pub use crate::discrete_finite::DiscreteFinite;
+pub use crate::discrete_finite::{
+	CheckedArithmetic, Countable, DiscreteFinite, SmallCountable,
+};
+#[cfg(feature = "derive")]
+pub use nodit_derive::DiscreteFinite;
+pub use crate::gqdit::persistent::PersistentGqdit;
 pub use crate::gqdit::{Gqdit, IdType};
-pub use crate::interval::{InclusiveInterval, Interval};
-pub use crate::nodit::map::{IntervalType, NoditMap, OverlapError, PointType};
+pub use crate::interval::bounded::BoundedInterval;
+pub use crate::interval::{
+	EmptyIntervalError, InclusiveInterval, Interval, IntoInterval,
+};
+pub use crate::laminar::{CrossingError, LaminarMap};
+pub use crate::nodit::bimap::BiNoditMap;
+pub use crate::nodit::map::{
+	CollisionPolicy, DiffItem, IntervalType, InvariantError, NoditMap,
+	NonMonotonicError, OverlapError, PointType, Relation,
+};
+pub use crate::nodit::observed::ObservedNoditMap;
+pub use crate::nodit::persistent::PersistentNoditMap;
 pub use crate::nodit::set::NoditSet;
+pub use crate::parse::{ParseError, ParseErrorKind};
+pub use crate::rect::{Rect, RectMap};
 pub use crate::zosdit::map::{NonZeroOverlapError, ZosditMap};
@@ -0,0 +1,554 @@
+//! A module containing [`BiNoditMap`].
+//!
+//! Since [`BiNoditMap`] is just a wrapper around [`NoditMap`] with an extra
+//! reverse index, most of the methods' docs will point towards the
+//! equivalent method's docs on [`NoditMap`] to prevent inconsistency.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::nodit::map::IntoIter as NoditMapIntoIter;
+use crate::utils::expand;
+use crate::{
+	IntervalType, IntoInterval, NoditMap, NoditSet, OverlapError, PointType,
+};
+
+/// A [`NoditMap`] which also maintains a reverse index from each value to
+/// the set of intervals associated with that value.
+///
+/// This makes finding every interval associated with a given value, via
+/// [`BiNoditMap::intervals_of()`], a single `O(log n)` lookup rather than an
+/// `O(n)` scan of the map.
+///
+/// Since the reverse index is keyed by the values, there is no way to get
+/// mutable references to the values in the map, instead you can remove
+/// and re-insert an entry.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is a interval over.
+///
+/// `K` is the generic type parameter for the interval type stored as the
+/// keys in the map.
+///
+/// `V` is the generic type parameter for the values associated with the
+/// keys in the map.
+///
+/// Phrasing it another way: `I` is the point type, `K` is the interval type, and `V` is the value type.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ie;
+/// use nodit::{BiNoditMap, NoditSet};
+///
+/// let mut bookings = BiNoditMap::new();
+///
+/// bookings.insert_strict(ie(9, 10), "alice").unwrap();
+/// bookings.insert_strict(ie(10, 12), "bob").unwrap();
+/// bookings.insert_strict(ie(14, 15), "alice").unwrap();
+///
+/// assert_eq!(
+/// 	bookings.intervals_of(&"alice"),
+/// 	Some(
+/// 		&NoditSet::from_slice_strict([ie(9, 10), ie(14, 15)])
+/// 			.unwrap()
+/// 	)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BiNoditMap<I, K, V> {
+	map: NoditMap<I, K, V>,
+	index: BTreeMap<V, NoditSet<I, K>>,
+}
+
+impl<I, K, V> BiNoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Ord + Clone,
+{
+	/// Returns the set of intervals associated with the given value, if
+	/// there are any.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{BiNoditMap, NoditSet};
+	///
+	/// let mut map = BiNoditMap::new();
+	///
+	/// map.insert_strict(ie(1, 4), false).unwrap();
+	/// map.insert_strict(ie(4, 8), true).unwrap();
+	/// map.insert_strict(ie(8, 100), false).unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.intervals_of(&false),
+	/// 	Some(
+	/// 		&NoditSet::from_slice_strict([ie(1, 4), ie(8, 100)])
+	/// 			.unwrap()
+	/// 	)
+	/// );
+	///
+	/// map.cut(ie(4, 8));
+	///
+	/// assert_eq!(map.intervals_of(&true), None);
+	/// ```
+	pub fn intervals_of(&self, value: &V) -> Option<&NoditSet<I, K>> {
+		self.index.get(value)
+	}
+
+	/// Removes every entry associated with the given value from the map
+	/// and returns the set of their intervals, if there were any.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{BiNoditMap, NoditSet};
+	///
+	/// let mut map = BiNoditMap::new();
+	///
+	/// map.insert_strict(ie(1, 4), false).unwrap();
+	/// map.insert_strict(ie(4, 8), true).unwrap();
+	/// map.insert_strict(ie(8, 100), false).unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.remove_value(&false),
+	/// 	Some(
+	/// 		NoditSet::from_slice_strict([ie(1, 4), ie(8, 100)])
+	/// 			.unwrap()
+	/// 	)
+	/// );
+	///
+	/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&ie(4, 8), &true)]);
+	/// ```
+	pub fn remove_value(&mut self, value: &V) -> Option<NoditSet<I, K>> {
+		let intervals = self.index.remove(value)?;
+
+		for interval in intervals.iter() {
+			let _ = self.map.remove_overlapping(*interval);
+		}
+
+		Some(intervals)
+	}
+
+	/// Returns a reference to the underlying [`NoditMap`].
+	pub fn as_map(&self) -> &NoditMap<I, K, V> {
+		&self.map
+	}
+
+	/// Consumes the map returning the underlying [`NoditMap`].
+	pub fn into_map(self) -> NoditMap<I, K, V> {
+		self.map
+	}
+
+	/// See [`NoditMap::overlaps()`] for more details.
+	pub fn overlaps<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.overlaps(interval)
+	}
+	/// See [`NoditMap::overlapping()`] for more details.
+	pub fn overlapping<Q, M>(
+		&self,
+		interval: Q,
+	) -> impl DoubleEndedIterator<Item = (&K, &V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.overlapping(interval)
+	}
+	/// See [`NoditMap::get_at_point()`] for more details.
+	pub fn get_at_point(&self, point: I) -> Option<&V> {
+		self.map.get_at_point(point)
+	}
+	/// See [`NoditMap::get_key_value_at_point()`] for more details.
+	pub fn get_key_value_at_point(&self, point: I) -> Result<(&K, &V), K> {
+		self.map.get_key_value_at_point(point)
+	}
+	/// See [`NoditMap::contains_point()`] for more details.
+	pub fn contains_point(&self, point: I) -> bool {
+		self.map.contains_point(point)
+	}
+	/// See [`NoditMap::gaps_untrimmed()`] for more details.
+	pub fn gaps_untrimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		self.map.gaps_untrimmed(interval)
+	}
+	/// See [`NoditMap::gaps_trimmed()`] for more details.
+	pub fn gaps_trimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		self.map.gaps_trimmed(interval)
+	}
+	/// See [`NoditMap::contains_interval()`] for more details.
+	pub fn contains_interval<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.contains_interval(interval)
+	}
+
+	/// See [`NoditMap::remove_overlapping()`] for more details.
+	pub fn remove_overlapping<Q, M>(
+		&mut self,
+		interval: Q,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		let removed = self.map.remove_overlapping(interval).collect::<Vec<_>>();
+
+		for (interval, value) in removed.iter() {
+			self.unindex(*interval, value);
+		}
+
+		removed.into_iter()
+	}
+	/// See [`NoditMap::cut()`] for more details.
+	pub fn cut<Q, M>(&mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		let cut = self.map.cut(interval).collect::<Vec<_>>();
+
+		for (interval, value) in cut.iter() {
+			self.unindex(*interval, value);
+		}
+
+		cut.into_iter()
+	}
+
+	/// See [`NoditMap::insert_strict()`] for more details.
+	pub fn insert_strict(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		self.map.insert_strict(interval, value.clone())?;
+		self.index(interval, value);
+		Ok(())
+	}
+	/// See [`NoditMap::insert_merge_touching()`] for more details.
+	pub fn insert_merge_touching(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		self.insert_merge_with(interval, value, |map, interval, value| {
+			map.insert_merge_touching(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_merge_touching_if_values_equal()`] for more
+	/// details.
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		self.insert_merge_with(interval, value, |map, interval, value| {
+			map.insert_merge_touching_if_values_equal(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_merge_overlapping()`] for more details.
+	pub fn insert_merge_overlapping(&mut self, interval: K, value: V) -> K {
+		self.insert_merge_with(interval, value, |map, interval, value| {
+			Ok(map.insert_merge_overlapping(interval, value))
+		})
+		.unwrap_or_else(|_| unreachable!())
+	}
+	/// See [`NoditMap::insert_merge_touching_or_overlapping()`] for more
+	/// details.
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> K {
+		self.insert_merge_with(interval, value, |map, interval, value| {
+			Ok(map.insert_merge_touching_or_overlapping(interval, value))
+		})
+		.unwrap_or_else(|_| unreachable!())
+	}
+	/// See [`NoditMap::insert_overwrite()`] for more details.
+	pub fn insert_overwrite(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> impl Iterator<Item = (K, V)> {
+		let cut = self.cut(interval).collect::<Vec<_>>();
+		//the interval was just cut out of the map so it can't overlap
+		self.map.insert_unchecked(interval, value.clone());
+		self.index(interval, value);
+		cut.into_iter()
+	}
+
+	/// See [`NoditMap::from_slice_strict()`] for more details.
+	pub fn from_slice_strict<const N: usize>(
+		slice: [(K, V); N],
+	) -> Result<BiNoditMap<I, K, V>, OverlapError<K, V>> {
+		BiNoditMap::from_iter_strict(slice.into_iter())
+	}
+	/// See [`NoditMap::from_iter_strict()`] for more details.
+	pub fn from_iter_strict(
+		iter: impl Iterator<Item = (K, V)>,
+	) -> Result<BiNoditMap<I, K, V>, OverlapError<K, V>> {
+		let mut map = BiNoditMap::new();
+		for (interval, value) in iter {
+			map.insert_strict(interval, value)?;
+		}
+		Ok(map)
+	}
+
+	fn insert_merge_with<F>(
+		&mut self,
+		interval: K,
+		value: V,
+		insert: F,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		F: FnOnce(
+			&mut NoditMap<I, K, V>,
+			K,
+			V,
+		) -> Result<K, OverlapError<K, V>>,
+	{
+		//every entry which could be merged either overlaps or touches the
+		//given interval, so it must overlap the interval expanded by one
+		//point on either side
+		let candidates = self
+			.map
+			.overlapping(expand(interval))
+			.map(|(interval, value)| (*interval, value.clone()))
+			.collect::<Vec<_>>();
+
+		let merged = insert(&mut self.map, interval, value.clone())?;
+
+		//any of the candidates which are now inside the merged interval
+		//must have been merged into it
+		for (candidate, candidate_value) in candidates {
+			if merged.contains_interval(&candidate) {
+				self.unindex(candidate, &candidate_value);
+			}
+		}
+		self.index(merged, value);
+
+		Ok(merged)
+	}
+	fn index(&mut self, interval: K, value: V) {
+		//every interval in the index is also in the map, where they can't
+		//overlap
+		self.index
+			.entry(value)
+			.or_default()
+			.insert_unchecked(interval);
+	}
+	fn unindex(&mut self, interval: K, value: &V) {
+		if let Some(intervals) = self.index.get_mut(value) {
+			let _ = intervals.cut(interval);
+
+			if intervals.is_empty() {
+				self.index.remove(value);
+			}
+		}
+	}
+}
+
+impl<I, K, V> BiNoditMap<I, K, V> {
+	/// See [`NoditMap::new()`] for more details.
+	pub fn new() -> Self {
+		BiNoditMap {
+			map: NoditMap::new(),
+			index: BTreeMap::new(),
+		}
+	}
+	/// See [`NoditMap::len()`] for more details.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+	/// See [`NoditMap::is_empty()`] for more details.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+	/// See [`NoditMap::iter()`] for more details.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.map.iter()
+	}
+	/// See [`NoditMap::first_key_value()`] for more details.
+	pub fn first_key_value(&self) -> Option<(&K, &V)> {
+		self.map.first_key_value()
+	}
+	/// See [`NoditMap::last_key_value()`] for more details.
+	pub fn last_key_value(&self) -> Option<(&K, &V)> {
+		self.map.last_key_value()
+	}
+	/// Returns an iterator over every distinct value in the map along
+	/// with the set of intervals associated with it, in ascending order of
+	/// the values.
+	pub fn values(
+		&self,
+	) -> impl DoubleEndedIterator<Item = (&V, &NoditSet<I, K>)> {
+		self.index.iter()
+	}
+}
+
+// Trait Impls ==========================
+
+impl<I, K, V> IntoIterator for BiNoditMap<I, K, V> {
+	type Item = (K, V);
+	type IntoIter = NoditMapIntoIter<I, K, V>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.map.into_iter()
+	}
+}
+
+impl<I, K, V> Default for BiNoditMap<I, K, V> {
+	fn default() -> Self {
+		BiNoditMap::new()
+	}
+}
+
+impl<I, K, V> From<NoditMap<I, K, V>> for BiNoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Ord + Clone,
+{
+	fn from(map: NoditMap<I, K, V>) -> Self {
+		BiNoditMap {
+			index: map.group_by_value(),
+			map,
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+	use core::fmt;
+
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	use crate::{BiNoditMap, IntervalType, NoditMap, PointType};
+
+	impl<I, K, V> Serialize for BiNoditMap<I, K, V>
+	where
+		K: Serialize,
+		V: Serialize,
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: Serializer,
+		{
+			self.map.serialize(serializer)
+		}
+	}
+
+	impl<'de, I, K, V> Deserialize<'de> for BiNoditMap<I, K, V>
+	where
+		I: PointType,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
+		V: Ord + Clone + Deserialize<'de>,
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: Deserializer<'de>,
+		{
+			NoditMap::deserialize(deserializer).map(BiNoditMap::from)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::Interval;
+	use crate::interval::{ee, ie, ii, iu, ui};
+
+	fn basic() -> BiNoditMap<i8, Interval<i8>, u8> {
+		BiNoditMap::from_slice_strict([
+			(ui(4).into(), 0),
+			(ee(5, 7).into(), 1),
+			(ii(7, 7).into(), 0),
+			(ie(14, 16).into(), 1),
+		])
+		.unwrap()
+	}
+
+	fn assert_in_sync(map: &BiNoditMap<i8, Interval<i8>, u8>) {
+		assert_eq!(map.index, map.map.group_by_value());
+	}
+
+	#[test]
+	fn index_stays_in_sync_tests() {
+		let intervals = [ii(0, 0), ii(4, 6), ie(7, 14), iu(15), ui(-100)]
+			.map(Interval::from);
+
+		for interval in intervals {
+			for value in 0..3 {
+				let mut map = basic();
+				let _ = map.insert_strict(interval, value);
+				assert_in_sync(&map);
+
+				let mut map = basic();
+				let _ = map.insert_merge_touching(interval, value);
+				assert_in_sync(&map);
+
+				let mut map = basic();
+				let _ =
+					map.insert_merge_touching_if_values_equal(interval, value);
+				assert_in_sync(&map);
+
+				let mut map = basic();
+				map.insert_merge_overlapping(interval, value);
+				assert_in_sync(&map);
+
+				let mut map = basic();
+				map.insert_merge_touching_or_overlapping(interval, value);
+				assert_in_sync(&map);
+
+				let mut map = basic();
+				let _ = map.insert_overwrite(interval, value);
+				assert_in_sync(&map);
+			}
+
+			let mut map = basic();
+			let _ = map.cut(interval);
+			assert_in_sync(&map);
+
+			let mut map = basic();
+			let _ = map.remove_overlapping(interval);
+			assert_in_sync(&map);
+		}
+	}
+
+	#[test]
+	fn remove_value_tests() {
+		let mut map = basic();
+
+		assert_eq!(
+			map.remove_value(&1),
+			Some(
+				NoditSet::from_slice_strict([
+					ee(5, 7).into(),
+					ie(14, 16).into(),
+				])
+				.unwrap()
+			)
+		);
+		assert_eq!(map.remove_value(&1), None);
+		assert_eq!(
+			map.iter().collect::<Vec<_>>(),
+			[(&ui(4).into(), &0), (&ii(7, 7).into(), &0)]
+		);
+		assert_in_sync(&map);
+	}
+}
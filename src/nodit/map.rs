@@ -1,7 +1,11 @@
 //! A module containing [`NoditMap`].
 
+use alloc::collections::BTreeMap as StdBTreeMap;
 use alloc::vec::Vec;
+use core::fmt;
 use core::marker::PhantomData;
+use core::ops::{Add, Rem, Sub};
+use core::str::FromStr;
 
 use btree_monstrousity::BTreeMap;
 use btree_monstrousity::btree_map::{
@@ -9,11 +13,17 @@ use btree_monstrousity::btree_map::{
 };
 use itertools::Itertools;
 
+use crate::parse::{parse_entries, ParseError, ParseErrorKind};
 use crate::utils::{
-	cut_interval, invalid_interval_panic, overlapping_comp, starts_comp,
+	check_ordered_intervals, cut_interval, invalid_interval_panic,
+	overlapping_comp, query_interval, refinement, starts_comp,
 	touching_end_comp, touching_start_comp,
 };
-use crate::{DiscreteFinite, InclusiveInterval, Interval};
+use crate::nodit::transaction::{History, Transaction};
+use crate::{
+	Countable, DiscreteFinite, InclusiveInterval, Interval, IntoInterval,
+	NoditSet,
+};
 
 /// An ordered map of non-overlapping intervals based on [`BTreeMap`].
 ///
@@ -62,13 +72,226 @@ pub struct NoditMap<I, K, V> {
 }
 
 /// The error returned when inserting a interval that overlaps another interval when
-/// it should not have. Contains the value that was not inserted.
+/// it should not have. Contains the interval and value that were not inserted,
+/// along with the first interval they overlapped.
+///
+/// The error only stores the first overlapped interval so that it stays
+/// cheap to create, use [`NoditMap::overlapping()`] with `interval` if you
+/// need all of them.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ie;
+/// use nodit::{NoditMap, OverlapError};
+///
+/// let mut map =
+/// 	NoditMap::from_slice_strict([(ie(1, 4), false), (ie(6, 8), true)])
+/// 		.unwrap();
+///
+/// let error = map.insert_strict(ie(2, 7), true).unwrap_err();
+///
+/// assert_eq!(
+/// 	error,
+/// 	OverlapError {
+/// 		interval: ie(2, 7),
+/// 		conflict: ie(1, 4),
+/// 		value: true,
+/// 	}
+/// );
+/// assert_eq!(
+/// 	error.to_string(),
+/// 	"interval [2, 7) overlaps the existing interval [1, 4)"
+/// );
+/// assert_eq!(map.overlapping(error.interval).count(), 2);
+/// ```
 #[derive(PartialEq, Debug)]
-pub struct OverlapError<V> {
+pub struct OverlapError<K, V> {
+	/// The interval which was not inserted, because of the overlap error.
+	pub interval: K,
+	/// The first interval already in the map which `interval` overlapped.
+	pub conflict: K,
 	/// The value which was not inserted, because of the overlap error.
 	pub value: V,
 }
 
+impl<K, V> fmt::Display for OverlapError<K, V>
+where
+	K: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"interval {} overlaps the existing interval {}",
+			self.interval, self.conflict
+		)
+	}
+}
+impl<K, V> core::error::Error for OverlapError<K, V>
+where
+	K: fmt::Debug + fmt::Display,
+	V: fmt::Debug,
+{
+}
+
+/// The error returned when remapping the points of a map with a function
+/// that is not strictly monotone. Contains the two points whose images
+/// were not in strictly ascending order.
+#[derive(PartialEq, Debug)]
+pub struct NonMonotonicError<I> {
+	/// The smaller of the two points.
+	pub first: I,
+	/// The larger of the two points, whose image was not greater than
+	/// the image of `first`.
+	pub second: I,
+}
+
+impl<I> fmt::Display for NonMonotonicError<I>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"the function is not strictly monotone between the points {:?} \
+			 and {:?}",
+			self.first, self.second
+		)
+	}
+}
+impl<I> core::error::Error for NonMonotonicError<I> where I: fmt::Debug {}
+
+/// How [`NoditMap::quantize()`] should resolve two entries whose intervals
+/// overlap once they have been rounded to the grid.
+pub enum CollisionPolicy<'a, V> {
+	/// The earlier entry keeps the overlapping part and the later entry
+	/// is trimmed, or dropped if nothing of it remains.
+	FirstWins,
+	/// The two entries are merged into one entry spanning both intervals,
+	/// with the values combined by the given function as `f(earlier,
+	/// later)`.
+	Merge(&'a mut dyn FnMut(V, V) -> V),
+	/// An [`OverlapError`] is returned containing the value of the later
+	/// entry.
+	Error,
+}
+
+/// The relation between the points covered by two maps or sets, as
+/// returned by [`NoditMap::relation()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation<I> {
+	/// Both cover exactly the same points.
+	Equal,
+	/// Every point covered by `self` is also covered by `other`, but not
+	/// the other way around.
+	ProperSubset {
+		/// The first point covered by `other` but not by `self`.
+		witness: I,
+	},
+	/// Every point covered by `other` is also covered by `self`, but not
+	/// the other way around.
+	ProperSuperset {
+		/// The first point covered by `self` but not by `other`.
+		witness: I,
+	},
+	/// Neither covers every point of the other.
+	Incomparable {
+		/// The first point covered by `self` but not by `other`.
+		only_in_self: I,
+		/// The first point covered by `other` but not by `self`.
+		only_in_other: I,
+	},
+}
+
+/// A single change between two maps, as returned by [`NoditMap::diff()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffItem<'a, K, V> {
+	/// The interval is only in the new map, with the given value.
+	Added(K, &'a V),
+	/// The interval is only in the old map, with the given value.
+	Removed(K, &'a V),
+	/// The interval is in both maps but its value changed from the first
+	/// value to the second value.
+	Changed(K, &'a V, &'a V),
+}
+
+/// The error returned by the `check_invariants()` methods, such as
+/// [`NoditMap::check_invariants()`], describing the first internal
+/// invariant of the data-structure found not to hold.
+///
+/// This should only ever be returned if there is a bug in this crate, or
+/// in the [`Ord`] or [`DiscreteFinite`] implementations of the point type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantError<I> {
+	/// The interval is invalid, see [`Invalid
+	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals).
+	InvalidInterval {
+		/// The invalid interval.
+		interval: Interval<I>,
+	},
+	/// The intervals are stored in the wrong order.
+	Unsorted {
+		/// The interval stored first.
+		first: Interval<I>,
+		/// The interval stored second, which should have been first.
+		second: Interval<I>,
+	},
+	/// The intervals overlap, or for a [`ZosditMap`](crate::ZosditMap)
+	/// they overlap by more than a single point.
+	Overlap {
+		/// The interval stored first.
+		first: Interval<I>,
+		/// The interval stored second.
+		second: Interval<I>,
+	},
+	/// The interval has no values stored with it.
+	EmptyValueStore {
+		/// The interval with no values.
+		interval: Interval<I>,
+	},
+	/// The stored length doesn't match the number of entries.
+	LenMismatch {
+		/// The stored length.
+		len: usize,
+		/// The number of entries counted.
+		counted: usize,
+	},
+	/// The intervals don't cover the whole domain of the point type, as
+	/// they should in a [`Gqdit`](crate::Gqdit).
+	Gap {
+		/// The uncovered gap.
+		gap: Interval<I>,
+	},
+}
+
+impl<I> fmt::Display for InvariantError<I>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			InvariantError::InvalidInterval { interval } => {
+				write!(f, "invalid interval {interval:?}")
+			}
+			InvariantError::Unsorted { first, second } => {
+				write!(f, "interval {first:?} is stored before {second:?}")
+			}
+			InvariantError::Overlap { first, second } => {
+				write!(f, "interval {first:?} overlaps {second:?}")
+			}
+			InvariantError::EmptyValueStore { interval } => {
+				write!(f, "interval {interval:?} has no values")
+			}
+			InvariantError::LenMismatch { len, counted } => {
+				write!(f, "stored length {len} but counted {counted} entries")
+			}
+			InvariantError::Gap { gap } => {
+				write!(f, "the gap {gap:?} is not covered")
+			}
+		}
+	}
+}
+impl<I> core::error::Error for InvariantError<I> where I: fmt::Debug {}
+
 /// The marker trait for valid point types, a blanket implementation is provided for all types
 /// which implement this traits' super-traits so you shouln't need to implement this yourself.
 pub trait PointType: Ord + Copy + DiscreteFinite {}
@@ -92,12 +315,6 @@ where
 	/// Returns `true` if the given interval overlaps any of the
 	/// intervals in the map, and `false` if not.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ie, ii};
@@ -113,24 +330,16 @@ where
 	/// assert_eq!(map.overlaps(ii(4, 5)), true);
 	/// assert_eq!(map.overlaps(ie(4, 6)), true);
 	/// ```
-	pub fn overlaps<Q>(&self, interval: Q) -> bool
+	pub fn overlaps<Q, M>(&self, interval: Q) -> bool
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
 		self.overlapping(interval).next().is_some()
 	}
 
 	/// Returns an iterator over every entry in the map that overlaps
 	/// the given interval in ascending order.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
@@ -149,33 +358,34 @@ where
 	/// 	overlapping.collect::<Vec<_>>(),
 	/// 	[(&ie(1, 4), &false), (&ie(4, 8), &true)]
 	/// );
+	///
+	/// // the std range types can be used directly too
+	/// assert_eq!(map.overlapping(2..8).count(), 2);
+	/// assert_eq!(map.overlapping(..).count(), 3);
 	/// ```
-	pub fn overlapping<Q>(
+	pub fn overlapping<Q, M>(
 		&self,
 		interval: Q,
 	) -> impl DoubleEndedIterator<Item = (&K, &V)>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		self.inner.range(
-			overlapping_comp(interval.start()),
-			SearchBoundCustom::Included,
-			overlapping_comp(interval.end()),
-			SearchBoundCustom::Included,
-		)
+		query_interval(interval)
+			.map(|interval| {
+				self.inner.range(
+					overlapping_comp(interval.start()),
+					SearchBoundCustom::Included,
+					overlapping_comp(interval.end()),
+					SearchBoundCustom::Included,
+				)
+			})
+			.into_iter()
+			.flatten()
 	}
 
 	/// Returns an mutable iterator over every entry in the map that
 	/// overlaps the given interval in ascending order.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
@@ -196,21 +406,24 @@ where
 	/// 	}
 	/// }
 	/// ```
-	pub fn overlapping_mut<Q>(
+	pub fn overlapping_mut<Q, M>(
 		&mut self,
 		interval: Q,
 	) -> impl DoubleEndedIterator<Item = (&K, &mut V)>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		self.inner.range_mut(
-			overlapping_comp(interval.start()),
-			SearchBoundCustom::Included,
-			overlapping_comp(interval.end()),
-			SearchBoundCustom::Included,
-		)
+		query_interval(interval)
+			.map(|interval| {
+				self.inner.range_mut(
+					overlapping_comp(interval.start()),
+					SearchBoundCustom::Included,
+					overlapping_comp(interval.end()),
+					SearchBoundCustom::Included,
+				)
+			})
+			.into_iter()
+			.flatten()
 	}
 
 	/// Returns a reference to the value corresponding to the interval in
@@ -320,6 +533,8 @@ where
 			.inner
 			.lower_bound(overlapping_comp(point), SearchBoundCustom::Included);
 
+		//point isn't in any interval so the interval below it must end
+		//before it and the interval above it must start after it
 		Interval {
 			start: lower
 				.key()
@@ -333,12 +548,6 @@ where
 	/// Removes every entry in the map which overlaps the given interval
 	/// and returns them in an iterator in ascending order.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
@@ -363,14 +572,16 @@ where
 	/// 	[(ie(8, 100), false)]
 	/// );
 	/// ```
-	pub fn remove_overlapping<'a, Q>(
+	pub fn remove_overlapping<'a, Q, M>(
 		&'a mut self,
 		interval: Q,
 	) -> impl Iterator<Item = (K, V)>
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
-		invalid_interval_panic(interval);
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
 
 		let mut result = Vec::new();
 
@@ -397,12 +608,6 @@ where
 	/// using `Clone`. Or if you partially cut a interval then
 	/// `V` must be cloned to be returned in the iterator.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ie, ii};
@@ -427,12 +632,14 @@ where
 	/// );
 	/// assert_eq!(base, after_cut);
 	/// ```
-	pub fn cut<'a, Q>(&'a mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	pub fn cut<'a, Q, M>(&'a mut self, interval: Q) -> impl Iterator<Item = (K, V)>
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 		V: Clone,
 	{
-		invalid_interval_panic(interval);
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
 
 		let mut result = Vec::new();
 
@@ -469,12 +676,6 @@ where
 	/// See [`NoditMap::gaps_trimmed()`] if you require the returned
 	/// gaps to be trimmed to be fully contained within given `interval`.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ie, ii, iu};
@@ -494,15 +695,22 @@ where
 	/// 	[ie(3, 5), ie(7, 9), iu(100)]
 	/// );
 	/// ```
-	pub fn gaps_untrimmed<'a, Q>(
+	pub fn gaps_untrimmed<'a, Q, M>(
 		&'a self,
 		interval: Q,
-	) -> impl Iterator<Item = K> + '_
+	) -> impl Iterator<Item = K> + 'a
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
-		invalid_interval_panic(interval);
-
+		query_interval(interval)
+			.map(|interval| self.gaps_untrimmed_raw(interval))
+			.into_iter()
+			.flatten()
+	}
+	fn gaps_untrimmed_raw(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = K> + '_ {
 		// If the start or end point of interval is not
 		// contained within a interval in the map then we need to
 		// generate the gaps.
@@ -532,6 +740,8 @@ where
 			.overlapping(interval)
 			.map(|(key, _)| (key.start(), key.end()));
 
+		//first ends before second starts so neither can be at the edge of
+		//the domain
 		let inner_gaps = overlapping
 			.tuple_windows()
 			.map(|(first, second)| {
@@ -557,12 +767,6 @@ where
 	/// See [`NoditMap::gaps_untrimmed()`] if you do not want the
 	/// returned gaps to be trimmed.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ie, ii, iu};
@@ -582,15 +786,22 @@ where
 	/// 	[ie(4, 5), ie(7, 9), ii(100, 120)]
 	/// );
 	/// ```
-	pub fn gaps_trimmed<'a, Q>(
+	pub fn gaps_trimmed<'a, Q, M>(
 		&'a self,
 		interval: Q,
-	) -> impl Iterator<Item = K> + '_
+	) -> impl Iterator<Item = K> + 'a
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
-		invalid_interval_panic(interval);
-
+		query_interval(interval)
+			.map(|interval| self.gaps_trimmed_raw(interval))
+			.into_iter()
+			.flatten()
+	}
+	fn gaps_trimmed_raw(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = K> + '_ {
 		// If the start or end point of interval is not
 		// contained within a interval in the map then we need to
 		// generate the gaps.
@@ -632,6 +843,8 @@ where
 			.overlapping(interval)
 			.map(|(key, _)| (key.start(), key.end()));
 
+		//first ends before second starts so neither can be at the edge of
+		//the domain
 		let inner_gaps = overlapping
 			.tuple_windows()
 			.map(|(first, second)| {
@@ -653,12 +866,6 @@ where
 	/// Returns `true` if the map covers every point in the given
 	/// interval, and `false` if it does not.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
@@ -675,12 +882,10 @@ where
 	/// assert_eq!(map.contains_interval(ie(2, 6)), false);
 	/// assert_eq!(map.contains_interval(ie(6, 100)), true);
 	/// ```
-	pub fn contains_interval<Q>(&self, interval: Q) -> bool
+	pub fn contains_interval<Q, M>(&self, interval: Q) -> bool
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
 		// Soooo clean and mathematical!
 		self.gaps_untrimmed(interval).next().is_none()
 	}
@@ -707,7 +912,11 @@ where
 	/// assert_eq!(map.insert_strict(ie(5, 10), 9), Ok(()));
 	/// assert_eq!(
 	/// 	map.insert_strict(ie(5, 10), 2),
-	/// 	Err(OverlapError { value: 2 })
+	/// 	Err(OverlapError {
+	/// 		interval: ie(5, 10),
+	/// 		conflict: ie(5, 10),
+	/// 		value: 2,
+	/// 	})
 	/// );
 	/// assert_eq!(map.len(), 1);
 	/// ```
@@ -715,20 +924,37 @@ where
 		&mut self,
 		interval: K,
 		value: V,
-	) -> Result<(), OverlapError<V>> {
+	) -> Result<(), OverlapError<K, V>> {
 		invalid_interval_panic(interval);
 
 		if self.overlaps(interval) {
-			return Err(OverlapError { value });
+			return Err(self.overlap_error(interval, value));
 		}
 
 		self.insert_unchecked(interval, value);
 
 		Ok(())
 	}
-	fn insert_unchecked(&mut self, interval: K, value: V) {
+	pub(crate) fn insert_unchecked(&mut self, interval: K, value: V) {
 		self.inner.insert(interval, value, starts_comp());
 	}
+	//must only be called once the interval is known to overlap the map
+	pub(crate) fn overlap_error(
+		&self,
+		interval: K,
+		value: V,
+	) -> OverlapError<K, V> {
+		let (conflict, _) = self
+			.overlapping(interval)
+			.next()
+			.expect("the interval overlaps the map");
+
+		OverlapError {
+			interval,
+			conflict: *conflict,
+			value,
+		}
+	}
 
 	fn insert_merge_with_comps<G1, G2, R1, R2>(
 		&mut self,
@@ -815,7 +1041,11 @@ where
 	/// // Overlapping
 	/// assert_eq!(
 	/// 	map.insert_merge_touching(ie(4, 8), false),
-	/// 	Err(OverlapError { value: false }),
+	/// 	Err(OverlapError {
+	/// 		interval: ie(4, 8),
+	/// 		conflict: ie(1, 8),
+	/// 		value: false,
+	/// 	}),
 	/// );
 	///
 	/// // Neither Touching or Overlapping
@@ -833,11 +1063,11 @@ where
 		&mut self,
 		interval: K,
 		value: V,
-	) -> Result<K, OverlapError<V>> {
+	) -> Result<K, OverlapError<K, V>> {
 		invalid_interval_panic(interval);
 
 		if self.overlaps(interval) {
-			return Err(OverlapError { value });
+			return Err(self.overlap_error(interval, value));
 		}
 
 		Ok(self.insert_merge_with_comps(
@@ -903,7 +1133,11 @@ where
 	/// // Overlapping
 	/// assert_eq!(
 	/// 	map.insert_merge_touching_if_values_equal(ie(4, 8), false),
-	/// 	Err(OverlapError { value: false }),
+	/// 	Err(OverlapError {
+	/// 		interval: ie(4, 8),
+	/// 		conflict: ie(4, 8),
+	/// 		value: false,
+	/// 	}),
 	/// );
 	///
 	/// // Neither Touching or Overlapping
@@ -921,14 +1155,14 @@ where
 		&mut self,
 		interval: K,
 		value: V,
-	) -> Result<K, OverlapError<V>>
+	) -> Result<K, OverlapError<K, V>>
 	where
 		V: Eq,
 	{
 		invalid_interval_panic(interval);
 
 		if self.overlaps(interval) {
-			return Err(OverlapError { value });
+			return Err(self.overlap_error(interval, value));
 		}
 
 		let get_start = |selfy: &Self, value: &V| {
@@ -1173,7 +1407,10 @@ where
 	{
 		invalid_interval_panic(interval);
 
-		let cut = self.cut(interval);
+		let cut = self.cut(Interval {
+			start: interval.start(),
+			end: interval.end(),
+		});
 		self.insert_unchecked(interval, value);
 		cut
 	}
@@ -1205,7 +1442,7 @@ where
 	/// ```
 	pub fn from_slice_strict<const N: usize>(
 		slice: [(K, V); N],
-	) -> Result<NoditMap<I, K, V>, OverlapError<V>> {
+	) -> Result<NoditMap<I, K, V>, OverlapError<K, V>> {
 		NoditMap::from_iter_strict(slice.into_iter())
 	}
 
@@ -1224,7 +1461,7 @@ where
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
-	/// use nodit::NoditMap;
+	/// use nodit::{InclusiveInterval, NoditMap};
 	///
 	/// let slice =
 	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), false)];
@@ -1238,65 +1475,92 @@ where
 	/// ```
 	pub fn from_iter_strict(
 		iter: impl Iterator<Item = (K, V)>,
-	) -> Result<NoditMap<I, K, V>, OverlapError<V>> {
+	) -> Result<NoditMap<I, K, V>, OverlapError<K, V>> {
 		let mut map = NoditMap::new();
 		for (interval, value) in iter {
 			map.insert_strict(interval, value)?;
 		}
 		Ok(map)
 	}
-}
 
-impl<I, K, V> NoditMap<I, K, V> {
-	/// Makes a new, empty [`NoditMap`].
+	/// Consumes the map and returns a new map with the same intervals
+	/// but with every value transformed by `f`.
 	///
-	/// # Examples
-	/// ```
-	/// use nodit::{Interval, NoditMap};
-	///
-	/// let map: NoditMap<i8, Interval<i8>, bool> = NoditMap::new();
-	/// ```
-	pub fn new() -> Self {
-		Self::default()
-	}
-
-	/// Returns the number of intervals in the map.
+	/// Since the intervals are not changed, the new map does not need to
+	/// re-check the intervals for overlaps.
 	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
 	/// use nodit::NoditMap;
 	///
-	/// let mut map = NoditMap::new();
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 2),
+	/// 	(ie(4, 8), 4),
+	/// 	(ie(8, 100), 8),
+	/// ])
+	/// .unwrap();
 	///
-	/// assert_eq!(map.len(), 0);
-	/// map.insert_strict(ie(0, 1), false).unwrap();
-	/// assert_eq!(map.len(), 1);
+	/// let map = map.map_values(|value| value > 3);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), true)]
+	/// );
 	/// ```
-	pub fn len(&self) -> usize {
-		self.inner.len()
+	pub fn map_values<W, F>(self, mut f: F) -> NoditMap<I, K, W>
+	where
+		F: FnMut(V) -> W,
+	{
+		self.filter_map_values(|value| Some(f(value)))
 	}
 
-	/// Returns `true` if the map contains no intervals, and
-	/// `false` if it does.
+	/// Consumes the map and returns a new map with every value
+	/// transformed by `f`, entries for which `f` returns `None` are
+	/// removed, leaving gaps where their intervals were.
+	///
+	/// Since the remaining intervals are not changed, the new map does
+	/// not need to re-check the intervals for overlaps.
 	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
 	/// use nodit::NoditMap;
 	///
-	/// let mut map = NoditMap::new();
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 2),
+	/// 	(ie(4, 8), 3),
+	/// 	(ie(8, 100), 8),
+	/// ])
+	/// .unwrap();
 	///
-	/// assert_eq!(map.is_empty(), true);
-	/// map.insert_strict(ie(0, 1), false).unwrap();
-	/// assert_eq!(map.is_empty(), false);
+	/// let map =
+	/// 	map.filter_map_values(|value| (value % 2 == 0).then_some(value / 2));
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 1), (ie(8, 100), 4)]
+	/// );
 	/// ```
-	pub fn is_empty(&self) -> bool {
-		self.inner.is_empty()
+	pub fn filter_map_values<W, F>(self, mut f: F) -> NoditMap<I, K, W>
+	where
+		F: FnMut(V) -> Option<W>,
+	{
+		let mut map = NoditMap::new();
+		for (interval, value) in self.inner {
+			if let Some(value) = f(value) {
+				map.insert_unchecked(interval, value);
+			}
+		}
+		map
 	}
 
-	/// Returns an iterator over every entry in the map in ascending
-	/// order.
+	/// Consumes the map and returns a new map with every value
+	/// transformed by the fallible `f`, returning the first error
+	/// encountered, if any.
+	///
+	/// Since the intervals are not changed, the new map does not need to
+	/// re-check the intervals for overlaps.
 	///
 	/// # Examples
 	/// ```
@@ -1304,58 +1568,101 @@ impl<I, K, V> NoditMap<I, K, V> {
 	/// use nodit::NoditMap;
 	///
 	/// let map = NoditMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
+	/// 	(ie(1, 4), "2"),
+	/// 	(ie(4, 8), "4"),
 	/// ])
 	/// .unwrap();
 	///
-	/// let mut iter = map.iter();
+	/// let parsed = map.clone().try_map_values(|value| value.parse::<u8>());
+	/// assert_eq!(
+	/// 	parsed.unwrap().into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 2), (ie(4, 8), 4)]
+	/// );
 	///
-	/// assert_eq!(iter.next(), Some((&ie(1, 4), &false)));
-	/// assert_eq!(iter.next(), Some((&ie(4, 8), &true)));
-	/// assert_eq!(iter.next(), Some((&ie(8, 100), &false)));
-	/// assert_eq!(iter.next(), None);
+	/// let failed = map.try_map_values(|value| value.parse::<bool>());
+	/// assert!(failed.is_err());
 	/// ```
-	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
-		self.inner.iter()
+	pub fn try_map_values<W, E, F>(
+		self,
+		mut f: F,
+	) -> Result<NoditMap<I, K, W>, E>
+	where
+		F: FnMut(V) -> Result<W, E>,
+	{
+		let mut map = NoditMap::new();
+		for (interval, value) in self.inner {
+			map.insert_unchecked(interval, f(value)?);
+		}
+		Ok(map)
 	}
 
-	/// Returns an mutable iterator over every entry in the map in
-	/// ascending order.
+	/// Consumes the map and returns a new map with every entry
+	/// transformed by `f`.
+	///
+	/// `f` may shrink the interval it is given but it may never grow
+	/// it, this guarantees that the new map still contains no
+	/// overlapping intervals without re-checking them.
+	///
+	/// # Panics
+	///
+	/// Panics if `f` returns an invalid interval or an interval which is
+	/// not contained within the interval it was given. See [`Invalid
+	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
+	/// for more details.
 	///
 	/// # Examples
 	/// ```
-	/// use nodit::interval::ie;
-	/// use nodit::NoditMap;
+	/// use nodit::interval::{ie, ii};
+	/// use nodit::{InclusiveInterval, NoditMap};
 	///
-	/// let mut map = NoditMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 2),
+	/// 	(ie(4, 8), 4),
 	/// ])
 	/// .unwrap();
 	///
-	/// for (interval, value) in map.iter_mut() {
-	/// 	if *interval == ie(4, 8) {
-	/// 		*value = false
-	/// 	} else {
-	/// 		*value = true
-	/// 	}
-	/// }
+	/// // Trim the last point off every interval
+	/// let map = map.map_entries(|interval, value| {
+	/// 	(ie(interval.start(), interval.end()), value * 10)
+	/// });
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ii(1, 2), 20), (ii(4, 6), 40)]
+	/// );
 	/// ```
-	pub fn iter_mut(
-		&mut self,
-	) -> impl DoubleEndedIterator<Item = (&K, &mut V)> {
-		self.inner.iter_mut()
+	pub fn map_entries<W, F>(self, mut f: F) -> NoditMap<I, K, W>
+	where
+		F: FnMut(K, V) -> (K, W),
+	{
+		let mut map = NoditMap::new();
+		for (interval, value) in self.inner {
+			let (new_interval, new_value) = f(interval, value);
+
+			invalid_interval_panic(new_interval);
+			if !interval.contains_interval(&new_interval) {
+				panic!(
+					"map_entries() was given an interval that grew outside of the original interval"
+				);
+			}
+
+			map.insert_unchecked(new_interval, new_value);
+		}
+		map
 	}
 
-	/// Returns the first entry in the map, if any.
+	/// Returns a map from every distinct value in the map to the set of
+	/// intervals associated with that value.
+	///
+	/// This is computed in one pass over the map, see [`BiNoditMap`] if
+	/// you need it to be kept up to date as the map is modified.
 	///
 	/// # Examples
 	/// ```
+	/// use std::collections::BTreeMap;
+	///
 	/// use nodit::interval::ie;
-	/// use nodit::NoditMap;
+	/// use nodit::{NoditMap, NoditSet};
 	///
 	/// let map = NoditMap::from_slice_strict([
 	/// 	(ie(1, 4), false),
@@ -1364,9 +1671,1052 @@ impl<I, K, V> NoditMap<I, K, V> {
 	/// ])
 	/// .unwrap();
 	///
-	/// assert_eq!(map.first_key_value(), Some((&ie(1, 4), &false)));
+	/// assert_eq!(
+	/// 	map.group_by_value(),
+	/// 	BTreeMap::from([
+	/// 		(
+	/// 			false,
+	/// 			NoditSet::from_slice_strict([ie(1, 4), ie(8, 100)]).unwrap()
+	/// 		),
+	/// 		(true, NoditSet::from_slice_strict([ie(4, 8)]).unwrap()),
+	/// 	])
+	/// );
 	/// ```
-	pub fn first_key_value(&self) -> Option<(&K, &V)> {
+	///
+	/// [`BiNoditMap`]: crate::BiNoditMap
+	pub fn group_by_value(&self) -> StdBTreeMap<V, NoditSet<I, K>>
+	where
+		V: Ord + Clone,
+	{
+		let mut groups: StdBTreeMap<V, NoditSet<I, K>> = StdBTreeMap::new();
+		for (interval, value) in self.iter() {
+			//the intervals come from this map so they can't overlap
+			groups
+				.entry(value.clone())
+				.or_default()
+				.insert_unchecked(*interval);
+		}
+		groups
+	}
+
+	/// Trims the map so that it only covers the points covered by the given
+	/// set, cutting out every part of every entry which is not covered by
+	/// `set`.
+	///
+	/// `V` must implement `Clone` for the same reasons as
+	/// [`NoditMap::cut()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let mut prices = NoditMap::from_slice_strict([
+	/// 	(ie(0, 10), 5),
+	/// 	(ie(10, 20), 8),
+	/// ])
+	/// .unwrap();
+	/// let availability =
+	/// 	NoditSet::from_slice_strict([ie(2, 4), ie(8, 12)]).unwrap();
+	///
+	/// prices.restrict_to(&availability);
+	///
+	/// assert_eq!(
+	/// 	prices.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(2, 4), 5), (ie(8, 10), 5), (ie(10, 12), 8)]
+	/// );
+	/// ```
+	pub fn restrict_to<K2>(&mut self, set: &NoditSet<I, K2>)
+	where
+		K2: IntervalType<I>,
+		V: Clone,
+	{
+		for gap in set.gaps_trimmed(Interval {
+			start: I::MIN,
+			end: I::MAX,
+		}) {
+			let _ = self.cut(gap);
+		}
+	}
+
+	/// Cuts every interval of the given set out of the map.
+	///
+	/// `V` must implement `Clone` for the same reasons as
+	/// [`NoditMap::cut()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let mut prices = NoditMap::from_slice_strict([
+	/// 	(ie(0, 10), 5),
+	/// 	(ie(10, 20), 8),
+	/// ])
+	/// .unwrap();
+	/// let booked =
+	/// 	NoditSet::from_slice_strict([ie(2, 4), ie(8, 12)]).unwrap();
+	///
+	/// prices.subtract(&booked);
+	///
+	/// assert_eq!(
+	/// 	prices.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(0, 2), 5), (ie(4, 8), 5), (ie(12, 20), 8)]
+	/// );
+	/// ```
+	pub fn subtract<K2>(&mut self, set: &NoditSet<I, K2>)
+	where
+		K2: IntervalType<I>,
+		V: Clone,
+	{
+		for interval in set.iter() {
+			let _ = self.cut(*interval);
+		}
+	}
+
+	/// Returns the set of points covered by the intervals of the map, with
+	/// touching intervals merged together.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(10, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.keys_set(),
+	/// 	NoditSet::from_slice_strict([ie(1, 8), ie(10, 100)]).unwrap()
+	/// );
+	/// ```
+	pub fn keys_set(&self) -> NoditSet<I, K> {
+		self.where_value(|_| true)
+	}
+
+	/// Returns the set of points covered by the intervals of the map whose
+	/// values match the given predicate, with touching intervals merged
+	/// together.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 2),
+	/// 	(ie(4, 8), 4),
+	/// 	(ie(8, 10), 3),
+	/// 	(ie(10, 100), 6),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.where_value(|value| value % 2 == 0),
+	/// 	NoditSet::from_slice_strict([ie(1, 8), ie(10, 100)]).unwrap()
+	/// );
+	/// ```
+	pub fn where_value<F>(&self, mut predicate: F) -> NoditSet<I, K>
+	where
+		F: FnMut(&V) -> bool,
+	{
+		let coalesced = self
+			.iter()
+			.filter(|(_, value)| predicate(value))
+			.map(|(interval, _)| *interval)
+			.coalesce(|x, y| {
+				if x.end().up() == Some(y.start()) {
+					Ok(K::from(Interval {
+						start: x.start(),
+						end: y.end(),
+					}))
+				} else {
+					Err((x, y))
+				}
+			});
+
+		//the intervals come from this map so they can't overlap, even
+		//once the touching ones have been merged
+		let mut set = NoditSet::new();
+		for interval in coalesced {
+			set.insert_unchecked(interval);
+		}
+		set
+	}
+
+	/// Consumes the map and returns a new map over a different point
+	/// type, by passing the start and end points of every interval
+	/// through `f`.
+	///
+	/// `f` must be strictly monotone, meaning that if `x < y` then `f(x) <
+	/// f(y)`, this guarantees that the intervals of the new map are still
+	/// valid and non-overlapping. Since `f` is only evaluated at the start
+	/// and end points of the intervals, a [`NonMonotonicError`] is returned
+	/// if it is not strictly monotone over those points.
+	///
+	/// See [`NoditMap::quantize()`] if you need to map the points through a
+	/// function that is only monotone, such as rounding to a grid.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{NoditMap, NonMonotonicError};
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ii(1_u8, 4), false),
+	/// 	(ii(5, 8), true),
+	/// ])
+	/// .unwrap();
+	///
+	/// let map: NoditMap<u16, _, _> = map
+	/// 	.clone()
+	/// 	.map_points(|point| u16::from(point) * 1000)
+	/// 	.unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ii(1000, 4000), false), (ii(5000, 8000), true)]
+	/// );
+	///
+	/// let not_monotone = NoditMap::from_slice_strict([
+	/// 	(ii(1_u8, 4), false),
+	/// 	(ii(5, 8), true),
+	/// ])
+	/// .unwrap()
+	/// .map_points::<u8, nodit::Interval<u8>, _>(|point| point / 5);
+	///
+	/// assert_eq!(
+	/// 	not_monotone,
+	/// 	Err(NonMonotonicError {
+	/// 		first: 1,
+	/// 		second: 4
+	/// 	})
+	/// );
+	/// ```
+	pub fn map_points<J, K2, F>(
+		self,
+		mut f: F,
+	) -> Result<NoditMap<J, K2, V>, NonMonotonicError<I>>
+	where
+		J: PointType,
+		K2: IntervalType<J>,
+		F: FnMut(I) -> J,
+	{
+		let mut map = NoditMap::new();
+		let mut previous_end: Option<(I, J)> = None;
+		for (interval, value) in self.inner {
+			let start = f(interval.start());
+			let end = if interval.is_singular() {
+				start
+			} else {
+				f(interval.end())
+			};
+
+			if let Some((previous_end, mapped_previous_end)) = previous_end {
+				if mapped_previous_end >= start {
+					return Err(NonMonotonicError {
+						first: previous_end,
+						second: interval.start(),
+					});
+				}
+			}
+			if !interval.is_singular() && start >= end {
+				return Err(NonMonotonicError {
+					first: interval.start(),
+					second: interval.end(),
+				});
+			}
+
+			previous_end = Some((interval.end(), end));
+			map.insert_unchecked(K2::from(Interval { start, end }), value);
+		}
+		Ok(map)
+	}
+
+	/// Consumes the map and returns a new map with the intervals rounded
+	/// outwards to a grid of `bucket`-sized buckets aligned to zero.
+	///
+	/// Every start point is rounded down to the first point of its bucket
+	/// and every end point is rounded up to the last point of its bucket,
+	/// clamped to the domain of `I`. If two entries overlap after rounding,
+	/// which happens when they shared a bucket, then the given
+	/// [`CollisionPolicy`] is used to resolve the overlap.
+	///
+	/// # Panics
+	///
+	/// Panics if `bucket` is not greater than zero.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{CollisionPolicy, NoditMap, OverlapError};
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ii(3, 12), 1),
+	/// 	(ii(14, 16), 2),
+	/// 	(ii(31, 33), 4),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.clone()
+	/// 		.quantize(10, CollisionPolicy::FirstWins)
+	/// 		.unwrap()
+	/// 		.into_iter()
+	/// 		.collect::<Vec<_>>(),
+	/// 	[(ii(0, 19), 1), (ii(30, 39), 4)]
+	/// );
+	/// assert_eq!(
+	/// 	map.clone()
+	/// 		.quantize(10, CollisionPolicy::Merge(&mut |x, y| x + y))
+	/// 		.unwrap()
+	/// 		.into_iter()
+	/// 		.collect::<Vec<_>>(),
+	/// 	[(ii(0, 19), 3), (ii(30, 39), 4)]
+	/// );
+	/// assert_eq!(
+	/// 	map.quantize(10, CollisionPolicy::Error),
+	/// 	Err(OverlapError {
+	/// 		interval: ii(10, 19),
+	/// 		conflict: ii(0, 19),
+	/// 		value: 2,
+	/// 	})
+	/// );
+	/// ```
+	pub fn quantize(
+		self,
+		bucket: I,
+		mut policy: CollisionPolicy<V>,
+	) -> Result<NoditMap<I, K, V>, OverlapError<K, V>>
+	where
+		I: Add<Output = I> + Sub<Output = I> + Rem<Output = I>,
+	{
+		let zero = zero_of(bucket);
+		if bucket <= zero {
+			panic!("quantize() was given a bucket size which is not positive");
+		}
+
+		let mut map = NoditMap::new();
+		let mut pending: Option<(Interval<I>, V)> = None;
+		for (interval, value) in self.inner {
+			let mut quantized = Interval {
+				start: bucket_start(interval.start(), bucket),
+				end: bucket_end(interval.end(), bucket),
+			};
+
+			let Some((pending_interval, pending_value)) = pending.take()
+			else {
+				pending = Some((quantized, value));
+				continue;
+			};
+
+			if quantized.start() > pending_interval.end() {
+				map.insert_unchecked(K::from(pending_interval), pending_value);
+				pending = Some((quantized, value));
+				continue;
+			}
+
+			match policy {
+				CollisionPolicy::FirstWins => {
+					match pending_interval.end().up() {
+						Some(after) if after <= quantized.end() => {
+							quantized.start = after;
+							map.insert_unchecked(
+								K::from(pending_interval),
+								pending_value,
+							);
+							pending = Some((quantized, value));
+						}
+						_ => pending = Some((pending_interval, pending_value)),
+					}
+				}
+				CollisionPolicy::Merge(ref mut merge) => {
+					pending = Some((
+						Interval {
+							start: pending_interval.start(),
+							end: I::max(pending_interval.end(), quantized.end()),
+						},
+						merge(pending_value, value),
+					));
+				}
+				CollisionPolicy::Error => {
+					return Err(OverlapError {
+						interval: K::from(quantized),
+						conflict: K::from(pending_interval),
+						value,
+					});
+				}
+			}
+		}
+		if let Some((pending_interval, pending_value)) = pending {
+			map.insert_unchecked(K::from(pending_interval), pending_value);
+		}
+		Ok(map)
+	}
+
+	/// Returns `true` if every point covered by the intervals of the map
+	/// is also covered by the intervals of `other`.
+	///
+	/// Only the intervals are compared, the values of both maps are
+	/// ignored. This runs in a single pass over both maps without
+	/// allocating.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let opening_hours =
+	/// 	NoditSet::from_slice_strict([ie(9, 12), ie(13, 17)]).unwrap();
+	///
+	/// let bookings = NoditMap::from_slice_strict([
+	/// 	(ie(9, 10), "alice"),
+	/// 	(ie(10, 12), "bob"),
+	/// 	(ie(14, 15), "carol"),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(bookings.is_subset(opening_hours.as_map()), true);
+	///
+	/// let late_booking =
+	/// 	NoditMap::from_slice_strict([(ie(16, 18), "dave")]).unwrap();
+	///
+	/// assert_eq!(late_booking.is_subset(opening_hours.as_map()), false);
+	/// ```
+	pub fn is_subset<K2, W>(&self, other: &NoditMap<I, K2, W>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.refine_keys(other).all(|(_, _, in_other)| in_other)
+	}
+
+	/// Returns `true` if every point covered by the intervals of `other`
+	/// is also covered by the intervals of the map.
+	///
+	/// See [`NoditMap::is_subset()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map =
+	/// 	NoditMap::from_slice_strict([(ie(1, 4), false), (ie(4, 8), true)])
+	/// 		.unwrap();
+	///
+	/// let other = NoditMap::from_slice_strict([(ie(2, 6), 'a')]).unwrap();
+	///
+	/// assert_eq!(map.is_superset(&other), true);
+	/// assert_eq!(other.is_superset(&map), false);
+	/// ```
+	pub fn is_superset<K2, W>(&self, other: &NoditMap<I, K2, W>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.refine_keys(other).all(|(_, in_self, _)| in_self)
+	}
+
+	/// Returns `true` if the intervals of the map cover every point
+	/// covered by the intervals of `other`.
+	///
+	/// This is an alias for [`NoditMap::is_superset()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map =
+	/// 	NoditMap::from_slice_strict([(ie(1, 4), false), (ie(4, 8), true)])
+	/// 		.unwrap();
+	///
+	/// let other = NoditMap::from_slice_strict([(ie(2, 6), 'a')]).unwrap();
+	///
+	/// assert_eq!(map.covers(&other), true);
+	/// ```
+	pub fn covers<K2, W>(&self, other: &NoditMap<I, K2, W>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.is_superset(other)
+	}
+
+	/// Returns `true` if no point is covered by the intervals of both the
+	/// map and `other`.
+	///
+	/// See [`NoditMap::is_subset()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map =
+	/// 	NoditMap::from_slice_strict([(ie(1, 4), false), (ie(6, 8), true)])
+	/// 		.unwrap();
+	///
+	/// let other = NoditMap::from_slice_strict([(ie(4, 6), 'a')]).unwrap();
+	/// let another = NoditMap::from_slice_strict([(ie(7, 9), 'b')]).unwrap();
+	///
+	/// assert_eq!(map.is_disjoint(&other), true);
+	/// assert_eq!(map.is_disjoint(&another), false);
+	/// ```
+	pub fn is_disjoint<K2, W>(&self, other: &NoditMap<I, K2, W>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		!self
+			.refine_keys(other)
+			.any(|(_, in_self, in_other)| in_self && in_other)
+	}
+
+	/// Returns the [`Relation`] between the points covered by the
+	/// intervals of the map and those covered by the intervals of
+	/// `other`, along with the first points where they differ.
+	///
+	/// See [`NoditMap::is_subset()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, Relation};
+	///
+	/// let map =
+	/// 	NoditMap::from_slice_strict([(ie(1, 4), false), (ie(6, 8), true)])
+	/// 		.unwrap();
+	///
+	/// let other = NoditMap::from_slice_strict([(ie(1, 8), 'a')]).unwrap();
+	/// let another = NoditMap::from_slice_strict([(ie(2, 10), 'b')]).unwrap();
+	///
+	/// assert_eq!(map.relation(&map), Relation::Equal);
+	/// assert_eq!(map.relation(&other), Relation::ProperSubset { witness: 4 });
+	/// assert_eq!(
+	/// 	other.relation(&map),
+	/// 	Relation::ProperSuperset { witness: 4 }
+	/// );
+	/// assert_eq!(
+	/// 	map.relation(&another),
+	/// 	Relation::Incomparable {
+	/// 		only_in_self: 1,
+	/// 		only_in_other: 4
+	/// 	}
+	/// );
+	/// ```
+	pub fn relation<K2, W>(&self, other: &NoditMap<I, K2, W>) -> Relation<I>
+	where
+		K2: IntervalType<I>,
+	{
+		let mut only_in_self = None;
+		let mut only_in_other = None;
+
+		for (segment, in_self, in_other) in self.refine_keys(other) {
+			if !in_other && only_in_self.is_none() {
+				only_in_self = Some(segment.start);
+			}
+			if !in_self && only_in_other.is_none() {
+				only_in_other = Some(segment.start);
+			}
+			if only_in_self.is_some() && only_in_other.is_some() {
+				break;
+			}
+		}
+
+		match (only_in_self, only_in_other) {
+			(None, None) => Relation::Equal,
+			(None, Some(witness)) => Relation::ProperSubset { witness },
+			(Some(witness), None) => Relation::ProperSuperset { witness },
+			(Some(only_in_self), Some(only_in_other)) => {
+				Relation::Incomparable {
+					only_in_self,
+					only_in_other,
+				}
+			}
+		}
+	}
+
+	/// Returns an iterator over the changes needed to turn the map (the
+	/// old map) into `new`, in ascending order.
+	///
+	/// The changes are computed on the common refinement of the intervals
+	/// of both maps, so differences in how the two maps happen to be
+	/// fragmented are ignored. Touching changes of the same kind with
+	/// equal values are merged together.
+	///
+	/// The changes can be applied to a copy of the old map using
+	/// [`NoditMap::patch()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{DiffItem, NoditMap};
+	///
+	/// let old = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 'a'),
+	/// 	(ie(4, 8), 'b'),
+	/// 	(ie(8, 10), 'c'),
+	/// ])
+	/// .unwrap();
+	/// let new = NoditMap::from_slice_strict([
+	/// 	(ie(1, 2), 'a'),
+	/// 	(ie(2, 4), 'a'),
+	/// 	(ie(4, 6), 'z'),
+	/// 	(ie(8, 12), 'c'),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	NoditMap::diff(&old, &new).collect::<Vec<_>>(),
+	/// 	[
+	/// 		DiffItem::Changed(ie(4, 6), &'b', &'z'),
+	/// 		DiffItem::Removed(ie(6, 8), &'b'),
+	/// 		DiffItem::Added(ie(10, 12), &'c'),
+	/// 	]
+	/// );
+	/// ```
+	pub fn diff<'a>(
+		&'a self,
+		new: &'a NoditMap<I, K, V>,
+	) -> impl Iterator<Item = DiffItem<'a, K, V>> + 'a
+	where
+		V: PartialEq,
+	{
+		let old_entries = self.iter().map(|(interval, value)| {
+			let interval = Interval {
+				start: interval.start(),
+				end: interval.end(),
+			};
+			(interval, value)
+		});
+		let new_entries = new.iter().map(|(interval, value)| {
+			let interval = Interval {
+				start: interval.start(),
+				end: interval.end(),
+			};
+			(interval, value)
+		});
+
+		refinement(old_entries, new_entries)
+			.filter(|(_, old, new)| old != new)
+			.coalesce(|(x, x_old, x_new), (y, y_old, y_new)| {
+				if x.end.up() == Some(y.start)
+					&& x_old == y_old
+					&& x_new == y_new
+				{
+					Ok((
+						Interval {
+							start: x.start,
+							end: y.end,
+						},
+						x_old,
+						x_new,
+					))
+				} else {
+					Err(((x, x_old, x_new), (y, y_old, y_new)))
+				}
+			})
+			.map(|(segment, old, new)| match (old, new) {
+				(None, Some(new)) => DiffItem::Added(K::from(segment), new),
+				(Some(old), None) => DiffItem::Removed(K::from(segment), old),
+				(Some(old), Some(new)) => {
+					DiffItem::Changed(K::from(segment), old, new)
+				}
+				(None, None) => unreachable!(),
+			})
+	}
+
+	/// Applies the changes from [`NoditMap::diff()`] to the map.
+	///
+	/// `Added` and `Changed` intervals are inserted with
+	/// [`NoditMap::insert_overwrite()`] and `Removed` intervals are removed
+	/// with [`NoditMap::cut()`], hence the same `V: Clone` trait bound
+	/// applies.
+	///
+	/// Applying the diff of an old map and a new map to a copy of the old
+	/// map gives a map which covers every point with the same value as
+	/// the new map, although it may be fragmented differently.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let old = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), 'a'),
+	/// 	(ie(4, 8), 'b'),
+	/// ])
+	/// .unwrap();
+	/// let new = NoditMap::from_slice_strict([
+	/// 	(ie(2, 6), 'b'),
+	/// 	(ie(6, 10), 'c'),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut patched = old.clone();
+	/// patched.patch(old.diff(&new));
+	///
+	/// assert_eq!(
+	/// 	patched.into_iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ie(2, 4), 'b'),
+	/// 		(ie(4, 6), 'b'),
+	/// 		(ie(6, 8), 'c'),
+	/// 		(ie(8, 10), 'c'),
+	/// 	]
+	/// );
+	/// ```
+	pub fn patch<'a>(
+		&mut self,
+		diff: impl IntoIterator<Item = DiffItem<'a, K, V>>,
+	) where
+		V: Clone + 'a,
+	{
+		for item in diff {
+			match item {
+				DiffItem::Added(interval, value)
+				| DiffItem::Changed(interval, _, value) => {
+					let _ = self.insert_overwrite(interval, value.clone());
+				}
+				DiffItem::Removed(interval, _) => {
+					let _ = self.cut(interval);
+				}
+			}
+		}
+	}
+
+	/// Runs `f` as a transaction on the map, if `f` returns an error then
+	/// every edit it made to the map is rolled back.
+	///
+	/// `f` is given a [`Transaction`] which supports most of the editing
+	/// methods of the map. Since each edit records the entries it replaces
+	/// this requires `V: Clone`.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, OverlapError};
+	///
+	/// let mut map = NoditMap::from_slice_strict([(ie(1, 4), false)]).unwrap();
+	///
+	/// let result = map.transaction(|tx| {
+	/// 	tx.insert_overwrite(ie(2, 3), true);
+	/// 	tx.insert_strict(ie(6, 8), true)?;
+	/// 	// overlaps, so the whole transaction is rolled back
+	/// 	tx.insert_strict(ie(7, 10), true)?;
+	/// 	Ok(())
+	/// });
+	///
+	/// assert_eq!(
+	/// 	result,
+	/// 	Err(OverlapError {
+	/// 		interval: ie(7, 10),
+	/// 		conflict: ie(6, 8),
+	/// 		value: true,
+	/// 	})
+	/// );
+	/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&ie(1, 4), &false)]);
+	/// ```
+	pub fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+	where
+		V: Clone,
+		F: FnOnce(&mut Transaction<I, K, V>) -> Result<T, E>,
+	{
+		Transaction::run(self, f).map(|(result, _)| result)
+	}
+
+	/// Runs `f` as a transaction on the map like
+	/// [`NoditMap::transaction()`], and if it succeeds records it in the
+	/// given [`History`] so that it can be undone later.
+	///
+	/// See [`History`] for more details.
+	pub fn transaction_with_history<T, E, F>(
+		&mut self,
+		history: &mut History<I, K, V>,
+		f: F,
+	) -> Result<T, E>
+	where
+		V: Clone,
+		F: FnOnce(&mut Transaction<I, K, V>) -> Result<T, E>,
+	{
+		let (result, changes) = Transaction::run(self, f)?;
+		history.push(changes);
+		Ok(result)
+	}
+
+	/// Checks that the internal invariants of the map hold, returning the
+	/// first [`InvariantError`] found if not.
+	///
+	/// The invariants are that every interval is valid, the intervals are
+	/// stored in ascending order without overlapping, and the length of
+	/// the map matches the number of entries.
+	///
+	/// This is mostly useful for testing, such as fuzzing types built on
+	/// top of the map. It runs in `O(n)` time.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.check_invariants(), Ok(()));
+	/// ```
+	pub fn check_invariants(&self) -> Result<(), InvariantError<I>> {
+		let counted =
+			check_ordered_intervals(self.iter().map(|(key, _)| *key), false)?;
+
+		if counted != self.len() {
+			return Err(InvariantError::LenMismatch {
+				len: self.len(),
+				counted,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Walks the common refinement of the intervals of both maps, yielding
+	/// each covered segment along with whether it is covered by `self` and
+	/// by `other`.
+	fn refine_keys<'a, K2, W>(
+		&'a self,
+		other: &'a NoditMap<I, K2, W>,
+	) -> impl Iterator<Item = (Interval<I>, bool, bool)> + 'a
+	where
+		K2: IntervalType<I>,
+	{
+		let self_intervals = self.iter().map(|(interval, _)| {
+			let interval = Interval {
+				start: interval.start(),
+				end: interval.end(),
+			};
+			(interval, ())
+		});
+		let other_intervals = other.iter().map(|(interval, _)| {
+			let interval = Interval {
+				start: interval.start(),
+				end: interval.end(),
+			};
+			(interval, ())
+		});
+
+		refinement(self_intervals, other_intervals)
+			.map(|(segment, x, y)| (segment, x.is_some(), y.is_some()))
+	}
+}
+
+/// The zero of the point type, since `DiscreteFinite` doesn't provide one.
+#[allow(clippy::eq_op)]
+fn zero_of<I>(x: I) -> I
+where
+	I: Copy + Sub<Output = I>,
+{
+	x - x
+}
+/// The first point of the `bucket`-sized bucket containing `point`,
+/// clamped to `I::MIN`.
+fn bucket_start<I>(point: I, bucket: I) -> I
+where
+	I: PointType + Add<Output = I> + Sub<Output = I> + Rem<Output = I>,
+{
+	let zero = zero_of(bucket);
+	let remainder = point % bucket;
+	//truncated towards zero so the remainder is never more than
+	//point away from zero
+	let truncated = point - remainder;
+	if remainder >= zero {
+		truncated
+	} else if truncated < I::MIN + bucket {
+		I::MIN
+	} else {
+		truncated - bucket
+	}
+}
+/// The last point of the `bucket`-sized bucket containing `point`,
+/// clamped to `I::MAX`.
+fn bucket_end<I>(point: I, bucket: I) -> I
+where
+	I: PointType + Add<Output = I> + Sub<Output = I> + Rem<Output = I>,
+{
+	let zero = zero_of(bucket);
+	let remainder = point % bucket;
+	let truncated = point - remainder;
+	if remainder < zero {
+		//truncated is the start of the next bucket which is above point
+		truncated.down().unwrap()
+	} else {
+		//bucket is positive so this can't overflow
+		let last_offset = bucket.down().unwrap();
+		if truncated > I::MAX - last_offset {
+			I::MAX
+		} else {
+			truncated + last_offset
+		}
+	}
+}
+
+impl<I, K, V> NoditMap<I, K, V> {
+	/// Makes a new, empty [`NoditMap`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::{Interval, NoditMap};
+	///
+	/// let map: NoditMap<i8, Interval<i8>, bool> = NoditMap::new();
+	/// ```
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of intervals in the map.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let mut map = NoditMap::new();
+	///
+	/// assert_eq!(map.len(), 0);
+	/// map.insert_strict(ie(0, 1), false).unwrap();
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Returns `true` if the map contains no intervals, and
+	/// `false` if it does.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let mut map = NoditMap::new();
+	///
+	/// assert_eq!(map.is_empty(), true);
+	/// map.insert_strict(ie(0, 1), false).unwrap();
+	/// assert_eq!(map.is_empty(), false);
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Returns an iterator over every entry in the map in ascending
+	/// order.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut iter = map.iter();
+	///
+	/// assert_eq!(iter.next(), Some((&ie(1, 4), &false)));
+	/// assert_eq!(iter.next(), Some((&ie(4, 8), &true)));
+	/// assert_eq!(iter.next(), Some((&ie(8, 100), &false)));
+	/// assert_eq!(iter.next(), None);
+	/// ```
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.inner.iter()
+	}
+
+	/// Returns an iterator over every point covered by the map, with the
+	/// value of the entry covering it, in ascending order.
+	///
+	/// See [`InclusiveInterval::points()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ie, ii};
+	/// use nodit::NoditMap;
+	///
+	/// let map =
+	/// 	NoditMap::from_slice_strict([(ie(1, 3), 'a'), (ii(6, 7), 'b')])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.points_with_values().collect::<Vec<_>>(),
+	/// 	[(1, &'a'), (2, &'a'), (6, &'b'), (7, &'b')]
+	/// );
+	/// assert_eq!(
+	/// 	map.points_with_values().rev().next(),
+	/// 	Some((7, &'b'))
+	/// );
+	/// ```
+	pub fn points_with_values(&self) -> impl DoubleEndedIterator<Item = (I, &V)>
+	where
+		I: PointType + Countable,
+		K: IntervalType<I>,
+	{
+		self.inner.iter().flat_map(|(key, value)| {
+			key.points().map(move |point| (point, value))
+		})
+	}
+
+	/// Returns an mutable iterator over every entry in the map in
+	/// ascending order.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let mut map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// for (interval, value) in map.iter_mut() {
+	/// 	if *interval == ie(4, 8) {
+	/// 		*value = false
+	/// 	} else {
+	/// 		*value = true
+	/// 	}
+	/// }
+	/// ```
+	pub fn iter_mut(
+		&mut self,
+	) -> impl DoubleEndedIterator<Item = (&K, &mut V)> {
+		self.inner.iter_mut()
+	}
+
+	/// Returns the first entry in the map, if any.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditMap;
+	///
+	/// let map = NoditMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.first_key_value(), Some((&ie(1, 4), &false)));
+	/// ```
+	pub fn first_key_value(&self) -> Option<(&K, &V)> {
 		self.inner.first_key_value()
 	}
 
@@ -1440,14 +2790,122 @@ impl<I, K, V> Default for NoditMap<I, K, V> {
 	}
 }
 
+/// Formats the map in mathematical interval notation such as
+/// `{[0, 5) => a, [7, 9] => b}`.
+///
+/// ```
+/// use nodit::interval::{ie, ii};
+/// use nodit::NoditMap;
+///
+/// let map =
+/// 	NoditMap::from_slice_strict([(ie(0, 5), 'a'), (ii(7, 9), 'b')])
+/// 		.unwrap();
+///
+/// assert_eq!(map.to_string(), "{[0, 5) => a, [7, 9] => b}");
+/// ```
+impl<I, K, V> fmt::Display for NoditMap<I, K, V>
+where
+	K: fmt::Display,
+	V: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{{")?;
+		for (i, (interval, value)) in self.inner.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{interval} => {value}")?;
+		}
+		write!(f, "}}")
+	}
+}
+/// Parses a map in mathematical interval notation such as
+/// `{[0, 5) => a, [7, 9] => b}`, inserting each entry with
+/// [`NoditMap::insert_strict()`].
+///
+/// A value extends up to the next comma which is followed by an interval,
+/// or the closing brace. The byte offsets in any [`ParseError`] are
+/// relative to the start of the whole input.
+///
+/// ```
+/// use nodit::interval::{ie, ii};
+/// use nodit::{BoundedInterval, NoditMap, ParseError, ParseErrorKind};
+///
+/// let map: NoditMap<u8, BoundedInterval<u8>, char> =
+/// 	"{[0, 5) => a, [7, 9] => b}".parse().unwrap();
+///
+/// assert_eq!(
+/// 	map,
+/// 	NoditMap::from_slice_strict([(ie(0, 5), 'a'), (ii(7, 9), 'b')])
+/// 		.unwrap()
+/// );
+///
+/// assert_eq!(
+/// 	"{[0, 5) => a, [4, 9] => b}"
+/// 		.parse::<NoditMap<u8, BoundedInterval<u8>, char>>(),
+/// 	Err(ParseError {
+/// 		offset: 14,
+/// 		kind: ParseErrorKind::Overlap
+/// 	})
+/// );
+/// ```
+impl<I, K, V> FromStr for NoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I> + FromStr<Err = ParseError>,
+	V: FromStr,
+{
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut map = NoditMap::new();
+		for entry in parse_entries(s, true)? {
+			let interval = parse_key(entry.interval, entry.interval_offset)?;
+			let value = entry.value.parse().map_err(|_| ParseError {
+				offset: entry.value_offset,
+				kind: ParseErrorKind::InvalidValue,
+			})?;
+
+			map.insert_strict(interval, value).map_err(|_| ParseError {
+				offset: entry.interval_offset,
+				kind: ParseErrorKind::Overlap,
+			})?;
+		}
+		Ok(map)
+	}
+}
+/// Parses an interval of a map or set, checking that it is valid so that
+/// inserting it won't panic.
+pub(crate) fn parse_key<I, K>(
+	input: &str,
+	offset: usize,
+) -> Result<K, ParseError>
+where
+	I: PointType,
+	K: IntervalType<I> + FromStr<Err = ParseError>,
+{
+	let interval = K::from_str(input).map_err(|error| error.shift(offset))?;
+
+	if interval.start() > interval.end() {
+		return Err(ParseError {
+			offset,
+			kind: ParseErrorKind::EmptyInterval,
+		});
+	}
+
+	Ok(interval)
+}
+
 #[cfg(feature = "serde")]
 mod serde {
+	use core::fmt;
 	use core::marker::PhantomData;
 
 	use serde::de::{SeqAccess, Visitor};
 	use serde::ser::SerializeSeq;
 	use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+	use crate::utils::deserialize_overlap_error;
 	use crate::{IntervalType, NoditMap, PointType};
 
 	impl<I, K, V> Serialize for NoditMap<I, K, V>
@@ -1470,7 +2928,7 @@ mod serde {
 	impl<'de, I, K, V> Deserialize<'de> for NoditMap<I, K, V>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 		V: Deserialize<'de>,
 	{
 		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -1494,7 +2952,7 @@ mod serde {
 	impl<'de, I, K, V> Visitor<'de> for NoditMapVisitor<I, K, V>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 		V: Deserialize<'de>,
 	{
 		type Value = NoditMap<I, K, V>;
@@ -1511,9 +2969,11 @@ mod serde {
 			A: SeqAccess<'de>,
 		{
 			let mut map = NoditMap::new();
+			let mut index = 0;
 			while let Some((interval, value)) = access.next_element()? {
 				map.insert_strict(interval, value)
-					.or(Err(serde::de::Error::custom("intervals overlap")))?;
+					.map_err(|error| deserialize_overlap_error(index, error))?;
+				index += 1;
 			}
 			Ok(map)
 		}
@@ -1524,13 +2984,16 @@ mod serde {
 mod tests {
 	extern crate std;
 
+	use core::ops::Bound;
 	use std::dbg;
 
 	use pretty_assertions::assert_eq;
 
 	use super::*;
-	use crate::interval::{ee, ei, ie, ii, iu, ue, ui, uu};
+	use crate::interval::{ee, ei, eu, ie, ii, iu, ue, ui, uu};
 	use crate::utils::{Config, CutResult, config, contains_point};
+	use crate::utils::test_helpers::{I8_EDGES, U8_EDGES, edge_intervals};
+	use crate::{CheckedArithmetic, Countable};
 
 	//only every other number to allow mathematical_overlapping_definition
 	//to test between bounds in finite using smaller intervalled finite
@@ -1541,19 +3004,19 @@ mod tests {
 
 	fn basic() -> NoditMap<i8, Interval<i8>, bool> {
 		NoditMap::from_slice_strict([
-			(ui(4), false),
-			(ee(5, 7), true),
-			(ii(7, 7), false),
-			(ie(14, 16), true),
+			(ui(4).into(), false),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
 		])
 		.unwrap()
 	}
 	fn basic_slice() -> [(Interval<i8>, bool); 4] {
 		[
-			(ui(4), false),
-			(ee(5, 7), true),
-			(ii(7, 7), false),
-			(ie(14, 16), true),
+			(ui(4).into(), false),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
 		]
 	}
 
@@ -1561,34 +3024,46 @@ mod tests {
 	fn insert_strict_tests() {
 		assert_insert_strict(
 			basic(),
-			(ii(0, 4), false),
-			Err(OverlapError { value: false }),
+			(ii(0, 4).into(), false),
+			Err(OverlapError {
+				interval: ii(0, 4).into(),
+				conflict: ui(4).into(),
+				value: false,
+			}),
 			basic_slice(),
 		);
 		assert_insert_strict(
 			basic(),
-			(ii(5, 6), false),
-			Err(OverlapError { value: false }),
+			(ii(5, 6).into(), false),
+			Err(OverlapError {
+				interval: ii(5, 6).into(),
+				conflict: ee(5, 7).into(),
+				value: false,
+			}),
 			basic_slice(),
 		);
 		assert_insert_strict(
 			basic(),
-			(ii(4, 5), true),
-			Err(OverlapError { value: true }),
+			(ii(4, 5).into(), true),
+			Err(OverlapError {
+				interval: ii(4, 5).into(),
+				conflict: ui(4).into(),
+				value: true,
+			}),
 			basic_slice(),
 		);
-		assert_insert_strict(basic(), (ei(4, 5), true), Ok(()), [
-			(ui(4), false),
-			(ei(4, 5), true),
-			(ee(5, 7), true),
-			(ii(7, 7), false),
-			(ie(14, 16), true),
+		assert_insert_strict(basic(), (ei(4, 5).into(), true), Ok(()), [
+			(ui(4).into(), false),
+			(ei(4, 5).into(), true),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
 		]);
 	}
 	fn assert_insert_strict<const N: usize>(
 		mut before: NoditMap<i8, Interval<i8>, bool>,
 		to_insert: (Interval<i8>, bool),
-		result: Result<(), OverlapError<bool>>,
+		result: Result<(), OverlapError<Interval<i8>, bool>>,
 		after: [(Interval<i8>, bool); N],
 	) {
 		assert_eq!(before.insert_strict(to_insert.0, to_insert.1), result);
@@ -1678,29 +3153,33 @@ mod tests {
 
 	#[test]
 	fn remove_overlapping_tests() {
-		assert_remove_overlapping(basic(), ii(5, 5), [], basic_slice());
+		assert_remove_overlapping(basic(), ii(5, 5).into(), [], basic_slice());
 		assert_remove_overlapping(
 			basic(),
-			uu(),
+			uu().into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
 			],
 			[],
 		);
 		assert_remove_overlapping(
 			basic(),
-			ii(6, 7),
-			[(ee(5, 7), true), (ii(7, 7), false)],
-			[(ui(4), false), (ie(14, 16), true)],
+			ii(6, 7).into(),
+			[(ee(5, 7).into(), true), (ii(7, 7).into(), false)],
+			[(ui(4).into(), false), (ie(14, 16).into(), true)],
 		);
 		assert_remove_overlapping(
 			basic(),
-			iu(6),
-			[(ee(5, 7), true), (ii(7, 7), false), (ie(14, 16), true)],
-			[(ui(4), false)],
+			iu(6).into(),
+			[
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
+			],
+			[(ui(4).into(), false)],
 		);
 	}
 	fn assert_remove_overlapping<const N: usize, const Y: usize>(
@@ -1718,32 +3197,38 @@ mod tests {
 
 	#[test]
 	fn cut_tests() {
-		assert_cut(basic(), ii(50, 60), [], [
-			(ui(4), false),
-			(ee(5, 7), true),
-			(ii(7, 7), false),
-			(ie(14, 16), true),
+		assert_cut(basic(), ii(50, 60).into(), [], [
+			(ui(4).into(), false),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
 		]);
 		assert_cut(
 			basic(),
-			uu(),
+			uu().into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
 			],
 			[],
 		);
-		assert_cut(basic(), ui(6), [(ui(4), false), (ei(5, 6), true)], [
-			(ii(7, 7), false),
-			(ie(14, 16), true),
-		]);
 		assert_cut(
 			basic(),
-			iu(6),
-			[(ie(6, 7), true), (ii(7, 7), false), (ie(14, 16), true)],
-			[(ui(4), false)],
+			ui(6).into(),
+			[(ui(4).into(), false), (ei(5, 6).into(), true)],
+			[(ii(7, 7).into(), false), (ie(14, 16).into(), true)],
+		);
+		assert_cut(
+			basic(),
+			iu(6).into(),
+			[
+				(ie(6, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
+			],
+			[(ui(4).into(), false)],
 		);
 	}
 	fn assert_cut<const N: usize, const Y: usize>(
@@ -1758,32 +3243,35 @@ mod tests {
 
 	#[test]
 	fn gaps_untrimmed_tests() {
-		assert_gaps_untrimmed(basic(), ii(50, 60), [iu(16)]);
-		assert_gaps_untrimmed(basic(), iu(50), [iu(16)]);
-		assert_gaps_untrimmed(basic(), ee(3, 16), [ei(4, 5), ee(7, 14)]);
-		assert_gaps_untrimmed(basic(), ei(3, 16), [
-			ei(4, 5),
-			ee(7, 14),
-			iu(16),
+		assert_gaps_untrimmed(basic(), ii(50, 60).into(), [iu(16).into()]);
+		assert_gaps_untrimmed(basic(), iu(50).into(), [iu(16).into()]);
+		assert_gaps_untrimmed(basic(), ee(3, 16).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
 		]);
-		assert_gaps_untrimmed(basic(), ue(5), []);
-		assert_gaps_untrimmed(basic(), ui(3), []);
-		assert_gaps_untrimmed(basic(), ii(5, 5), [ii(5, 5)]);
-		assert_gaps_untrimmed(basic(), ii(6, 6), []);
-		assert_gaps_untrimmed(basic(), ii(7, 7), []);
-		assert_gaps_untrimmed(basic(), ii(8, 8), [ii(8, 13)]);
-
-		assert_gaps_untrimmed(basic(), ii(i8::MIN, i8::MAX), [
-			ei(4, 5),
-			ee(7, 14),
-			ii(16, i8::MAX),
+		assert_gaps_untrimmed(basic(), ei(3, 16).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
+			iu(16).into(),
+		]);
+		assert_gaps_untrimmed(basic(), ue(5).into(), []);
+		assert_gaps_untrimmed(basic(), ui(3).into(), []);
+		assert_gaps_untrimmed(basic(), ii(5, 5).into(), [ii(5, 5).into()]);
+		assert_gaps_untrimmed(basic(), ii(6, 6).into(), []);
+		assert_gaps_untrimmed(basic(), ii(7, 7).into(), []);
+		assert_gaps_untrimmed(basic(), ii(8, 8).into(), [ii(8, 13).into()]);
+
+		assert_gaps_untrimmed(basic(), ii(i8::MIN, i8::MAX).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
+			ii(16, i8::MAX).into(),
 		]);
 		assert_eq!(
 			NoditMap::from_slice_strict([(ii(i8::MIN, i8::MAX), false)])
 				.unwrap()
 				.gaps_trimmed(uu())
-				.collect::<Vec<_>>(),
-			[]
+				.next(),
+			None
 		);
 	}
 	fn assert_gaps_untrimmed<const N: usize>(
@@ -1796,32 +3284,35 @@ mod tests {
 
 	#[test]
 	fn gaps_trimmed_tests() {
-		assert_gaps_trimmed(basic(), ii(50, 60), [ii(50, 60)]);
-		assert_gaps_trimmed(basic(), iu(50), [iu(50)]);
-		assert_gaps_trimmed(basic(), ee(3, 16), [ei(4, 5), ee(7, 14)]);
-		assert_gaps_trimmed(basic(), ei(3, 16), [
-			ei(4, 5),
-			ee(7, 14),
-			ii(16, 16),
+		assert_gaps_trimmed(basic(), ii(50, 60).into(), [ii(50, 60).into()]);
+		assert_gaps_trimmed(basic(), iu(50).into(), [iu(50).into()]);
+		assert_gaps_trimmed(basic(), ee(3, 16).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
 		]);
-		assert_gaps_trimmed(basic(), ue(5), []);
-		assert_gaps_trimmed(basic(), ui(3), []);
-		assert_gaps_trimmed(basic(), ii(5, 5), [ii(5, 5)]);
-		assert_gaps_trimmed(basic(), ii(6, 6), []);
-		assert_gaps_trimmed(basic(), ii(7, 7), []);
-		assert_gaps_trimmed(basic(), ii(8, 8), [ii(8, 8)]);
-
-		assert_gaps_trimmed(basic(), ii(i8::MIN, i8::MAX), [
-			ei(4, 5),
-			ee(7, 14),
-			ii(16, i8::MAX),
+		assert_gaps_trimmed(basic(), ei(3, 16).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
+			ii(16, 16).into(),
+		]);
+		assert_gaps_trimmed(basic(), ue(5).into(), []);
+		assert_gaps_trimmed(basic(), ui(3).into(), []);
+		assert_gaps_trimmed(basic(), ii(5, 5).into(), [ii(5, 5).into()]);
+		assert_gaps_trimmed(basic(), ii(6, 6).into(), []);
+		assert_gaps_trimmed(basic(), ii(7, 7).into(), []);
+		assert_gaps_trimmed(basic(), ii(8, 8).into(), [ii(8, 8).into()]);
+
+		assert_gaps_trimmed(basic(), ii(i8::MIN, i8::MAX).into(), [
+			ei(4, 5).into(),
+			ee(7, 14).into(),
+			ii(16, i8::MAX).into(),
 		]);
 		assert_eq!(
 			NoditMap::from_slice_strict([(ii(i8::MIN, i8::MAX), false)])
 				.unwrap()
 				.gaps_trimmed(uu())
-				.collect::<Vec<_>>(),
-			[]
+				.next(),
+			None
 		);
 	}
 	fn assert_gaps_trimmed<const N: usize>(
@@ -1836,48 +3327,56 @@ mod tests {
 	fn insert_merge_touching_tests() {
 		assert_insert_merge_touching(
 			basic(),
-			(ii(0, 4), false),
-			Err(OverlapError { value: false }),
+			(ii(0, 4).into(), false),
+			Err(OverlapError {
+				interval: ii(0, 4).into(),
+				conflict: ui(4).into(),
+				value: false,
+			}),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching(
 			basic(),
-			(ee(7, 10), false),
-			Ok(ie(7, 10)),
+			(ee(7, 10).into(), false),
+			Ok(ie(7, 10).into()),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ie(7, 10), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ie(7, 10).into(), false),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching(
 			basic(),
-			(ee(7, 11), true),
-			Ok(ie(7, 11)),
+			(ee(7, 11).into(), true),
+			Ok(ie(7, 11).into()),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ie(7, 11), true),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ie(7, 11).into(), true),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching(
 			basic(),
-			(ee(7, 14), false),
-			Ok(ie(7, 16)),
-			[(ui(4), false), (ee(5, 7), true), (ie(7, 16), false)],
+			(ee(7, 14).into(), false),
+			Ok(ie(7, 16).into()),
+			[
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ie(7, 16).into(), false),
+			],
 		);
 	}
 	fn assert_insert_merge_touching<const N: usize>(
 		mut before: NoditMap<i8, Interval<i8>, bool>,
 		to_insert: (Interval<i8>, bool),
-		result: Result<Interval<i8>, OverlapError<bool>>,
+		result: Result<Interval<i8>, OverlapError<Interval<i8>, bool>>,
 		after: [(Interval<i8>, bool); N],
 	) {
 		assert_eq!(
@@ -1890,50 +3389,54 @@ mod tests {
 	fn insert_merge_touching_if_values_equal_tests() {
 		assert_insert_merge_touching_if_values_equal(
 			basic(),
-			(ii(0, 4), false),
-			Err(OverlapError { value: false }),
+			(ii(0, 4).into(), false),
+			Err(OverlapError {
+				interval: ii(0, 4).into(),
+				conflict: ui(4).into(),
+				value: false,
+			}),
 			basic_slice(),
 		);
 		dbg!("hererere");
 		assert_insert_merge_touching_if_values_equal(
 			basic(),
-			(ee(7, 10), false),
-			Ok(ie(7, 10)),
+			(ee(7, 10).into(), false),
+			Ok(ie(7, 10).into()),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ie(7, 10), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ie(7, 10).into(), false),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching_if_values_equal(
 			basic(),
-			(ee(7, 11), true),
-			Ok(ee(7, 11)),
+			(ee(7, 11).into(), true),
+			Ok(ee(7, 11).into()),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ee(7, 11), true),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ee(7, 11).into(), true),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching_if_values_equal(
 			basic(),
-			(ee(7, 14), false),
-			Ok(ie(7, 14)),
+			(ee(7, 14).into(), false),
+			Ok(ie(7, 14).into()),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ie(7, 14), false),
-				(ie(14, 16), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ie(7, 14).into(), false),
+				(ie(14, 16).into(), true),
 			],
 		);
 	}
 	fn assert_insert_merge_touching_if_values_equal<const N: usize>(
 		mut before: NoditMap<i8, Interval<i8>, bool>,
 		to_insert: (Interval<i8>, bool),
-		result: Result<Interval<i8>, OverlapError<bool>>,
+		result: Result<Interval<i8>, OverlapError<Interval<i8>, bool>>,
 		after: [(Interval<i8>, bool); N],
 	) {
 		assert_eq!(
@@ -1948,44 +3451,55 @@ mod tests {
 
 	#[test]
 	fn insert_merge_overlapping_tests() {
-		assert_insert_merge_overlapping(basic(), (ii(0, 2), true), ui(4), [
-			(ui(4), true),
-			(ee(5, 7), true),
-			(ii(7, 7), false),
-			(ie(14, 16), true),
-		]);
 		assert_insert_merge_overlapping(
 			basic(),
-			(ie(14, 16), false),
-			ie(14, 16),
+			(ii(0, 2).into(), true),
+			ui(4).into(),
+			[
+				(ui(4).into(), true),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
+			],
+		);
+		assert_insert_merge_overlapping(
+			basic(),
+			(ie(14, 16).into(), false),
+			ie(14, 16).into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), false),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), false),
 			],
 		);
 		assert_insert_merge_overlapping(
 			basic(),
-			(ii(6, 11), false),
-			ei(5, 11),
-			[(ui(4), false), (ei(5, 11), false), (ie(14, 16), true)],
+			(ii(6, 11).into(), false),
+			ei(5, 11).into(),
+			[
+				(ui(4).into(), false),
+				(ei(5, 11).into(), false),
+				(ie(14, 16).into(), true),
+			],
 		);
 		assert_insert_merge_overlapping(
 			basic(),
-			(ii(15, 18), true),
-			ii(14, 18),
+			(ii(15, 18).into(), true),
+			ii(14, 18).into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ii(14, 18), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ii(14, 18).into(), true),
 			],
 		);
-		assert_insert_merge_overlapping(basic(), (uu(), false), uu(), [(
-			uu(),
-			false,
-		)]);
+		assert_insert_merge_overlapping(
+			basic(),
+			(uu().into(), false),
+			uu().into(),
+			[(uu().into(), false)],
+		);
 	}
 	fn assert_insert_merge_overlapping<const N: usize>(
 		mut before: NoditMap<i8, Interval<i8>, bool>,
@@ -2003,64 +3517,68 @@ mod tests {
 	#[test]
 	fn insert_merge_touching_or_overlapping_tests() {
 		assert_insert_merge_touching_or_overlapping(
-			NoditMap::from_slice_strict([(ie(1, 4), false)]).unwrap(),
-			(ie(0, 1), true),
-			ie(0, 4),
-			[(ie(0, 4), true)],
+			NoditMap::from_slice_strict([(ie(1, 4).into(), false)]).unwrap(),
+			(ie(0, 1).into(), true),
+			ie(0, 4).into(),
+			[(ie(0, 4).into(), true)],
 		);
 
 		//copied from insert_merge_overlapping_tests
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ii(0, 2), true),
-			ui(4),
+			(ii(0, 2).into(), true),
+			ui(4).into(),
 			[
-				(ui(4), true),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), true),
+				(ui(4).into(), true),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
 			],
 		);
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ie(14, 16), false),
-			ie(14, 16),
+			(ie(14, 16).into(), false),
+			ie(14, 16).into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), false),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), false),
 			],
 		);
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ii(6, 11), false),
-			ei(5, 11),
-			[(ui(4), false), (ei(5, 11), false), (ie(14, 16), true)],
+			(ii(6, 11).into(), false),
+			ei(5, 11).into(),
+			[
+				(ui(4).into(), false),
+				(ei(5, 11).into(), false),
+				(ie(14, 16).into(), true),
+			],
 		);
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ii(15, 18), true),
-			ii(14, 18),
+			(ii(15, 18).into(), true),
+			ii(14, 18).into(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ii(14, 18), true),
+				(ui(4).into(), false),
+				(ee(5, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ii(14, 18).into(), true),
 			],
 		);
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(uu(), false),
-			uu(),
-			[(uu(), false)],
+			(uu().into(), false),
+			uu().into(),
+			[(uu().into(), false)],
 		);
 		//the only difference from the insert_merge_overlapping
 		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ii(7, 14), false),
-			ee(5, 16),
-			[(ui(4), false), (ee(5, 16), false)],
+			(ii(7, 14).into(), false),
+			ee(5, 16).into(),
+			[(ui(4).into(), false), (ee(5, 16).into(), false)],
 		);
 	}
 	fn assert_insert_merge_touching_or_overlapping<const N: usize>(
@@ -2077,6 +3595,517 @@ mod tests {
 		assert_eq!(before, NoditMap::from_slice_strict(after).unwrap())
 	}
 
+	#[test]
+	fn map_entries_tests() {
+		assert_eq!(
+			basic().map_entries(|interval, value| {
+				(interval.intersection(&ii(6, 15)).unwrap_or(interval), !value)
+			}),
+			NoditMap::from_slice_strict([
+				(ui(4).into(), true),
+				(ii(6, 6).into(), false),
+				(ii(7, 7).into(), true),
+				(ii(14, 15).into(), false),
+			])
+			.unwrap()
+		);
+	}
+	#[test]
+	#[should_panic]
+	fn map_entries_growing_interval_panics() {
+		let _ = basic().map_entries(|interval, value| {
+			(
+				Interval {
+					start: interval.start(),
+					end: interval.end().saturating_add(1),
+				},
+				value,
+			)
+		});
+	}
+
+	#[test]
+	fn map_points_tests() {
+		assert_eq!(
+			basic().map_points(|x| i16::from(x) * 2),
+			Ok(NoditMap::from_slice_strict([
+				(Interval::from(ii(i16::from(i8::MIN) * 2, 8)), false),
+				(ii(12, 12).into(), true),
+				(ii(14, 14).into(), false),
+				(ii(28, 30).into(), true),
+			])
+			.unwrap())
+		);
+		assert_eq!(
+			basic().map_points::<i8, Interval<i8>, _>(|x| x / 2),
+			Err(NonMonotonicError {
+				first: 6,
+				second: 7
+			})
+		);
+		assert_eq!(
+			basic().map_points::<i8, Interval<i8>, _>(|x| -x.saturating_add(1)),
+			Err(NonMonotonicError {
+				first: i8::MIN,
+				second: 4
+			})
+		);
+	}
+
+	#[test]
+	fn bucket_tests() {
+		for bucket in [1_i8, 2, 3, 5, 7, 64, i8::MAX] {
+			for point in i8::MIN..=i8::MAX {
+				let start = bucket_start(point, bucket);
+				let end = bucket_end(point, bucket);
+
+				assert!(start <= point && point <= end);
+				assert!(start == i8::MIN || start % bucket == 0);
+				assert!(end == i8::MAX || (end + 1) % bucket == 0);
+				assert!(
+					i16::from(end) - i16::from(start) < i16::from(bucket)
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn quantize_tests() {
+		assert_eq!(
+			basic().quantize(4, CollisionPolicy::FirstWins),
+			Ok(NoditMap::from_slice_strict([
+				(ui(7).into(), false),
+				(ii(12, 15).into(), true),
+			])
+			.unwrap())
+		);
+		assert_eq!(
+			basic().quantize(4, CollisionPolicy::Merge(&mut |x, y| x || y)),
+			Ok(NoditMap::from_slice_strict([
+				(ui(7).into(), true),
+				(ii(12, 15).into(), true),
+			])
+			.unwrap())
+		);
+		assert_eq!(
+			basic().quantize(4, CollisionPolicy::Error),
+			Err(OverlapError {
+				interval: ii(4, 7).into(),
+				conflict: ui(7).into(),
+				value: true,
+			})
+		);
+		assert_eq!(
+			basic().quantize(i8::MAX, CollisionPolicy::FirstWins),
+			Ok(NoditMap::from_slice_strict([(ii(i8::MIN, 126).into(), false)])
+				.unwrap())
+		);
+		assert_eq!(
+			basic().quantize(1, CollisionPolicy::Error),
+			Ok(basic())
+		);
+	}
+
+	#[test]
+	fn restrict_to_and_subtract_tests() {
+		for (interval1, interval2) in all_non_overlapping_test_bound_entries()
+		{
+			let set =
+				NoditSet::from_slice_strict([interval1, interval2]).unwrap();
+
+			let mut restricted = basic();
+			restricted.restrict_to(&set);
+			let mut subtracted = basic();
+			subtracted.subtract(&set);
+
+			for point in -20..20 {
+				let expected = basic().get_at_point(point).copied();
+				if set.contains_point(point) {
+					assert_eq!(restricted.get_at_point(point).copied(), expected);
+					assert_eq!(subtracted.get_at_point(point), None);
+				} else {
+					assert_eq!(restricted.get_at_point(point), None);
+					assert_eq!(subtracted.get_at_point(point).copied(), expected);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn relation_tests() {
+		let sets = all_non_overlapping_test_bound_entries()
+			.into_iter()
+			.map(|(interval1, interval2)| {
+				NoditSet::from_slice_strict([interval1, interval2]).unwrap()
+			})
+			.chain([NoditSet::new(), basic().keys_set()])
+			.collect::<Vec<_>>();
+
+		for set1 in sets.iter() {
+			for set2 in sets.iter() {
+				let only_in_1 = (i8::MIN..=i8::MAX).find(|point| {
+					set1.contains_point(*point) && !set2.contains_point(*point)
+				});
+				let only_in_2 = (i8::MIN..=i8::MAX).find(|point| {
+					!set1.contains_point(*point) && set2.contains_point(*point)
+				});
+				let in_both = (i8::MIN..=i8::MAX).any(|point| {
+					set1.contains_point(point) && set2.contains_point(point)
+				});
+
+				let expected = match (only_in_1, only_in_2) {
+					(None, None) => Relation::Equal,
+					(None, Some(witness)) => Relation::ProperSubset { witness },
+					(Some(witness), None) => {
+						Relation::ProperSuperset { witness }
+					}
+					(Some(only_in_self), Some(only_in_other)) => {
+						Relation::Incomparable {
+							only_in_self,
+							only_in_other,
+						}
+					}
+				};
+
+				assert_eq!(set1.relation(set2), expected);
+				assert_eq!(set1.is_subset(set2), only_in_1.is_none());
+				assert_eq!(set1.is_superset(set2), only_in_2.is_none());
+				assert_eq!(set1.is_disjoint(set2), !in_both);
+			}
+		}
+
+		//the values and the fragmentation of the map shouldn't matter
+		assert_eq!(
+			basic().relation(basic().keys_set().as_map()),
+			Relation::Equal
+		);
+	}
+
+	#[test]
+	fn diff_and_patch_tests() {
+		let maps = all_non_overlapping_test_bound_entries()
+			.into_iter()
+			.flat_map(|(interval1, interval2)| {
+				[
+					NoditMap::from_slice_strict([
+						(interval1, true),
+						(interval2, true),
+					])
+					.unwrap(),
+					NoditMap::from_slice_strict([
+						(interval1, false),
+						(interval2, true),
+					])
+					.unwrap(),
+				]
+			})
+			.chain([NoditMap::new(), basic()])
+			.collect::<Vec<_>>();
+
+		for old in maps.iter() {
+			for new in maps.iter() {
+				let diff = old.diff(new).collect::<Vec<_>>();
+
+				for point in i8::MIN..=i8::MAX {
+					let expected = match (
+						old.get_at_point(point),
+						new.get_at_point(point),
+					) {
+						(old, new) if old == new => None,
+						(None, Some(new)) => Some((None, Some(new))),
+						(Some(old), None) => Some((Some(old), None)),
+						(Some(old), Some(new)) => Some((Some(old), Some(new))),
+						(None, None) => unreachable!(),
+					};
+					let found = diff
+						.iter()
+						.find(|item| match item {
+							DiffItem::Added(interval, _)
+							| DiffItem::Removed(interval, _)
+							| DiffItem::Changed(interval, _, _) => {
+								interval.contains_point(point)
+							}
+						})
+						.map(|item| match item {
+							DiffItem::Added(_, new) => (None, Some(*new)),
+							DiffItem::Removed(_, old) => (Some(*old), None),
+							DiffItem::Changed(_, old, new) => {
+								(Some(*old), Some(*new))
+							}
+						});
+					assert_eq!(found, expected);
+				}
+
+				let mut patched = old.clone();
+				patched.patch(diff);
+				for point in i8::MIN..=i8::MAX {
+					assert_eq!(
+						patched.get_at_point(point),
+						new.get_at_point(point)
+					);
+				}
+			}
+		}
+
+		//pure fragmentation differences shouldn't show up
+		let fragmented =
+			NoditMap::from_slice_strict([(ie(1, 4), true), (ie(4, 8), true)])
+				.unwrap();
+		let whole = NoditMap::from_slice_strict([(ie(1, 8), true)]).unwrap();
+		assert_eq!(fragmented.diff(&whole).count(), 0);
+	}
+
+	#[test]
+	fn into_interval_tests() {
+		let mut map = basic();
+
+		assert!(map.overlapping(5..7).eq(map.overlapping(ie(5, 7))));
+		assert!(map.overlapping(..=7).eq(map.overlapping(ui(7))));
+		assert!(map.overlapping(..).eq(map.iter()));
+		assert!(map.gaps_trimmed(8..).eq(map.gaps_trimmed(iu(8))));
+		assert!(map
+			.gaps_untrimmed((Bound::Excluded(4), Bound::Unbounded))
+			.eq(map.gaps_untrimmed(eu(4))));
+		assert!(map.contains_interval(..4));
+
+		assert_eq!(
+			map.cut(6..).collect::<Vec<_>>(),
+			[
+				(ie(6, 7).into(), true),
+				(ii(7, 7).into(), false),
+				(ie(14, 16).into(), true),
+			]
+		);
+		assert_eq!(map.remove_overlapping(..=6).count(), 1);
+		assert!(map.is_empty());
+	}
+
+	#[test]
+	fn into_interval_empty_tests() {
+		let mut map = basic();
+
+		for empty in [i8::MIN..i8::MIN, 5..5, i8::MAX..i8::MAX] {
+			assert!(!map.overlaps(empty.clone()));
+			assert_eq!(map.overlapping(empty.clone()).count(), 0);
+			assert_eq!(map.overlapping_mut(empty.clone()).count(), 0);
+			assert_eq!(map.gaps_untrimmed(empty.clone()).count(), 0);
+			assert_eq!(map.gaps_trimmed(empty.clone()).count(), 0);
+			assert!(map.contains_interval(empty.clone()));
+			assert_eq!(map.cut(empty.clone()).count(), 0);
+			assert_eq!(map.remove_overlapping(empty).count(), 0);
+		}
+		assert_eq!(map.overlapping(..i8::MIN).count(), 0);
+		assert!(!map.overlaps((Bound::Excluded(4), Bound::Excluded(5))));
+		assert_eq!(map, basic());
+	}
+
+	#[test]
+	fn check_invariants_tests() {
+		assert_eq!(basic().check_invariants(), Ok(()));
+		assert_eq!(
+			NoditMap::<i8, Interval<i8>, ()>::new().check_invariants(),
+			Ok(())
+		);
+
+		let mut overlapping = basic();
+		overlapping.insert_unchecked(ii(3, 5).into(), false);
+		assert_eq!(
+			overlapping.check_invariants(),
+			Err(InvariantError::Overlap {
+				first: ui(4).into(),
+				second: ii(3, 5).into()
+			})
+		);
+
+		let mut invalid = basic();
+		invalid.insert_unchecked(Interval { start: 10, end: 9 }, false);
+		assert_eq!(
+			invalid.check_invariants(),
+			Err(InvariantError::InvalidInterval {
+				interval: Interval { start: 10, end: 9 }
+			})
+		);
+	}
+
+	#[test]
+	fn parse_tests() {
+		use alloc::string::{String, ToString};
+
+		use crate::ParseErrorKind::*;
+
+		let display = "{[-128, 4] => false, [6, 6] => true, [7, 7] => false, \
+		               [14, 15] => true}";
+		assert_eq!(basic().to_string(), display);
+		assert_eq!(display.parse(), Ok(basic()));
+		assert_eq!(
+			" { } ".parse(),
+			Ok(NoditMap::<i8, Interval<i8>, bool>::new())
+		);
+		assert_eq!(
+			"{(-∞, 4] => false,[6, 6]=>true , (6,8) =>false, [14, inf) => \
+			 true}"
+				.parse::<NoditMap<i8, Interval<i8>, bool>>(),
+			Ok(NoditMap::from_slice_strict([
+				(ui(4).into(), false),
+				(ii(6, 6).into(), true),
+				(ii(7, 7).into(), false),
+				(iu(14).into(), true)
+			])
+			.unwrap())
+		);
+
+		//values may contain commas not followed by an interval
+		let map: NoditMap<i8, Interval<i8>, String> =
+			"{[0, 4] => a, b, [5, 5] => (c)}".parse().unwrap();
+		assert_eq!(
+			map.iter().map(|(_, value)| value.as_str()).collect::<Vec<_>>(),
+			["a, b", "(c)"]
+		);
+
+		let error = |input: &str| {
+			input
+				.parse::<NoditMap<i8, Interval<i8>, bool>>()
+				.map_err(|error| (error.offset, error.kind))
+		};
+		assert_eq!(error("[0, 4] => true"), Err((0, Expected("`{`"))));
+		assert_eq!(error("{[0, 4] => true"), Err((15, Expected("`}`"))));
+		assert_eq!(error("{[0, 4] true}"), Err((8, Expected("`=>`"))));
+		assert_eq!(
+			error("{[0, 4 => true}"),
+			Err((14, Expected("`]` or `)`")))
+		);
+		assert_eq!(error("{[0; 4] => true}"), Err((6, Expected("`,`"))));
+		assert_eq!(error("{[0, x] => true}"), Err((5, InvalidPoint)));
+		assert_eq!(error("{[0, 4] => yes}"), Err((11, InvalidValue)));
+		assert_eq!(error("{[0, 200] => true}"), Err((5, InvalidPoint)));
+		assert_eq!(error("{ (4, 5) => true}"), Err((2, EmptyInterval)));
+		assert_eq!(
+			error("{[0, 4] => true, [4, 5] => true}"),
+			Err((17, Overlap))
+		);
+		assert_eq!(
+			error("{[0, 4] => true [5, 5] => true}"),
+			Err((11, InvalidValue))
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "invalid interval")]
+	fn excluded_min_panic_test() {
+		let _ = ue(u8::MIN);
+	}
+	#[test]
+	#[should_panic(expected = "invalid interval")]
+	fn excluded_max_panic_test() {
+		let _ = ei(i8::MAX, i8::MAX);
+	}
+
+	#[test]
+	fn domain_edge_tests() {
+		domain_edge_tests_for(&U8_EDGES);
+		domain_edge_tests_for(&I8_EDGES);
+	}
+	fn domain_edge_tests_for<I>(edges: &[I])
+	where
+		I: PointType
+			+ CheckedArithmetic
+			+ Countable
+			+ Into<i128>
+			+ TryFrom<i128>
+			+ core::fmt::Debug,
+	{
+		let domain = uu::<I>().points().collect::<Vec<_>>();
+		let points = |intervals: &[Interval<I>]| {
+			domain
+				.iter()
+				.copied()
+				.filter(|point| {
+					intervals.iter().any(|x| x.contains_point(*point))
+				})
+				.collect::<Vec<_>>()
+		};
+		let covered = |map: &NoditMap<I, Interval<I>, ()>| {
+			assert_eq!(map.check_invariants(), Ok(()));
+			points(&map.iter().map(|(key, _)| *key).collect::<Vec<_>>())
+		};
+		let to_point = |x: i128| I::try_from(x).ok();
+
+		let intervals = edge_intervals(edges);
+
+		for a in intervals.iter().copied() {
+			let (start, end) = (a.start().into(), a.end().into());
+
+			assert_eq!(a.len(), Some((end - start + 1) as u128));
+			assert_eq!(a.checked_width(), to_point(end - start));
+			for delta in edges.iter().copied() {
+				let delta_int: i128 = delta.into();
+				assert_eq!(
+					a.checked_translate(delta),
+					to_point(start + delta_int)
+						.zip(to_point(end + delta_int))
+						.map(|(start, end)| Interval { start, end })
+				);
+			}
+
+			let map = NoditMap::from_slice_strict([(a, ())]).unwrap();
+			for point in edges.iter().copied() {
+				match map.get_key_value_at_point(point) {
+					Ok((key, _)) => assert!(key.contains_point(point)),
+					Err(gap) => {
+						assert!(gap.contains_point(point));
+						assert!(!gap.overlaps(&a));
+						assert!(
+							gap.start() == I::MIN || gap.touches(&a),
+							"{gap:?}"
+						);
+					}
+				}
+			}
+
+			for b in intervals.iter().copied() {
+				let a_minus_b = points(&[a])
+					.into_iter()
+					.filter(|point| !b.contains_point(*point))
+					.collect::<Vec<_>>();
+				let b_minus_a = points(&[b])
+					.into_iter()
+					.filter(|point| !a.contains_point(*point))
+					.collect::<Vec<_>>();
+				let union = points(&[a, b]);
+
+				let untrimmed = map.gaps_untrimmed(b).collect::<Vec<_>>();
+				assert!(untrimmed.iter().all(|gap| !gap.overlaps(&a)));
+				let trimmed = map.gaps_trimmed(b).collect::<Vec<_>>();
+				assert_eq!(points(&trimmed), b_minus_a);
+
+				let mut cut = map.clone();
+				let _ = cut.cut(b);
+				assert_eq!(covered(&cut), a_minus_b);
+
+				let mut merged = map.clone();
+				match merged.insert_merge_touching(b, ()) {
+					Ok(_) => assert_eq!(covered(&merged), union),
+					Err(_) => assert!(a.overlaps(&b)),
+				}
+
+				let mut merged = map.clone();
+				merged.insert_merge_overlapping(b, ());
+				assert_eq!(covered(&merged), union);
+
+				let mut merged = map.clone();
+				merged.insert_merge_touching_or_overlapping(b, ());
+				assert_eq!(covered(&merged), union);
+				let expected_len =
+					if a.overlaps(&b) || a.touches(&b) { 1 } else { 2 };
+				assert_eq!(merged.len(), expected_len);
+
+				let mut overwritten = map.clone();
+				let _ = overwritten.insert_overwrite(b, ());
+				assert_eq!(covered(&overwritten), union);
+			}
+		}
+	}
+
 	#[test]
 	fn config_tests() {
 		assert_eq!(config(ie(1, 4), ie(6, 8)), Config::LeftFirstNonOverlapping);
@@ -2218,6 +4247,149 @@ mod tests {
 		assert_eq!(input.translate(-2), Interval { start: 3, end: 8 });
 	}
 
+	#[test]
+	fn test_interval_algebra() {
+		let points = |interval: Option<Interval<i8>>| {
+			NUMBERS_DOMAIN
+				.iter()
+				.filter(|point| {
+					interval.is_some_and(|x| x.contains_point(**point))
+				})
+				.copied()
+				.collect::<Vec<_>>()
+		};
+
+		for a in all_valid_test_bounds() {
+			assert_eq!(a.len(), Some(points(Some(a)).len() as u128));
+
+			for b in all_valid_test_bounds() {
+				let (before, after) = a.difference(&b);
+				let mut difference = points(before);
+				difference.extend(points(after));
+				assert_eq!(
+					difference,
+					points(Some(a))
+						.into_iter()
+						.filter(|point| !b.contains_point(*point))
+						.collect::<Vec<_>>()
+				);
+
+				let hull = a.hull(&b);
+				assert!(
+					hull.contains_interval(&a) && hull.contains_interval(&b)
+				);
+
+				assert_eq!(
+					a.touches(&b),
+					!a.overlaps(&b)
+						&& (a.end() + 1 == b.start()
+							|| b.end() + 1 == a.start())
+				);
+			}
+
+			for point in NUMBERS_DOMAIN {
+				let (left, right) = a.split_at(*point);
+				assert!(points(left).iter().all(|x| x < point));
+				assert!(points(right).iter().all(|x| x >= point));
+				let mut split = points(left);
+				split.extend(points(right));
+				assert_eq!(split, points(Some(a)));
+			}
+		}
+
+		assert_eq!(ie(0, u8::MAX), ii(0, u8::MAX - 1));
+		assert_eq!(ee(i8::MIN, i8::MAX), ii(i8::MIN + 1, i8::MAX - 1));
+		assert_eq!(uu::<i8>().len(), Some(256));
+		assert_eq!(uu::<i128>().len(), None);
+		assert_eq!(ii(i128::MIN, i128::MAX - 1).len(), Some(u128::MAX));
+		assert_eq!(
+			ii(u8::MAX, u8::MAX).split_at(u8::MAX),
+			(None, Some(ii(u8::MAX, u8::MAX)))
+		);
+		assert_eq!(ii(0, 4_u8).split_at(0), (None, Some(ii(0, 4))));
+		assert_eq!(iu(0_u8).touches(&ii(0, 0)), false);
+	}
+
+	#[test]
+	fn test_points() {
+		for interval in all_valid_test_bounds() {
+			let expected = NUMBERS_DOMAIN
+				.iter()
+				.copied()
+				.filter(|point| interval.contains_point(*point))
+				.collect::<Vec<_>>();
+
+			assert_eq!(interval.points().collect::<Vec<_>>(), expected);
+			assert_eq!(interval.points().len(), expected.len());
+			assert_eq!(
+				interval.points().rev().collect::<Vec<_>>(),
+				expected.iter().rev().copied().collect::<Vec<_>>()
+			);
+			for step in 1..4 {
+				assert_eq!(
+					interval.points().step_by(step).collect::<Vec<_>>(),
+					expected.iter().step_by(step).copied().collect::<Vec<_>>()
+				);
+				assert_eq!(
+					interval.points().rev().step_by(step).collect::<Vec<_>>(),
+					expected
+						.iter()
+						.rev()
+						.step_by(step)
+						.copied()
+						.collect::<Vec<_>>()
+				);
+			}
+
+			//alternating from both ends meets in the middle
+			let mut points = interval.points();
+			let mut meet = Vec::new();
+			while let Some(front) = points.next() {
+				meet.push(front);
+				meet.extend(points.next_back());
+			}
+			meet.sort();
+			assert_eq!(meet, expected);
+			assert_eq!(points.next_back(), None);
+		}
+
+		assert_eq!(
+			ii(253, u8::MAX).points().collect::<Vec<_>>(),
+			[253, 254, 255]
+		);
+		assert_eq!(
+			ii(i8::MIN, -126).points().rev().collect::<Vec<_>>(),
+			[-126, -127, -128]
+		);
+		assert_eq!(uu::<u8>().points().len(), 256);
+		assert_eq!(uu::<u8>().points().nth(255), Some(u8::MAX));
+		assert_eq!(uu::<u8>().points().nth(256), None);
+		assert_eq!(uu::<i8>().points().nth_back(255), Some(i8::MIN));
+		assert_eq!(uu::<i8>().points().last(), Some(i8::MAX));
+		assert_eq!(
+			uu::<u64>().points().step_by(1 << 62).collect::<Vec<_>>(),
+			[0, 1 << 62, 2 << 62, 3 << 62]
+		);
+		assert_eq!(uu::<u128>().points().size_hint(), (usize::MAX, None));
+
+		let map = basic();
+		assert_eq!(
+			map.points_with_values()
+				.skip_while(|(point, _)| *point < 2)
+				.take(5)
+				.collect::<Vec<_>>(),
+			[(2, &false), (3, &false), (4, &false), (6, &true), (7, &false)]
+		);
+		assert_eq!(map.points_with_values().next(), Some((i8::MIN, &false)));
+		assert_eq!(
+			map.points_with_values().rev().collect::<Vec<_>>(),
+			[(15, &true), (14, &true), (7, &false), (6, &true)]
+				.into_iter()
+				.chain((i8::MIN..=4).rev().map(|point| (point, &false)))
+				.collect::<Vec<_>>()
+		);
+	}
+
 	// Test Helper Functions
 	//======================
 	fn all_non_overlapping_test_bound_entries()
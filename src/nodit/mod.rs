@@ -2,5 +2,9 @@
 //!
 //! `nodit` stands for Non-Overlapping Discrete Interval Tree.
 
+pub mod bimap;
 pub mod map;
+pub mod observed;
+pub mod persistent;
 pub mod set;
+pub mod transaction;
@@ -0,0 +1,500 @@
+//! A module containing [`ObservedNoditMap`] and the [`Observer`] trait.
+//!
+//! Since [`ObservedNoditMap`] is just a wrapper around [`NoditMap`] which
+//! reports its changes, most of the methods' docs will point towards the
+//! equivalent method's docs on [`NoditMap`] to prevent inconsistency.
+
+use alloc::vec::Vec;
+
+use crate::utils::{cut_interval, expand, query_interval};
+use crate::{
+	Interval, IntervalType, IntoInterval, NoditMap, OverlapError, PointType,
+};
+
+/// A receiver of the changes made to an [`ObservedNoditMap`].
+///
+/// Each event describes a change to the entries of the map, so applying
+/// the events in the order they are received to a copy of the map keeps
+/// the copy identical to the map, including how it is fragmented. Every
+/// method has an empty default implementation so you only need to
+/// implement the events you are interested in.
+pub trait Observer<K, V> {
+	/// A new entry was inserted.
+	fn inserted(&mut self, interval: K, value: &V) {
+		let _ = (interval, value);
+	}
+	/// The entry with the given interval was removed.
+	fn removed(&mut self, interval: K, value: V) {
+		let _ = (interval, value);
+	}
+	/// The entry with the interval `old` was split into two touching
+	/// entries `left` and `right`, which both keep its value.
+	fn split(&mut self, old: K, left: K, right: K) {
+		let _ = (old, left, right);
+	}
+	/// The entries with the intervals in `parts` were removed and a new
+	/// entry covering both them and the interval being inserted was
+	/// inserted with the interval `result` and the value being inserted.
+	fn merged(&mut self, parts: &[K], result: K, value: &V) {
+		let _ = (parts, result, value);
+	}
+}
+
+/// A [`NoditMap`] which reports every change made to it to an
+/// [`Observer`].
+///
+/// This includes the implicit changes made by the editing methods, such as
+/// the entries split by [`ObservedNoditMap::cut()`] and the entries merged
+/// by [`ObservedNoditMap::insert_merge_touching()`].
+///
+/// Since the changes are reported per entry there is no way to get
+/// mutable references to the values in the map, instead you can remove
+/// and re-insert an entry.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is a interval over.
+///
+/// `K` is the generic type parameter for the interval type stored as the
+/// keys in the map.
+///
+/// `V` is the generic type parameter for the values associated with the
+/// keys in the map.
+///
+/// `O` is the generic type parameter for the [`Observer`].
+///
+/// # Examples
+/// ```
+/// use nodit::interval::{ie, Interval};
+/// use nodit::nodit::observed::Observer;
+/// use nodit::{NoditMap, ObservedNoditMap};
+///
+/// #[derive(Default)]
+/// struct Log(Vec<String>);
+///
+/// impl Observer<Interval<u8>, char> for Log {
+/// 	fn inserted(&mut self, interval: Interval<u8>, value: &char) {
+/// 		self.0.push(format!("inserted {interval:?} {value}"));
+/// 	}
+/// 	fn removed(&mut self, interval: Interval<u8>, value: char) {
+/// 		self.0.push(format!("removed {interval:?} {value}"));
+/// 	}
+/// 	fn split(
+/// 		&mut self,
+/// 		old: Interval<u8>,
+/// 		left: Interval<u8>,
+/// 		right: Interval<u8>,
+/// 	) {
+/// 		self.0.push(format!("split {old:?} {left:?} {right:?}"));
+/// 	}
+/// }
+///
+/// let mut map = ObservedNoditMap::new(NoditMap::new(), Log::default());
+///
+/// map.insert_strict(Interval::from(ie(1, 8)), 'a').unwrap();
+/// map.insert_overwrite(Interval::from(ie(4, 8)), 'b');
+///
+/// assert_eq!(
+/// 	map.observer().0,
+/// 	[
+/// 		"inserted Interval { start: 1, end: 7 } a",
+/// 		"split Interval { start: 1, end: 7 } Interval { start: 1, end: 3 } Interval { start: 4, end: 7 }",
+/// 		"removed Interval { start: 4, end: 7 } a",
+/// 		"inserted Interval { start: 4, end: 7 } b",
+/// 	]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObservedNoditMap<I, K, V, O> {
+	map: NoditMap<I, K, V>,
+	observer: O,
+}
+
+impl<I, K, V, O> ObservedNoditMap<I, K, V, O>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	O: Observer<K, V>,
+{
+	/// Wraps the given map so that every change made to it from now on
+	/// is reported to the given observer.
+	pub fn new(map: NoditMap<I, K, V>, observer: O) -> Self {
+		ObservedNoditMap { map, observer }
+	}
+
+	/// Returns a reference to the underlying [`NoditMap`].
+	pub fn as_map(&self) -> &NoditMap<I, K, V> {
+		&self.map
+	}
+	/// Returns a reference to the observer.
+	pub fn observer(&self) -> &O {
+		&self.observer
+	}
+	/// Returns a mutable reference to the observer.
+	pub fn observer_mut(&mut self) -> &mut O {
+		&mut self.observer
+	}
+	/// Consumes the map returning the underlying [`NoditMap`] and the
+	/// observer.
+	pub fn into_parts(self) -> (NoditMap<I, K, V>, O) {
+		(self.map, self.observer)
+	}
+
+	/// See [`NoditMap::overlaps()`] for more details.
+	pub fn overlaps<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.overlaps(interval)
+	}
+	/// See [`NoditMap::overlapping()`] for more details.
+	pub fn overlapping<Q, M>(
+		&self,
+		interval: Q,
+	) -> impl DoubleEndedIterator<Item = (&K, &V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.overlapping(interval)
+	}
+	/// See [`NoditMap::get_at_point()`] for more details.
+	pub fn get_at_point(&self, point: I) -> Option<&V> {
+		self.map.get_at_point(point)
+	}
+	/// See [`NoditMap::get_key_value_at_point()`] for more details.
+	pub fn get_key_value_at_point(&self, point: I) -> Result<(&K, &V), K> {
+		self.map.get_key_value_at_point(point)
+	}
+	/// See [`NoditMap::contains_point()`] for more details.
+	pub fn contains_point(&self, point: I) -> bool {
+		self.map.contains_point(point)
+	}
+	/// See [`NoditMap::gaps_untrimmed()`] for more details.
+	pub fn gaps_untrimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		self.map.gaps_untrimmed(interval)
+	}
+	/// See [`NoditMap::gaps_trimmed()`] for more details.
+	pub fn gaps_trimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		self.map.gaps_trimmed(interval)
+	}
+	/// See [`NoditMap::contains_interval()`] for more details.
+	pub fn contains_interval<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.map.contains_interval(interval)
+	}
+
+	/// See [`NoditMap::remove_overlapping()`] for more details.
+	pub fn remove_overlapping<Q, M>(
+		&mut self,
+		interval: Q,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+		V: Clone,
+	{
+		let removed = self.map.remove_overlapping(interval).collect::<Vec<_>>();
+
+		for (key, value) in removed.iter() {
+			self.observer.removed(*key, value.clone());
+		}
+
+		removed.into_iter()
+	}
+	/// See [`NoditMap::cut()`] for more details.
+	///
+	/// An entry which is only partly inside the given interval is first
+	/// split so that the part inside the interval is its own entry, which
+	/// is then removed.
+	pub fn cut<Q, M>(&mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+		V: Clone,
+	{
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
+
+		let keys = self
+			.map
+			.overlapping(interval)
+			.map(|(key, _)| *key)
+			.collect::<Vec<_>>();
+
+		let cut = self.map.cut(interval).collect::<Vec<_>>();
+
+		//the cut returns one piece for each overlapping key and both are in
+		//ascending order, so each key pairs up with its piece by position
+		for (key, (inside_cut, value)) in keys.into_iter().zip(cut.iter()) {
+			let cut_result = cut_interval(key, interval);
+
+			let mut rest = key;
+			if let Some(before_cut) = cut_result.before_cut {
+				let after = K::from(Interval {
+					start: inside_cut.start(),
+					end: rest.end(),
+				});
+				self.observer.split(rest, K::from(before_cut), after);
+				rest = after;
+			}
+			if let Some(after_cut) = cut_result.after_cut {
+				self.observer.split(rest, *inside_cut, K::from(after_cut));
+			}
+
+			self.observer.removed(*inside_cut, value.clone());
+		}
+
+		cut.into_iter()
+	}
+	/// See [`NoditMap::insert_strict()`] for more details.
+	pub fn insert_strict(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		self.map.insert_strict(interval, value)?;
+
+		self.report_insert(&[], interval);
+
+		Ok(())
+	}
+	/// See [`NoditMap::insert_merge_touching()`] for more details.
+	pub fn insert_merge_touching(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		let parts = self.keys_overlapping(expand(interval), |_| true);
+
+		let merged = self.map.insert_merge_touching(interval, value)?;
+
+		self.report_insert(&parts, merged);
+
+		Ok(merged)
+	}
+	/// See [`NoditMap::insert_merge_touching_if_values_equal()`] for more
+	/// details.
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Eq,
+	{
+		let parts =
+			self.keys_overlapping(expand(interval), |other| *other == value);
+
+		let merged = self
+			.map
+			.insert_merge_touching_if_values_equal(interval, value)?;
+
+		self.report_insert(&parts, merged);
+
+		Ok(merged)
+	}
+	/// See [`NoditMap::insert_merge_overlapping()`] for more details.
+	pub fn insert_merge_overlapping(&mut self, interval: K, value: V) -> K {
+		let parts = self.keys_overlapping(interval, |_| true);
+
+		let merged = self.map.insert_merge_overlapping(interval, value);
+
+		self.report_insert(&parts, merged);
+
+		merged
+	}
+	/// See [`NoditMap::insert_merge_touching_or_overlapping()`] for more
+	/// details.
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> K {
+		let parts = self.keys_overlapping(expand(interval), |_| true);
+
+		let merged = self
+			.map
+			.insert_merge_touching_or_overlapping(interval, value);
+
+		self.report_insert(&parts, merged);
+
+		merged
+	}
+	/// See [`NoditMap::insert_overwrite()`] for more details.
+	///
+	/// The overwritten parts of other entries are reported as in
+	/// [`ObservedNoditMap::cut()`] before the new entry is reported as
+	/// inserted.
+	pub fn insert_overwrite(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		V: Clone,
+	{
+		let cut = self.cut(interval).collect::<Vec<_>>();
+
+		//the interval was just cut out so there's nothing left to overlap
+		self.map.insert_unchecked(interval, value);
+		self.report_insert(&[], interval);
+
+		cut.into_iter()
+	}
+
+	fn keys_overlapping<F>(&self, interval: K, mut predicate: F) -> Vec<K>
+	where
+		F: FnMut(&V) -> bool,
+	{
+		self.map
+			.overlapping(interval)
+			.filter(|(_, value)| predicate(value))
+			.map(|(key, _)| *key)
+			.collect()
+	}
+	/// Reports the entry at `interval` as inserted, or as merged if any
+	/// other entries were merged into it.
+	fn report_insert(&mut self, parts: &[K], interval: K) {
+		let value = self
+			.map
+			.get_at_point(interval.start())
+			.expect("the interval was just inserted into the map");
+
+		if parts.is_empty() {
+			self.observer.inserted(interval, value);
+		} else {
+			self.observer.merged(parts, interval, value);
+		}
+	}
+}
+
+impl<I, K, V, O> ObservedNoditMap<I, K, V, O> {
+	/// See [`NoditMap::len()`] for more details.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+	/// See [`NoditMap::is_empty()`] for more details.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+	/// See [`NoditMap::iter()`] for more details.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.map.iter()
+	}
+	/// See [`NoditMap::first_key_value()`] for more details.
+	pub fn first_key_value(&self) -> Option<(&K, &V)> {
+		self.map.first_key_value()
+	}
+	/// See [`NoditMap::last_key_value()`] for more details.
+	pub fn last_key_value(&self) -> Option<(&K, &V)> {
+		self.map.last_key_value()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ee, ie, ii, ui};
+
+	/// Mirrors the map by applying each event to a second map.
+	#[derive(Default)]
+	struct Mirror(NoditMap<i8, Interval<i8>, bool>);
+
+	impl Observer<Interval<i8>, bool> for Mirror {
+		fn inserted(&mut self, interval: Interval<i8>, value: &bool) {
+			self.0.insert_strict(interval, *value).unwrap();
+		}
+		fn removed(&mut self, interval: Interval<i8>, value: bool) {
+			assert_eq!(
+				self.0.remove_overlapping(interval).collect::<Vec<_>>(),
+				[(interval, value)]
+			);
+		}
+		fn split(
+			&mut self,
+			old: Interval<i8>,
+			left: Interval<i8>,
+			right: Interval<i8>,
+		) {
+			let removed = self.0.remove_overlapping(old).collect::<Vec<_>>();
+			assert_eq!(removed.len(), 1);
+			assert_eq!(removed[0].0, old);
+			assert_eq!(left.end.checked_add(1), Some(right.start));
+
+			self.0.insert_strict(left, removed[0].1).unwrap();
+			self.0.insert_strict(right, removed[0].1).unwrap();
+		}
+		fn merged(
+			&mut self,
+			parts: &[Interval<i8>],
+			result: Interval<i8>,
+			value: &bool,
+		) {
+			for part in parts {
+				let removed =
+					self.0.remove_overlapping(*part).collect::<Vec<_>>();
+				assert_eq!(removed.len(), 1);
+				assert_eq!(removed[0].0, *part);
+			}
+			self.0.insert_strict(result, *value).unwrap();
+		}
+	}
+
+	fn basic() -> NoditMap<i8, Interval<i8>, bool> {
+		NoditMap::from_slice_strict([
+			(ui(4).into(), false),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
+		])
+		.unwrap()
+	}
+
+	#[test]
+	fn mirror_tests() {
+		let numbers = [2, 4, 6, 7, 8, 10, 14, 16];
+		for start in numbers {
+			for end in numbers.into_iter().filter(|end| *end >= start) {
+				let interval = ii(start, end);
+				let shifted = ii(start + 1, end + 3);
+
+				let mut map = ObservedNoditMap::new(basic(), Mirror(basic()));
+
+				let _ = map.insert_merge_touching(interval.into(), true);
+				let _ = map.insert_merge_touching_if_values_equal(
+					interval.into(),
+					false,
+				);
+				map.insert_merge_touching_or_overlapping(shifted.into(), false);
+				assert_eq!(map.as_map(), &map.observer().0);
+
+				let _ = map.cut(interval).count();
+				assert_eq!(map.as_map(), &map.observer().0);
+
+				let _ = map.insert_strict(interval.into(), true);
+				map.insert_merge_overlapping(shifted.into(), true);
+				let _ = map.insert_overwrite(interval.into(), false).count();
+				assert_eq!(map.as_map(), &map.observer().0);
+
+				let _ = map.remove_overlapping(shifted).count();
+				assert_eq!(map.as_map(), &map.observer().0);
+			}
+		}
+	}
+}
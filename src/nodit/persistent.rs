@@ -0,0 +1,773 @@
+//! A module containing [`PersistentNoditMap`].
+//!
+//! Since [`PersistentNoditMap`] has the same semantics as [`NoditMap`],
+//! most of the methods' docs will point towards the equivalent method's
+//! docs on [`NoditMap`] to prevent inconsistency.
+
+use alloc::vec::Vec;
+
+use itertools::Itertools;
+use rpds::RedBlackTreeMapSync;
+
+use crate::utils::{
+	cut_interval, expand, invalid_interval_panic, query_interval,
+};
+use crate::{
+	InclusiveInterval, Interval, IntervalType, IntoInterval, NoditMap,
+	OverlapError, PointType,
+};
+
+/// A persistent version of [`NoditMap`] whose clones share their
+/// structure.
+///
+/// Cloning a [`PersistentNoditMap`] is `O(1)` and every update only copies
+/// the `O(log n)` nodes on the path to the entries it changes, leaving
+/// any other clones untouched. This makes it cheap to take a snapshot of
+/// a map before each of many speculative updates, for example at every
+/// node of a search.
+///
+/// The trade-off is that lookups are somewhat slower than on a
+/// [`NoditMap`] and that every update requires `V: Clone`, since values
+/// may be shared with other clones of the map.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is a interval over.
+///
+/// `K` is the generic type parameter for the interval type stored as the
+/// keys in the map.
+///
+/// `V` is the generic type parameter for the values associated with the
+/// keys in the map.
+///
+/// Phrasing it another way: `I` is the point type, `K` is the interval type, and `V` is the value type.
+///
+/// # Examples
+/// ```
+/// use nodit::PersistentNoditMap;
+/// use nodit::interval::ie;
+///
+/// let mut base = PersistentNoditMap::new();
+/// base.insert_strict(ie(1, 4), 'a').unwrap();
+/// base.insert_strict(ie(6, 8), 'b').unwrap();
+///
+/// let mut branch = base.clone();
+/// branch.insert_overwrite(ie(2, 7), 'c');
+///
+/// assert_eq!(
+/// 	base.iter().collect::<Vec<_>>(),
+/// 	[(&ie(1, 4), &'a'), (&ie(6, 8), &'b')]
+/// );
+/// assert_eq!(
+/// 	branch.iter().collect::<Vec<_>>(),
+/// 	[(&ie(1, 2), &'a'), (&ie(2, 7), &'c'), (&ie(7, 8), &'b')]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PersistentNoditMap<I, K, V> {
+	//since the intervals don't overlap their start points are unique and
+	//sorting by them also sorts the intervals
+	inner: RedBlackTreeMapSync<I, (K, V)>,
+}
+
+impl<I, K, V> PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+{
+	/// See [`NoditMap::overlaps()`] for more details.
+	pub fn overlaps<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.overlapping(interval).next().is_some()
+	}
+	/// See [`NoditMap::overlapping()`] for more details.
+	pub fn overlapping<Q, M>(
+		&self,
+		interval: Q,
+	) -> impl DoubleEndedIterator<Item = (&K, &V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		query_interval(interval)
+			.map(|interval| self.overlapping_raw(interval))
+			.into_iter()
+			.flatten()
+	}
+	fn overlapping_raw(
+		&self,
+		interval: Interval<I>,
+	) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		//the only interval starting before the given interval which
+		//could overlap it is the last one
+		let before = self
+			.inner
+			.range(..interval.start())
+			.next_back()
+			.filter(|(_, (key, _))| key.end() >= interval.start());
+
+		before
+			.into_iter()
+			.chain(self.inner.range(interval.start()..=interval.end()))
+			.map(|(_, (key, value))| (key, value))
+	}
+	/// See [`NoditMap::get_at_point()`] for more details.
+	pub fn get_at_point(&self, point: I) -> Option<&V> {
+		self.get_key_value_at_point(point)
+			.ok()
+			.map(|(_, value)| value)
+	}
+	/// See [`NoditMap::contains_point()`] for more details.
+	pub fn contains_point(&self, point: I) -> bool {
+		self.get_key_value_at_point(point).is_ok()
+	}
+	/// See [`NoditMap::get_key_value_at_point()`] for more details.
+	pub fn get_key_value_at_point(&self, point: I) -> Result<(&K, &V), K> {
+		self.inner
+			.range(..=point)
+			.next_back()
+			.filter(|(_, (key, _))| key.end() >= point)
+			.map(|(_, (key, value))| (key, value))
+			.ok_or_else(|| K::from(self.get_gap_at_raw(point)))
+	}
+	fn get_gap_at_raw(&self, point: I) -> Interval<I> {
+		let lower = self.inner.range(..point).next_back();
+		let upper = self.inner.range(point..).next();
+
+		//point isn't in any interval so the interval below it must end
+		//before it and the interval above it must start after it
+		Interval {
+			start: lower
+				.map_or(I::MIN, |(_, (lower, _))| lower.end().up().unwrap()),
+			end: upper.map_or(I::MAX, |(_, (upper, _))| {
+				upper.start().down().unwrap()
+			}),
+		}
+	}
+	/// See [`NoditMap::remove_overlapping()`] for more details.
+	pub fn remove_overlapping<Q, M>(
+		&mut self,
+		interval: Q,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+		V: Clone,
+	{
+		let removed = self
+			.overlapping(interval)
+			.map(|(key, value)| (*key, value.clone()))
+			.collect::<Vec<_>>();
+
+		for (key, _) in removed.iter() {
+			self.inner.remove_mut(&key.start());
+		}
+
+		removed.into_iter()
+	}
+	/// See [`NoditMap::cut()`] for more details.
+	pub fn cut<Q, M>(&mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+		V: Clone,
+	{
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
+
+		let mut result = Vec::new();
+
+		for (key, value) in self.remove_overlapping(interval) {
+			let cut_result = cut_interval(key, interval);
+
+			if let Some(before_cut) = cut_result.before_cut {
+				self.insert_unchecked(K::from(before_cut), value.clone());
+			}
+			if let Some(after_cut) = cut_result.after_cut {
+				self.insert_unchecked(K::from(after_cut), value.clone());
+			}
+
+			result.push((K::from(cut_result.inside_cut.unwrap()), value));
+		}
+
+		result.into_iter()
+	}
+	/// See [`NoditMap::gaps_untrimmed()`] for more details.
+	pub fn gaps_untrimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		query_interval(interval)
+			.map(|interval| self.gaps_untrimmed_raw(interval))
+			.into_iter()
+			.flatten()
+	}
+	fn gaps_untrimmed_raw(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = K> + '_ {
+		// If the start or end point of interval is not
+		// contained within a interval in the map then we need to
+		// generate the gaps.
+		let start_gap = (!self.contains_point(interval.start()))
+			.then(|| self.get_gap_at_raw(interval.start()));
+		let end_gap = (!self.contains_point(interval.end()))
+			.then(|| self.get_gap_at_raw(interval.end()))
+			//it might be the same gap as the start gap
+			.filter(|end_gap| {
+				start_gap.map(|start_gap| start_gap.start)
+					!= Some(end_gap.start)
+			});
+
+		let inner_gaps = self
+			.overlapping_raw(interval)
+			.map(|(key, _)| (key.start(), key.end()))
+			.tuple_windows()
+			//first ends before second starts so neither can be at the edge
+			//of the domain
+			.map(|(first, second)| Interval {
+				start: first.1.up().unwrap(),
+				end: second.0.down().unwrap(),
+			})
+			.filter(|interval| interval.is_valid());
+
+		start_gap
+			.into_iter()
+			.chain(inner_gaps)
+			.chain(end_gap)
+			.map(K::from)
+	}
+	/// See [`NoditMap::gaps_trimmed()`] for more details.
+	pub fn gaps_trimmed<'a, Q, M>(
+		&'a self,
+		interval: Q,
+	) -> impl Iterator<Item = K> + 'a
+	where
+		Q: IntoInterval<I, M> + 'a,
+	{
+		query_interval(interval)
+			.map(|interval| {
+				self.gaps_untrimmed_raw(interval).map(move |gap| {
+					K::from(Interval {
+						start: I::max(gap.start(), interval.start()),
+						end: I::min(gap.end(), interval.end()),
+					})
+				})
+			})
+			.into_iter()
+			.flatten()
+	}
+	/// See [`NoditMap::contains_interval()`] for more details.
+	pub fn contains_interval<Q, M>(&self, interval: Q) -> bool
+	where
+		Q: IntoInterval<I, M>,
+	{
+		self.gaps_untrimmed(interval).next().is_none()
+	}
+	/// See [`NoditMap::insert_strict()`] for more details.
+	pub fn insert_strict(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>>
+	where
+		V: Clone,
+	{
+		invalid_interval_panic(interval);
+
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		self.insert_unchecked(interval, value);
+
+		Ok(())
+	}
+	fn insert_unchecked(&mut self, interval: K, value: V)
+	where
+		V: Clone,
+	{
+		self.inner.insert_mut(interval.start(), (interval, value));
+	}
+	//must only be called once the interval is known to overlap the map
+	fn overlap_error(&self, interval: K, value: V) -> OverlapError<K, V> {
+		let (conflict, _) = self
+			.overlapping(interval)
+			.next()
+			.expect("the interval overlaps the map");
+
+		OverlapError {
+			interval,
+			conflict: *conflict,
+			value,
+		}
+	}
+	/// See [`NoditMap::insert_merge_touching()`] for more details.
+	pub fn insert_merge_touching(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Clone,
+	{
+		invalid_interval_panic(interval);
+
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		Ok(self.insert_merge_with(interval, value, expand(interval), |_| true))
+	}
+	/// See [`NoditMap::insert_merge_touching_if_values_equal()`] for more
+	/// details.
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Clone + Eq,
+	{
+		invalid_interval_panic(interval);
+
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		let merge_value = value.clone();
+		Ok(self.insert_merge_with(
+			interval,
+			value,
+			expand(interval),
+			|other_value| *other_value == merge_value,
+		))
+	}
+	/// See [`NoditMap::insert_merge_overlapping()`] for more details.
+	pub fn insert_merge_overlapping(&mut self, interval: K, value: V) -> K
+	where
+		V: Clone,
+	{
+		invalid_interval_panic(interval);
+
+		self.insert_merge_with(interval, value, interval, |_| true)
+	}
+	/// See [`NoditMap::insert_merge_touching_or_overlapping()`] for more
+	/// details.
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> K
+	where
+		V: Clone,
+	{
+		invalid_interval_panic(interval);
+
+		self.insert_merge_with(interval, value, expand(interval), |_| true)
+	}
+	/// Removes every entry overlapping `search` whose value matches
+	/// `should_merge` and inserts the given entry extended to cover them.
+	fn insert_merge_with<F>(
+		&mut self,
+		interval: K,
+		value: V,
+		search: K,
+		mut should_merge: F,
+	) -> K
+	where
+		V: Clone,
+		F: FnMut(&V) -> bool,
+	{
+		let merging = self
+			.overlapping(search)
+			.filter(|(_, value)| should_merge(value))
+			.map(|(key, _)| *key)
+			.collect::<Vec<_>>();
+
+		let mut merged = Interval {
+			start: interval.start(),
+			end: interval.end(),
+		};
+		for key in merging {
+			merged.start = I::min(merged.start, key.start());
+			merged.end = I::max(merged.end, key.end());
+
+			self.inner.remove_mut(&key.start());
+		}
+
+		let merged = K::from(merged);
+		self.insert_unchecked(merged, value);
+
+		merged
+	}
+	/// See [`NoditMap::insert_overwrite()`] for more details.
+	pub fn insert_overwrite(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		V: Clone,
+	{
+		invalid_interval_panic(interval);
+
+		let cut = self.cut(Interval {
+			start: interval.start(),
+			end: interval.end(),
+		});
+		self.insert_unchecked(interval, value);
+		cut
+	}
+	/// See [`NoditMap::from_slice_strict()`] for more details.
+	pub fn from_slice_strict<const N: usize>(
+		slice: [(K, V); N],
+	) -> Result<PersistentNoditMap<I, K, V>, OverlapError<K, V>>
+	where
+		V: Clone,
+	{
+		PersistentNoditMap::from_iter_strict(slice.into_iter())
+	}
+	/// See [`NoditMap::from_iter_strict()`] for more details.
+	pub fn from_iter_strict(
+		iter: impl Iterator<Item = (K, V)>,
+	) -> Result<PersistentNoditMap<I, K, V>, OverlapError<K, V>>
+	where
+		V: Clone,
+	{
+		let mut map = PersistentNoditMap::new();
+		for (interval, value) in iter {
+			map.insert_strict(interval, value)?;
+		}
+		Ok(map)
+	}
+}
+
+impl<I, K, V> PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+{
+	/// See [`NoditMap::new()`] for more details.
+	pub fn new() -> Self {
+		PersistentNoditMap {
+			inner: RedBlackTreeMapSync::new_sync(),
+		}
+	}
+	/// See [`NoditMap::len()`] for more details.
+	pub fn len(&self) -> usize {
+		self.inner.size()
+	}
+	/// See [`NoditMap::is_empty()`] for more details.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+	/// See [`NoditMap::iter()`] for more details.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.inner.iter().map(|(_, (key, value))| (key, value))
+	}
+	/// See [`NoditMap::first_key_value()`] for more details.
+	pub fn first_key_value(&self) -> Option<(&K, &V)> {
+		self.inner.first().map(|(_, (key, value))| (key, value))
+	}
+	/// See [`NoditMap::last_key_value()`] for more details.
+	pub fn last_key_value(&self) -> Option<(&K, &V)> {
+		self.inner.last().map(|(_, (key, value))| (key, value))
+	}
+}
+
+// Trait Impls ==========================
+
+impl<I, K, V> Clone for PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+{
+	fn clone(&self) -> Self {
+		PersistentNoditMap {
+			inner: self.inner.clone(),
+		}
+	}
+}
+
+impl<I, K, V> PartialEq for PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+	K: PartialEq,
+	V: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+impl<I, K, V> Eq for PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+	K: Eq,
+	V: Eq,
+{
+}
+
+impl<I, K, V> Default for PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+{
+	fn default() -> Self {
+		PersistentNoditMap::new()
+	}
+}
+
+impl<I, K, V> From<NoditMap<I, K, V>> for PersistentNoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Clone,
+{
+	fn from(map: NoditMap<I, K, V>) -> Self {
+		let mut persistent = PersistentNoditMap::new();
+		for (interval, value) in map {
+			persistent.insert_unchecked(interval, value);
+		}
+		persistent
+	}
+}
+
+impl<I, K, V> From<PersistentNoditMap<I, K, V>> for NoditMap<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Clone,
+{
+	fn from(persistent: PersistentNoditMap<I, K, V>) -> Self {
+		let mut map = NoditMap::new();
+		//the intervals of a valid PersistentNoditMap never overlap
+		for (interval, value) in persistent.iter() {
+			map.insert_unchecked(*interval, value.clone());
+		}
+		map
+	}
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+	use core::fmt;
+	use core::marker::PhantomData;
+
+	use serde::de::{SeqAccess, Visitor};
+	use serde::ser::SerializeSeq;
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	use crate::utils::deserialize_overlap_error;
+	use crate::{IntervalType, PersistentNoditMap, PointType};
+
+	impl<I, K, V> Serialize for PersistentNoditMap<I, K, V>
+	where
+		I: PointType,
+		K: Serialize,
+		V: Serialize,
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: Serializer,
+		{
+			let mut seq = serializer.serialize_seq(Some(self.len()))?;
+			for (interval, value) in self.iter() {
+				seq.serialize_element(&(interval, value))?;
+			}
+			seq.end()
+		}
+	}
+
+	impl<'de, I, K, V> Deserialize<'de> for PersistentNoditMap<I, K, V>
+	where
+		I: PointType,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
+		V: Clone + Deserialize<'de>,
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: Deserializer<'de>,
+		{
+			deserializer.deserialize_seq(PersistentNoditMapVisitor {
+				i: PhantomData,
+				k: PhantomData,
+				v: PhantomData,
+			})
+		}
+	}
+
+	struct PersistentNoditMapVisitor<I, K, V> {
+		i: PhantomData<I>,
+		k: PhantomData<K>,
+		v: PhantomData<V>,
+	}
+
+	impl<'de, I, K, V> Visitor<'de> for PersistentNoditMapVisitor<I, K, V>
+	where
+		I: PointType,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
+		V: Clone + Deserialize<'de>,
+	{
+		type Value = PersistentNoditMap<I, K, V>;
+
+		fn expecting(
+			&self,
+			formatter: &mut alloc::fmt::Formatter,
+		) -> alloc::fmt::Result {
+			formatter.write_str("a PersistentNoditMap")
+		}
+
+		fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+		where
+			A: SeqAccess<'de>,
+		{
+			let mut map = PersistentNoditMap::new();
+			let mut index = 0;
+			while let Some((interval, value)) = access.next_element()? {
+				map.insert_strict(interval, value)
+					.map_err(|error| deserialize_overlap_error(index, error))?;
+				index += 1;
+			}
+			Ok(map)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate std;
+
+	use alloc::vec::Vec;
+	use std::format;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ie, ii, ui};
+
+	const NUMBERS: &[i8] = &[2, 4, 6, 8, 10];
+
+	fn basic() -> NoditMap<i8, Interval<i8>, bool> {
+		NoditMap::from_slice_strict([
+			(ui(4).into(), false),
+			(ie(5, 8).into(), true),
+			(ii(8, 8).into(), false),
+			(ie(14, 16).into(), true),
+		])
+		.unwrap()
+	}
+
+	fn all_valid_test_bounds() -> Vec<Interval<i8>> {
+		let mut output = Vec::new();
+		for i in NUMBERS {
+			for j in NUMBERS {
+				if i <= j {
+					output.push(Interval { start: *i, end: *j });
+				}
+			}
+		}
+		output
+	}
+
+	fn assert_same<V>(
+		persistent: &PersistentNoditMap<i8, Interval<i8>, V>,
+		map: &NoditMap<i8, Interval<i8>, V>,
+	) where
+		V: PartialEq + core::fmt::Debug,
+	{
+		assert_eq!(
+			persistent.iter().collect::<Vec<_>>(),
+			map.iter().collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn matches_nodit_map_tests() {
+		for interval in all_valid_test_bounds() {
+			let map = basic();
+			let persistent = PersistentNoditMap::from(basic());
+
+			assert_eq!(persistent.overlaps(interval), map.overlaps(interval));
+			assert_eq!(
+				persistent.overlapping(interval).collect::<Vec<_>>(),
+				map.overlapping(interval).collect::<Vec<_>>()
+			);
+			assert_eq!(
+				persistent.gaps_untrimmed(interval).collect::<Vec<_>>(),
+				map.gaps_untrimmed(interval).collect::<Vec<_>>()
+			);
+			assert_eq!(
+				persistent.gaps_trimmed(interval).collect::<Vec<_>>(),
+				map.gaps_trimmed(interval).collect::<Vec<_>>()
+			);
+			assert_eq!(
+				persistent.contains_interval(interval),
+				map.contains_interval(interval)
+			);
+
+			macro_rules! same_update {
+				($method:ident $(, $arg:expr)*) => {{
+					let mut map = basic();
+					let mut persistent = PersistentNoditMap::from(basic());
+					let snapshot = persistent.clone();
+
+					let expected = map.$method(interval $(, $arg)*);
+					let result = persistent.$method(interval $(, $arg)*);
+
+					assert_eq!(format!("{:?}", result), format!("{:?}", expected));
+					assert_same(&persistent, &map);
+					//clones must not see the update
+					assert_same(&snapshot, &basic());
+				}};
+			}
+
+			same_update!(insert_strict, true);
+			same_update!(insert_merge_touching, true);
+			same_update!(insert_merge_touching_if_values_equal, true);
+			same_update!(insert_merge_touching_if_values_equal, false);
+			same_update!(insert_merge_overlapping, true);
+			same_update!(insert_merge_touching_or_overlapping, true);
+		}
+
+		for interval in all_valid_test_bounds() {
+			let mut map = basic();
+			let mut persistent = PersistentNoditMap::from(basic());
+
+			assert_eq!(
+				persistent.cut(interval).collect::<Vec<_>>(),
+				map.cut(interval).collect::<Vec<_>>()
+			);
+			assert_same(&persistent, &map);
+
+			let mut map = basic();
+			let mut persistent = PersistentNoditMap::from(basic());
+
+			assert_eq!(
+				persistent
+					.insert_overwrite(interval, true)
+					.collect::<Vec<_>>(),
+				map.insert_overwrite(interval, true).collect::<Vec<_>>()
+			);
+			assert_same(&persistent, &map);
+
+			let mut map = basic();
+			let mut persistent = PersistentNoditMap::from(basic());
+
+			assert_eq!(
+				persistent.remove_overlapping(interval).collect::<Vec<_>>(),
+				map.remove_overlapping(interval).collect::<Vec<_>>()
+			);
+			assert_same(&persistent, &map);
+		}
+
+		for point in i8::MIN..=i8::MAX {
+			let map = basic();
+			let persistent = PersistentNoditMap::from(basic());
+
+			assert_eq!(
+				persistent.get_key_value_at_point(point),
+				map.get_key_value_at_point(point)
+			);
+		}
+	}
+}
@@ -5,8 +5,16 @@
 //! equivalent method's docs on [`NoditMap`] to prevent
 //! inconsistency.
 
-use crate::nodit::map::IntoIter as NoditMapIntoIter;
-use crate::{IntervalType, NoditMap, OverlapError, PointType};
+use core::fmt;
+use core::ops::{Add, Rem, Sub};
+use core::str::FromStr;
+
+use crate::nodit::map::{parse_key, IntoIter as NoditMapIntoIter};
+use crate::parse::{parse_entries, ParseError, ParseErrorKind};
+use crate::{
+	CollisionPolicy, Countable, IntervalType, IntoInterval, InvariantError,
+	NoditMap, NonMonotonicError, OverlapError, PointType, Relation,
+};
 
 /// An ordered set of non-overlapping intervals based on [`NoditMap`].
 ///
@@ -30,19 +38,19 @@ where
 	K: IntervalType<I>,
 {
 	/// See [`NoditMap::overlaps()`] for more details.
-	pub fn overlaps<Q>(&self, interval: Q) -> bool
+	pub fn overlaps<Q, M>(&self, interval: Q) -> bool
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
 		self.inner.overlaps(interval)
 	}
 	/// See [`NoditMap::overlapping()`] for more details.
-	pub fn overlapping<Q>(
+	pub fn overlapping<Q, M>(
 		&self,
 		interval: Q,
 	) -> impl DoubleEndedIterator<Item = &K>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
 		self.inner.overlapping(interval).map(first)
 	}
@@ -55,46 +63,46 @@ where
 		self.inner.contains_point(point)
 	}
 	/// See [`NoditMap::remove_overlapping()`] for more details.
-	pub fn remove_overlapping<'a, Q>(
+	pub fn remove_overlapping<'a, Q, M>(
 		&'a mut self,
 		interval: Q,
 	) -> impl Iterator<Item = K>
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
 		self.inner.remove_overlapping(interval).map(first)
 	}
 	/// See [`NoditMap::cut()`] for more details.
-	pub fn cut<'a, Q>(&'a mut self, interval: Q) -> impl Iterator<Item = K>
+	pub fn cut<'a, Q, M>(&'a mut self, interval: Q) -> impl Iterator<Item = K>
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
 		self.inner.cut(interval).map(first)
 	}
 	/// See [`NoditMap::gaps_untrimmed()`] for more details.
-	pub fn gaps_untrimmed<'a, Q>(
+	pub fn gaps_untrimmed<'a, Q, M>(
 		&'a self,
 		interval: Q,
-	) -> impl Iterator<Item = K> + '_
+	) -> impl Iterator<Item = K> + 'a
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
 		self.inner.gaps_untrimmed(interval)
 	}
 	/// See [`NoditMap::gaps_trimmed()`] for more details.
-	pub fn gaps_trimmed<'a, Q>(
+	pub fn gaps_trimmed<'a, Q, M>(
 		&'a self,
 		interval: Q,
-	) -> impl Iterator<Item = K> + '_
+	) -> impl Iterator<Item = K> + 'a
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 	{
 		self.inner.gaps_trimmed(interval)
 	}
 	/// See [`NoditMap::contains_interval()`] for more details.
-	pub fn contains_interval<Q>(&self, interval: Q) -> bool
+	pub fn contains_interval<Q, M>(&self, interval: Q) -> bool
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
 		self.inner.contains_interval(interval)
 	}
@@ -102,14 +110,17 @@ where
 	pub fn insert_strict(
 		&mut self,
 		interval: K,
-	) -> Result<(), OverlapError<()>> {
+	) -> Result<(), OverlapError<K, ()>> {
 		self.inner.insert_strict(interval, ())
 	}
+	pub(crate) fn insert_unchecked(&mut self, interval: K) {
+		self.inner.insert_unchecked(interval, ());
+	}
 	/// See [`NoditMap::insert_merge_touching()`] for more details.
 	pub fn insert_merge_touching(
 		&mut self,
 		interval: K,
-	) -> Result<K, OverlapError<()>> {
+	) -> Result<K, OverlapError<K, ()>> {
 		self.inner.insert_merge_touching(interval, ())
 	}
 	/// See [`NoditMap::insert_merge_overlapping()`] for more details.
@@ -128,7 +139,7 @@ where
 	/// See [`NoditMap::from_slice_strict()`] for more details.
 	pub fn from_slice_strict<const N: usize>(
 		slice: [K; N],
-	) -> Result<NoditSet<I, K>, OverlapError<()>> {
+	) -> Result<NoditSet<I, K>, OverlapError<K, ()>> {
 		let mut set = NoditSet::new();
 		for interval in slice {
 			set.insert_strict(interval)?;
@@ -138,13 +149,100 @@ where
 	/// See [`NoditMap::from_iter_strict()`] for more details.
 	pub fn from_iter_strict(
 		iter: impl Iterator<Item = K>,
-	) -> Result<NoditSet<I, K>, OverlapError<()>> {
+	) -> Result<NoditSet<I, K>, OverlapError<K, ()>> {
 		let mut set = NoditSet::new();
 		for interval in iter {
 			set.insert_strict(interval)?;
 		}
 		return Ok(set);
 	}
+	/// Consumes the set and returns a [`NoditMap`] with the same intervals,
+	/// each associated with a clone of the given value.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::NoditSet;
+	///
+	/// let set = NoditSet::from_slice_strict([ie(1, 4), ie(6, 8)]).unwrap();
+	///
+	/// assert_eq!(
+	/// 	set.with_value(true).into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), true), (ie(6, 8), true)]
+	/// );
+	/// ```
+	pub fn with_value<V>(self, value: V) -> NoditMap<I, K, V>
+	where
+		V: Clone,
+	{
+		self.inner.map_values(|()| value.clone())
+	}
+	/// See [`NoditMap::map_points()`] for more details.
+	pub fn map_points<J, K2, F>(
+		self,
+		f: F,
+	) -> Result<NoditSet<J, K2>, NonMonotonicError<I>>
+	where
+		J: PointType,
+		K2: IntervalType<J>,
+		F: FnMut(I) -> J,
+	{
+		self.inner
+			.map_points(f)
+			.map(|inner| NoditSet { inner })
+	}
+	/// See [`NoditMap::quantize()`] for more details.
+	pub fn quantize(
+		self,
+		bucket: I,
+		policy: CollisionPolicy<()>,
+	) -> Result<NoditSet<I, K>, OverlapError<K, ()>>
+	where
+		I: Add<Output = I> + Sub<Output = I> + Rem<Output = I>,
+	{
+		self.inner
+			.quantize(bucket, policy)
+			.map(|inner| NoditSet { inner })
+	}
+	/// See [`NoditMap::is_subset()`] for more details.
+	pub fn is_subset<K2>(&self, other: &NoditSet<I, K2>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.inner.is_subset(&other.inner)
+	}
+	/// See [`NoditMap::is_superset()`] for more details.
+	pub fn is_superset<K2>(&self, other: &NoditSet<I, K2>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.inner.is_superset(&other.inner)
+	}
+	/// See [`NoditMap::covers()`] for more details.
+	pub fn covers<K2>(&self, other: &NoditSet<I, K2>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.inner.covers(&other.inner)
+	}
+	/// See [`NoditMap::is_disjoint()`] for more details.
+	pub fn is_disjoint<K2>(&self, other: &NoditSet<I, K2>) -> bool
+	where
+		K2: IntervalType<I>,
+	{
+		self.inner.is_disjoint(&other.inner)
+	}
+	/// See [`NoditMap::check_invariants()`] for more details.
+	pub fn check_invariants(&self) -> Result<(), InvariantError<I>> {
+		self.inner.check_invariants()
+	}
+	/// See [`NoditMap::relation()`] for more details.
+	pub fn relation<K2>(&self, other: &NoditSet<I, K2>) -> Relation<I>
+	where
+		K2: IntervalType<I>,
+	{
+		self.inner.relation(&other.inner)
+	}
 }
 
 impl<I, K> NoditSet<I, K> {
@@ -166,6 +264,30 @@ impl<I, K> NoditSet<I, K> {
 	pub fn iter(&self) -> impl DoubleEndedIterator<Item = &K> {
 		self.inner.iter().map(first)
 	}
+	/// Returns an iterator over every point covered by the set in
+	/// ascending order.
+	///
+	/// See [`InclusiveInterval::points()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::{ie, ii};
+	/// use nodit::NoditSet;
+	///
+	/// let set = NoditSet::from_slice_strict([ie(1, 3), ii(6, 7)]).unwrap();
+	///
+	/// assert_eq!(set.points().collect::<Vec<_>>(), [1, 2, 6, 7]);
+	/// assert_eq!(set.points().rev().collect::<Vec<_>>(), [7, 6, 2, 1]);
+	/// ```
+	///
+	/// [`InclusiveInterval::points()`]: crate::InclusiveInterval::points
+	pub fn points(&self) -> impl DoubleEndedIterator<Item = I> + '_
+	where
+		I: PointType + Countable,
+		K: IntervalType<I>,
+	{
+		self.inner.inner.keys().flat_map(|key| key.points())
+	}
 	/// See [`NoditMap::first_key_value()`] for more details.
 	pub fn first(&self) -> Option<&K> {
 		self.inner.first_key_value().map(first)
@@ -174,6 +296,25 @@ impl<I, K> NoditSet<I, K> {
 	pub fn last(&self) -> Option<&K> {
 		self.inner.last_key_value().map(first)
 	}
+	/// Returns a reference to the underlying [`NoditMap`] of the set,
+	/// whose values are all `()`.
+	///
+	/// This is useful for comparing a set against the intervals of a map,
+	/// such as with [`NoditMap::is_subset()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ie;
+	/// use nodit::{NoditMap, NoditSet};
+	///
+	/// let set = NoditSet::from_slice_strict([ie(1, 8)]).unwrap();
+	/// let map = NoditMap::from_slice_strict([(ie(2, 4), 'a')]).unwrap();
+	///
+	/// assert_eq!(set.as_map().covers(&map), true);
+	/// ```
+	pub fn as_map(&self) -> &NoditMap<I, K, ()> {
+		&self.inner
+	}
 }
 
 // Helper Functions ==========================
@@ -222,14 +363,78 @@ where
 	}
 }
 
+/// Formats the set in mathematical interval notation such as
+/// `{[0, 5), [7, 9]}`.
+///
+/// ```
+/// use nodit::interval::{ie, ii};
+/// use nodit::NoditSet;
+///
+/// let set = NoditSet::from_slice_strict([ie(0, 5), ii(7, 9)]).unwrap();
+///
+/// assert_eq!(set.to_string(), "{[0, 5), [7, 9]}");
+/// ```
+impl<I, K> fmt::Display for NoditSet<I, K>
+where
+	K: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{{")?;
+		for (i, interval) in self.inner.inner.keys().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{interval}")?;
+		}
+		write!(f, "}}")
+	}
+}
+/// Parses a set in mathematical interval notation such as
+/// `{[0, 5), [7, 9]}`, inserting each interval with
+/// [`NoditSet::insert_strict()`].
+///
+/// See the [`FromStr`] implementation of [`NoditMap`] for more details.
+///
+/// ```
+/// use nodit::interval::{ie, ii};
+/// use nodit::NoditSet;
+///
+/// let set: NoditSet<u8, _> = "{[0, 5), [7, 9]}".parse().unwrap();
+///
+/// assert_eq!(set, NoditSet::from_slice_strict([ie(0, 5), ii(7, 9)]).unwrap());
+/// assert_eq!(set.to_string(), "{[0, 5), [7, 9]}");
+/// ```
+impl<I, K> FromStr for NoditSet<I, K>
+where
+	I: PointType,
+	K: IntervalType<I> + FromStr<Err = ParseError>,
+{
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut set = NoditSet::new();
+		for entry in parse_entries(s, false)? {
+			let interval = parse_key(entry.interval, entry.interval_offset)?;
+
+			set.insert_strict(interval).map_err(|_| ParseError {
+				offset: entry.interval_offset,
+				kind: ParseErrorKind::Overlap,
+			})?;
+		}
+		Ok(set)
+	}
+}
+
 #[cfg(feature = "serde")]
 mod serde {
+	use core::fmt;
 	use core::marker::PhantomData;
 
 	use serde::de::{SeqAccess, Visitor};
 	use serde::ser::SerializeSeq;
 	use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+	use crate::utils::deserialize_overlap_error;
 	use crate::{IntervalType, NoditSet, PointType};
 
 	impl<I, K> Serialize for NoditSet<I, K>
@@ -251,7 +456,7 @@ mod serde {
 	impl<'de, I, K> Deserialize<'de> for NoditSet<I, K>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 	{
 		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 		where
@@ -272,7 +477,7 @@ mod serde {
 	impl<'de, I, K> Visitor<'de> for NoditSetVisitor<I, K>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 	{
 		type Value = NoditSet<I, K>;
 
@@ -288,10 +493,11 @@ mod serde {
 			A: SeqAccess<'de>,
 		{
 			let mut set = NoditSet::new();
+			let mut index = 0;
 			while let Some(interval) = access.next_element()? {
-				set.insert_strict(interval).map_err(|_| {
-					serde::de::Error::custom("intervals overlap")
-				})?;
+				set.insert_strict(interval)
+					.map_err(|error| deserialize_overlap_error(index, error))?;
+				index += 1;
 			}
 			Ok(set)
 		}
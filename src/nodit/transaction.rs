@@ -0,0 +1,455 @@
+//! A module containing [`Transaction`] and [`History`].
+//!
+//! Since the methods of [`Transaction`] are just recording wrappers around
+//! the methods of [`NoditMap`], most of the methods' docs will point
+//! towards the equivalent method's docs on [`NoditMap`] to prevent
+//! inconsistency.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::utils::{expand, invalid_interval_panic, query_interval};
+use crate::{
+	Interval, IntervalType, IntoInterval, NoditMap, OverlapError, PointType,
+};
+
+/// A batch of edits to a [`NoditMap`] which is rolled back if it fails,
+/// created by [`NoditMap::transaction()`].
+///
+/// Every edit made through the transaction is applied to the map straight
+/// away, so later edits and [`Transaction::as_map()`] see the earlier
+/// ones, but the entries each edit replaces are also recorded so that
+/// they can be restored if the transaction fails.
+#[derive(Debug)]
+pub struct Transaction<'a, I, K, V> {
+	map: &'a mut NoditMap<I, K, V>,
+	log: Vec<Change<K, V>>,
+}
+
+/// A record of a single edit, the entries of the map overlapping
+/// `region` before and after the edit.
+///
+/// Every edit only modifies entries overlapping the interval it was given
+/// (extended by a point on either side for the touching merges), so the
+/// hull of that interval and those entries contains every entry the edit
+/// removed or inserted and no other entries.
+#[derive(Debug, Clone)]
+pub(crate) struct Change<K, V> {
+	region: K,
+	before: Vec<(K, V)>,
+	after: Vec<(K, V)>,
+}
+
+impl<'a, I, K, V> Transaction<'a, I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Clone,
+{
+	pub(crate) fn new(map: &'a mut NoditMap<I, K, V>) -> Self {
+		Transaction {
+			map,
+			log: Vec::new(),
+		}
+	}
+
+	/// Returns a reference to the map, with all the edits made so far in
+	/// the transaction applied.
+	pub fn as_map(&self) -> &NoditMap<I, K, V> {
+		self.map
+	}
+	/// See [`NoditMap::remove_overlapping()`] for more details.
+	pub fn remove_overlapping<Q, M>(
+		&mut self,
+		interval: Q,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
+
+		self.record(interval, |map| {
+			map.remove_overlapping(interval).collect::<Vec<_>>()
+		})
+		.into_iter()
+	}
+	/// See [`NoditMap::cut()`] for more details.
+	pub fn cut<Q, M>(&mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	where
+		Q: IntoInterval<I, M>,
+	{
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
+
+		self.record(interval, |map| map.cut(interval).collect::<Vec<_>>())
+			.into_iter()
+	}
+	/// See [`NoditMap::insert_strict()`] for more details.
+	pub fn insert_strict(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		self.record(interval, |map| map.insert_strict(interval, value))
+	}
+	/// See [`NoditMap::insert_merge_touching()`] for more details.
+	pub fn insert_merge_touching(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		self.record(expand(interval), |map| {
+			map.insert_merge_touching(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_merge_touching_if_values_equal()`] for more
+	/// details.
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Eq,
+	{
+		self.record(expand(interval), |map| {
+			map.insert_merge_touching_if_values_equal(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_merge_overlapping()`] for more details.
+	pub fn insert_merge_overlapping(&mut self, interval: K, value: V) -> K {
+		self.record(interval, |map| {
+			map.insert_merge_overlapping(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_merge_touching_or_overlapping()`] for more
+	/// details.
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> K {
+		self.record(expand(interval), |map| {
+			map.insert_merge_touching_or_overlapping(interval, value)
+		})
+	}
+	/// See [`NoditMap::insert_overwrite()`] for more details.
+	pub fn insert_overwrite(
+		&mut self,
+		interval: K,
+		value: V,
+	) -> impl Iterator<Item = (K, V)> {
+		self.record(interval, |map| {
+			map.insert_overwrite(interval, value).collect::<Vec<_>>()
+		})
+		.into_iter()
+	}
+
+	/// Applies `edit` to the map, which must only modify entries
+	/// overlapping `interval`, and records the change it made.
+	fn record<Q, R, F>(&mut self, interval: Q, edit: F) -> R
+	where
+		Q: IntervalType<I>,
+		F: FnOnce(&mut NoditMap<I, K, V>) -> R,
+	{
+		invalid_interval_panic(interval);
+
+		let before = self
+			.map
+			.overlapping(interval)
+			.map(|(key, value)| (*key, value.clone()))
+			.collect::<Vec<_>>();
+
+		let region = K::from(Interval {
+			start: before.first().map_or(interval.start(), |(first, _)| {
+				I::min(first.start(), interval.start())
+			}),
+			end: before.last().map_or(interval.end(), |(last, _)| {
+				I::max(last.end(), interval.end())
+			}),
+		});
+
+		let result = edit(self.map);
+
+		let after = self
+			.map
+			.overlapping(region)
+			.map(|(key, value)| (*key, value.clone()))
+			.collect::<Vec<_>>();
+
+		self.log.push(Change {
+			region,
+			before,
+			after,
+		});
+
+		result
+	}
+
+	/// Runs `f` as a transaction on `map`, returning the log of changes if
+	/// it succeeded and rolling them back if it failed.
+	pub(crate) fn run<T, E, F>(
+		map: &'a mut NoditMap<I, K, V>,
+		f: F,
+	) -> Result<(T, Vec<Change<K, V>>), E>
+	where
+		F: FnOnce(&mut Transaction<'a, I, K, V>) -> Result<T, E>,
+	{
+		let mut transaction = Transaction::new(map);
+
+		match f(&mut transaction) {
+			Ok(result) => Ok((result, transaction.log)),
+			Err(error) => {
+				for change in transaction.log.iter().rev() {
+					restore(transaction.map, change.region, &change.before);
+				}
+				Err(error)
+			}
+		}
+	}
+}
+
+/// A bounded history of the transactions applied to a [`NoditMap`] which
+/// can be undone and redone.
+///
+/// Transactions are recorded into the history by running them with
+/// [`NoditMap::transaction_with_history()`], and undone and redone with
+/// [`History::undo()`] and [`History::redo()`]. Once the history holds
+/// `capacity` transactions recording another one forgets the oldest.
+///
+/// A history should only be used with the map whose transactions it
+/// recorded. If that map is also modified outside of a recorded
+/// transaction then undoing or redoing a transaction restores the entries
+/// in the region of the map it modified to how they were, which will
+/// overwrite any other modifications in that region.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ie;
+/// use nodit::nodit::transaction::History;
+/// use nodit::{Interval, NoditMap, OverlapError};
+///
+/// let mut map =
+/// 	NoditMap::from_slice_strict([(ie(1, 4), 'a')]).unwrap();
+/// let mut history = History::new(10);
+///
+/// map.transaction_with_history(&mut history, |tx| {
+/// 	tx.insert_overwrite(ie(2, 6), 'b');
+/// 	Ok::<_, OverlapError<Interval<i32>, char>>(())
+/// })
+/// .unwrap();
+///
+/// assert_eq!(
+/// 	map.iter().collect::<Vec<_>>(),
+/// 	[(&ie(1, 2), &'a'), (&ie(2, 6), &'b')]
+/// );
+///
+/// assert_eq!(history.undo(&mut map), true);
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&ie(1, 4), &'a')]);
+///
+/// assert_eq!(history.redo(&mut map), true);
+/// assert_eq!(
+/// 	map.iter().collect::<Vec<_>>(),
+/// 	[(&ie(1, 2), &'a'), (&ie(2, 6), &'b')]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct History<I, K, V> {
+	undo: VecDeque<Vec<Change<K, V>>>,
+	redo: Vec<Vec<Change<K, V>>>,
+	capacity: usize,
+	phantom: PhantomData<I>,
+}
+
+impl<I, K, V> History<I, K, V>
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Clone,
+{
+	/// Undoes the most recently recorded transaction which hasn't already
+	/// been undone, returning `false` if there is no such transaction.
+	///
+	/// See [`History`] for more details.
+	pub fn undo(&mut self, map: &mut NoditMap<I, K, V>) -> bool {
+		match self.undo.pop_back() {
+			Some(changes) => {
+				for change in changes.iter().rev() {
+					restore(map, change.region, &change.before);
+				}
+				self.redo.push(changes);
+				true
+			}
+			None => false,
+		}
+	}
+	/// Redoes the most recently undone transaction, returning `false` if
+	/// there is no such transaction.
+	///
+	/// Recording a new transaction clears the transactions which can be
+	/// redone.
+	///
+	/// See [`History`] for more details.
+	pub fn redo(&mut self, map: &mut NoditMap<I, K, V>) -> bool {
+		match self.redo.pop() {
+			Some(changes) => {
+				for change in changes.iter() {
+					restore(map, change.region, &change.after);
+				}
+				self.undo.push_back(changes);
+				true
+			}
+			None => false,
+		}
+	}
+	pub(crate) fn push(&mut self, changes: Vec<Change<K, V>>) {
+		self.redo.clear();
+
+		if self.capacity == 0 {
+			return;
+		}
+		if self.undo.len() == self.capacity {
+			self.undo.pop_front();
+		}
+		self.undo.push_back(changes);
+	}
+}
+
+impl<I, K, V> History<I, K, V> {
+	/// Makes a new, empty `History` which remembers up to `capacity`
+	/// transactions.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::Interval;
+	/// use nodit::nodit::transaction::History;
+	///
+	/// let history: History<i8, Interval<i8>, bool> = History::new(10);
+	/// ```
+	pub fn new(capacity: usize) -> Self {
+		History {
+			undo: VecDeque::new(),
+			redo: Vec::new(),
+			capacity,
+			phantom: PhantomData,
+		}
+	}
+	/// Returns `true` if there is a transaction which can be undone.
+	pub fn can_undo(&self) -> bool {
+		!self.undo.is_empty()
+	}
+	/// Returns `true` if there is a transaction which can be redone.
+	pub fn can_redo(&self) -> bool {
+		!self.redo.is_empty()
+	}
+	/// Forgets every recorded transaction.
+	pub fn clear(&mut self) {
+		self.undo.clear();
+		self.redo.clear();
+	}
+}
+
+// Helper Functions ==========================
+
+/// Replaces every entry in `map` overlapping `region` with `entries`.
+fn restore<I, K, V>(map: &mut NoditMap<I, K, V>, region: K, entries: &[(K, V)])
+where
+	I: PointType,
+	K: IntervalType<I>,
+	V: Clone,
+{
+	let _ = map.remove_overlapping(region);
+	for (interval, value) in entries.iter() {
+		map.insert_unchecked(*interval, value.clone());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::{ee, ie, ii, ui};
+
+	fn basic() -> NoditMap<i8, Interval<i8>, bool> {
+		NoditMap::from_slice_strict([
+			(ui(4).into(), false),
+			(ee(5, 7).into(), true),
+			(ii(7, 7).into(), false),
+			(ie(14, 16).into(), true),
+		])
+		.unwrap()
+	}
+
+	fn all_valid_test_bounds() -> Vec<Interval<i8>> {
+		let mut output = Vec::new();
+		for i in [2, 4, 6, 8, 10, 14, 16] {
+			for j in [2, 4, 6, 8, 10, 14, 16] {
+				if i <= j {
+					output.push(Interval { start: i, end: j });
+				}
+			}
+		}
+		output
+	}
+
+	/// Applies a batch of edits touching `interval` in every way.
+	fn edit(
+		tx: &mut Transaction<i8, Interval<i8>, bool>,
+		interval: Interval<i8>,
+	) {
+		let shifted = ii(interval.start() + 1, interval.end() + 3);
+
+		let _ = tx.insert_merge_touching(interval, true);
+		let _ = tx.insert_merge_touching_if_values_equal(interval, false);
+		tx.insert_merge_touching_or_overlapping(shifted.into(), false);
+		let _ = tx.cut(interval).count();
+		let _ = tx.insert_strict(interval, true);
+		tx.insert_merge_overlapping(shifted.into(), true);
+		let _ = tx.insert_overwrite(interval, false).count();
+		let _ = tx.remove_overlapping(shifted).count();
+	}
+
+	#[test]
+	fn rollback_and_undo_tests() {
+		for interval in all_valid_test_bounds() {
+			let mut expected = basic();
+			let _ = Transaction::run(&mut expected, |tx| {
+				edit(tx, interval);
+				Ok::<_, ()>(())
+			});
+
+			let mut map = basic();
+			assert_eq!(
+				map.transaction(|tx| {
+					edit(tx, interval);
+					Err::<(), _>(())
+				}),
+				Err(())
+			);
+			assert_eq!(map, basic());
+
+			let mut history = History::new(1);
+			map.transaction_with_history(&mut history, |tx| {
+				edit(tx, interval);
+				Ok::<_, ()>(())
+			})
+			.unwrap();
+			assert_eq!(map, expected);
+
+			assert!(history.undo(&mut map));
+			assert!(!history.undo(&mut map));
+			assert_eq!(map, basic());
+
+			assert!(history.redo(&mut map));
+			assert!(!history.redo(&mut map));
+			assert_eq!(map, expected);
+		}
+	}
+}
@@ -0,0 +1,248 @@
+//! A module containing the parsing shared by the [`FromStr`]
+//! implementations for intervals, maps and sets which use mathematical
+//! interval notation such as `[3, 7)`, `(-∞, 5]` and `{[0, 5) => a}`.
+//!
+//! [`FromStr`]: core::str::FromStr
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Bound;
+use core::str::FromStr;
+
+use crate::ip::CidrError;
+
+/// The error returned when parsing an interval, map or set from a string
+/// in mathematical interval notation fails.
+///
+/// # Examples
+/// ```
+/// use nodit::{Interval, ParseError, ParseErrorKind};
+///
+/// assert_eq!(
+/// 	"[3, x)".parse::<Interval<u8>>(),
+/// 	Err(ParseError {
+/// 		offset: 4,
+/// 		kind: ParseErrorKind::InvalidPoint
+/// 	})
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+	/// The byte offset into the input at which the error was found.
+	pub offset: usize,
+	/// The kind of error.
+	pub kind: ParseErrorKind,
+}
+
+/// The different kinds of [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+	/// The given token was expected but not found.
+	Expected(&'static str),
+	/// A point couldn't be parsed by its [`FromStr`] implementation.
+	InvalidPoint,
+	/// A value couldn't be parsed by its [`FromStr`] implementation.
+	InvalidValue,
+	/// An interval doesn't contain any points.
+	EmptyInterval,
+	/// An interval overlaps another interval earlier in the input.
+	Overlap,
+	/// A [`Cidr`] block couldn't be constructed from its parts.
+	///
+	/// [`Cidr`]: crate::ip::Cidr
+	InvalidCidr(CidrError),
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.kind {
+			ParseErrorKind::Expected(token) => write!(f, "expected {token}"),
+			ParseErrorKind::InvalidPoint => write!(f, "invalid point"),
+			ParseErrorKind::InvalidValue => write!(f, "invalid value"),
+			ParseErrorKind::EmptyInterval => write!(f, "empty interval"),
+			ParseErrorKind::Overlap => write!(f, "overlapping interval"),
+			ParseErrorKind::InvalidCidr(error) => write!(f, "{error}"),
+		}?;
+		write!(f, " at byte {}", self.offset)
+	}
+}
+impl core::error::Error for ParseError {}
+
+impl ParseError {
+	/// Moves the error along by `offset` bytes, for when the input that
+	/// was parsed was part of a larger input.
+	pub(crate) fn shift(self, offset: usize) -> Self {
+		ParseError {
+			offset: self.offset + offset,
+			kind: self.kind,
+		}
+	}
+}
+
+fn error(offset: usize, kind: ParseErrorKind) -> ParseError {
+	ParseError { offset, kind }
+}
+
+/// Returns the offset of the first non-whitespace character at or after
+/// `offset`.
+fn skip_whitespace(input: &str, offset: usize) -> usize {
+	offset + (input[offset..].len() - input[offset..].trim_start().len())
+}
+
+/// Parses the bounds of an interval such as `[3, 7)` or `(-∞, 5]`.
+pub(crate) fn parse_bounds<I>(
+	input: &str,
+) -> Result<(Bound<I>, Bound<I>), ParseError>
+where
+	I: FromStr,
+{
+	let start = skip_whitespace(input, 0);
+	let end = input.trim_end().len();
+
+	let start_included = match input[start..].chars().next() {
+		Some('[') => true,
+		Some('(') => false,
+		_ => return Err(error(start, ParseErrorKind::Expected("`[` or `(`"))),
+	};
+	let end_included = match input[..end].chars().next_back() {
+		Some(']') if end > start + 1 => true,
+		Some(')') if end > start + 1 => false,
+		_ => return Err(error(end, ParseErrorKind::Expected("`]` or `)`"))),
+	};
+
+	//both brackets are single bytes
+	let inner = start + 1..end - 1;
+	let comma = input[inner.clone()]
+		.find(',')
+		.map(|comma| inner.start + comma)
+		.ok_or(error(inner.end, ParseErrorKind::Expected("`,`")))?;
+
+	let start_bound = parse_bound(
+		input,
+		inner.start..comma,
+		start_included,
+		&["-∞", "-inf"],
+	)?;
+	let end_bound = parse_bound(
+		input,
+		comma + 1..inner.end,
+		end_included,
+		&["∞", "+∞", "inf", "+inf"],
+	)?;
+
+	Ok((start_bound, end_bound))
+}
+
+fn parse_bound<I>(
+	input: &str,
+	range: core::ops::Range<usize>,
+	included: bool,
+	infinities: &[&str],
+) -> Result<Bound<I>, ParseError>
+where
+	I: FromStr,
+{
+	let start = skip_whitespace(input, range.start);
+	let point = input[start..range.end].trim_end();
+
+	if infinities.contains(&point) {
+		return Ok(Bound::Unbounded);
+	}
+
+	let point = point
+		.parse()
+		.map_err(|_| error(start, ParseErrorKind::InvalidPoint))?;
+
+	Ok(if included {
+		Bound::Included(point)
+	} else {
+		Bound::Excluded(point)
+	})
+}
+
+/// An entry in a map or set, as the offsets and text of its interval and
+/// value.
+pub(crate) struct Entry<'a> {
+	pub(crate) interval_offset: usize,
+	pub(crate) interval: &'a str,
+	pub(crate) value_offset: usize,
+	pub(crate) value: &'a str,
+}
+
+/// Splits a map such as `{[0, 5) => a, [7, 9] => b}`, or a set such as
+/// `{[0, 5), [7, 9]}` if `with_values` is `false`, into its entries.
+///
+/// A value extends up to the next comma which is followed by the start of
+/// an interval, so values can't contain such a comma.
+pub(crate) fn parse_entries(
+	input: &str,
+	with_values: bool,
+) -> Result<Vec<Entry<'_>>, ParseError> {
+	let start = skip_whitespace(input, 0);
+	let end = input.trim_end().len();
+
+	if !input[start..].starts_with('{') {
+		return Err(error(start, ParseErrorKind::Expected("`{`")));
+	}
+	if end <= start + 1 || !input[..end].ends_with('}') {
+		return Err(error(end, ParseErrorKind::Expected("`}`")));
+	}
+	//both braces are single bytes
+	let inner_end = end - 1;
+
+	let mut entries = Vec::new();
+	let mut offset = skip_whitespace(input, start + 1);
+	while offset < inner_end {
+		if !input[offset..].starts_with(['[', '(']) {
+			return Err(error(offset, ParseErrorKind::Expected("`[` or `(`")));
+		}
+		let interval_end = input[offset..inner_end]
+			.find([']', ')'])
+			.map(|close| offset + close + 1)
+			.ok_or(error(inner_end, ParseErrorKind::Expected("`]` or `)`")))?;
+
+		let mut entry = Entry {
+			interval_offset: offset,
+			interval: &input[offset..interval_end],
+			value_offset: interval_end,
+			value: "",
+		};
+		offset = skip_whitespace(input, interval_end);
+
+		if with_values {
+			if !input[offset..].starts_with("=>") {
+				return Err(error(offset, ParseErrorKind::Expected("`=>`")));
+			}
+			let value_start = skip_whitespace(input, offset + 2);
+			let value_end = next_entry_comma(input, value_start, inner_end)
+				.unwrap_or(inner_end);
+
+			entry.value_offset = value_start;
+			entry.value = input[value_start..value_end].trim_end();
+			offset = value_end;
+		}
+
+		entries.push(entry);
+
+		if offset < inner_end {
+			if !input[offset..].starts_with(',') {
+				return Err(error(offset, ParseErrorKind::Expected("`,`")));
+			}
+			offset = skip_whitespace(input, offset + 1);
+		}
+	}
+
+	Ok(entries)
+}
+
+/// Returns the offset of the next comma before `end` which is followed by
+/// the start of an interval.
+fn next_entry_comma(input: &str, start: usize, end: usize) -> Option<usize> {
+	input[start..end]
+		.match_indices(',')
+		.map(|(comma, _)| start + comma)
+		.find(|comma| {
+			input[skip_whitespace(input, comma + 1)..end]
+				.starts_with(['[', '('])
+		})
+}
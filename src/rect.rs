@@ -0,0 +1,685 @@
+//! A module containing [`Rect`], an axis-aligned discrete rectangle made of
+//! one [`Interval`] per axis, and [`RectMap`], the two dimensional
+//! counterpart of [`NoditMap`] for rectangles which must not overlap.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{InclusiveInterval, Interval, NoditMap, OverlapError, PointType};
+
+/// An axis-aligned rectangle of points, made of an [`Interval`] on each
+/// axis.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::Rect;
+///
+/// let room = Rect::new(ii(0, 9), ii(0, 4));
+///
+/// assert_eq!(room.contains_point((9, 4)), true);
+/// assert_eq!(room.contains_point((10, 4)), false);
+/// assert_eq!(
+/// 	room.intersection(&Rect::new(ii(5, 20), ii(2, 20))),
+/// 	Some(Rect::new(ii(5, 9), ii(2, 4)))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect<X, Y = X> {
+	/// The interval covered on the x-axis.
+	pub x: Interval<X>,
+	/// The interval covered on the y-axis.
+	pub y: Interval<Y>,
+}
+
+impl<X, Y> Rect<X, Y> {
+	/// Makes a new rectangle from its interval on each axis.
+	pub fn new<A, B>(x: A, y: B) -> Self
+	where
+		A: Into<Interval<X>>,
+		B: Into<Interval<Y>>,
+	{
+		Rect {
+			x: x.into(),
+			y: y.into(),
+		}
+	}
+}
+
+impl<X, Y> Rect<X, Y>
+where
+	X: PointType,
+	Y: PointType,
+{
+	/// The lowest point of the rectangle on both axes.
+	fn corner(&self) -> (X, Y) {
+		(self.x.start(), self.y.start())
+	}
+
+	/// Returns `true` if the given point is inside the rectangle.
+	pub fn contains_point(&self, (x, y): (X, Y)) -> bool {
+		self.x.contains_point(x) && self.y.contains_point(y)
+	}
+
+	/// Returns `true` if the two rectangles have at least one point in
+	/// common.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::Rect;
+	///
+	/// let rect = Rect::new(ii(0, 4), ii(0, 4));
+	///
+	/// assert_eq!(rect.overlaps(&Rect::new(ii(4, 8), ii(4, 8))), true);
+	/// assert_eq!(rect.overlaps(&Rect::new(ii(5, 8), ii(0, 4))), false);
+	/// //overlapping on one axis isn't enough
+	/// assert_eq!(rect.overlaps(&Rect::new(ii(0, 4), ii(5, 8))), false);
+	/// ```
+	pub fn overlaps(&self, other: &Self) -> bool {
+		self.x.overlaps(&other.x) && self.y.overlaps(&other.y)
+	}
+
+	/// Returns the rectangle of points in both `self` and `other`, if they
+	/// overlap.
+	pub fn intersection(&self, other: &Self) -> Option<Self> {
+		Some(Rect {
+			x: self.x.intersection(&other.x)?,
+			y: self.y.intersection(&other.y)?,
+		})
+	}
+
+	/// Returns the parts of `self` which aren't in `other`, as at most four
+	/// non-overlapping rectangles.
+	///
+	/// The parts before and after `other` on the x-axis keep the full
+	/// height of `self`, and the parts below and above `other` on the
+	/// y-axis fill the column in between.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::Rect;
+	///
+	/// let rect = Rect::new(ii(0, 9), ii(0, 9));
+	///
+	/// assert_eq!(
+	/// 	rect.difference(&Rect::new(ii(3, 5), ii(3, 5)))
+	/// 		.collect::<Vec<_>>(),
+	/// 	[
+	/// 		Rect::new(ii(0, 2), ii(0, 9)),
+	/// 		Rect::new(ii(6, 9), ii(0, 9)),
+	/// 		Rect::new(ii(3, 5), ii(0, 2)),
+	/// 		Rect::new(ii(3, 5), ii(6, 9)),
+	/// 	]
+	/// );
+	/// assert_eq!(
+	/// 	rect.difference(&Rect::new(ii(5, 20), ii(0, 20)))
+	/// 		.collect::<Vec<_>>(),
+	/// 	[Rect::new(ii(0, 4), ii(0, 9))]
+	/// );
+	/// ```
+	pub fn difference(&self, other: &Self) -> impl Iterator<Item = Self> {
+		let pieces = match self.intersection(other) {
+			None => [Some(*self), None, None, None],
+			Some(middle) => {
+				let (left, right) = self.x.difference(&other.x);
+				let (below, above) = self.y.difference(&other.y);
+				[
+					left.map(|x| Rect { x, y: self.y }),
+					right.map(|x| Rect { x, y: self.y }),
+					below.map(|y| Rect { x: middle.x, y }),
+					above.map(|y| Rect { x: middle.x, y }),
+				]
+			}
+		};
+
+		pieces.into_iter().flatten()
+	}
+}
+
+impl<X, Y> From<(Interval<X>, Interval<Y>)> for Rect<X, Y> {
+	fn from((x, y): (Interval<X>, Interval<Y>)) -> Self {
+		Rect { x, y }
+	}
+}
+/// Formats the rectangle as the product of its intervals, such as
+/// `[0, 4] × [5, 9]`.
+///
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::Rect;
+///
+/// assert_eq!(Rect::new(ii(0, 4), ii(5, 9)).to_string(), "[0, 4] × [5, 9]");
+/// ```
+impl<X, Y> fmt::Display for Rect<X, Y>
+where
+	X: fmt::Display,
+	Y: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} × {}", self.x, self.y)
+	}
+}
+
+/// An ordered map of non-overlapping [`Rect`]s to values, such as the
+/// rooms of a floor-plan or the regions of a texture atlas.
+///
+/// This is the two dimensional counterpart of [`NoditMap`] and works the
+/// same way: rectangles are inserted with [`RectMap::insert_strict()`],
+/// which refuses any overlap, and removing the part of the map inside a
+/// rectangle with [`RectMap::cut()`] splits the rectangles it overlaps
+/// into at most four remainders each.
+///
+/// The map keeps the x-axis split into slabs which are each crossed by the
+/// same rectangles, with a [`NoditMap`] of the y-intervals of those
+/// rectangles in every slab, so finding the rectangle at a point takes two
+/// `O(log(N))` lookups.
+///
+/// # Examples
+/// ```
+/// use nodit::interval::ii;
+/// use nodit::{Rect, RectMap};
+///
+/// let mut floor = RectMap::new();
+///
+/// floor
+/// 	.insert_strict(Rect::new(ii(0, 9), ii(0, 4)), "kitchen")
+/// 	.unwrap();
+/// floor
+/// 	.insert_strict(Rect::new(ii(0, 4), ii(5, 9)), "bedroom")
+/// 	.unwrap();
+///
+/// assert_eq!(floor.get_at_point((3, 7)), Some(&"bedroom"));
+/// assert_eq!(floor.get_at_point((7, 7)), None);
+/// assert!(floor
+/// 	.insert_strict(Rect::new(ii(4, 6), ii(4, 6)), "hallway")
+/// 	.is_err());
+///
+/// assert_eq!(
+/// 	floor.gaps(Rect::new(ii(0, 9), ii(0, 9))).collect::<Vec<_>>(),
+/// 	[Rect::new(ii(5, 9), ii(5, 9))]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectMap<X, Y, V> {
+	//every rectangle keyed by its lowest corner, which is unique since the
+	//rectangles don't overlap
+	rects: BTreeMap<(X, Y), (Rect<X, Y>, V)>,
+	//the x-axis split into slabs crossed by the same rectangles, each
+	//mapping the y-intervals of those rectangles to their corners
+	slabs: NoditMap<X, Interval<X>, Column<X, Y>>,
+}
+
+/// The y-intervals of the rectangles crossing a slab of a [`RectMap`],
+/// mapped to the lowest corners of those rectangles.
+type Column<X, Y> = NoditMap<Y, Interval<Y>, (X, Y)>;
+
+impl<X, Y, V> RectMap<X, Y, V> {
+	/// Makes a new, empty `RectMap`.
+	pub fn new() -> Self {
+		RectMap {
+			rects: BTreeMap::new(),
+			slabs: NoditMap::new(),
+		}
+	}
+
+	/// Returns the number of rectangles in the map.
+	pub fn len(&self) -> usize {
+		self.rects.len()
+	}
+
+	/// Returns `true` if the map contains no rectangles.
+	pub fn is_empty(&self) -> bool {
+		self.rects.is_empty()
+	}
+
+	/// Returns an iterator over every rectangle in the map with its value,
+	/// in ascending order of their lowest corners, by x and then y.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&Rect<X, Y>, &V)> {
+		self.rects.values().map(|(rect, value)| (rect, value))
+	}
+}
+
+impl<X, Y, V> RectMap<X, Y, V>
+where
+	X: PointType,
+	Y: PointType,
+{
+	/// Returns `true` if the given rectangle overlaps any of the
+	/// rectangles in the map, and `false` if it is entirely free.
+	pub fn overlaps(&self, rect: Rect<X, Y>) -> bool {
+		self.slabs
+			.overlapping(rect.x)
+			.any(|(_, column)| column.overlaps(rect.y))
+	}
+
+	/// Returns an iterator over every rectangle in the map which overlaps
+	/// the given rectangle, with its value, in the same order as
+	/// [`RectMap::iter()`].
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{Rect, RectMap};
+	///
+	/// let map = RectMap::from_slice_strict([
+	/// 	(Rect::new(ii(0, 4), ii(0, 4)), 'a'),
+	/// 	(Rect::new(ii(5, 9), ii(0, 4)), 'b'),
+	/// 	(Rect::new(ii(0, 9), ii(5, 9)), 'c'),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.overlapping(Rect::new(ii(4, 5), ii(4, 4)))
+	/// 		.map(|(_, value)| *value)
+	/// 		.collect::<Vec<_>>(),
+	/// 	['a', 'b']
+	/// );
+	/// ```
+	pub fn overlapping(
+		&self,
+		rect: Rect<X, Y>,
+	) -> impl Iterator<Item = (&Rect<X, Y>, &V)> {
+		self.overlapping_corners(rect)
+			.into_iter()
+			.map(|corner| &self.rects[&corner])
+			.map(|(rect, value)| (rect, value))
+	}
+	fn overlapping_corners(&self, rect: Rect<X, Y>) -> BTreeSet<(X, Y)> {
+		self.slabs
+			.overlapping(rect.x)
+			.flat_map(|(_, column)| column.overlapping(rect.y))
+			.map(|(_, corner)| *corner)
+			.collect()
+	}
+
+	/// Returns a reference to the value of the rectangle containing the
+	/// given point, if there is one.
+	pub fn get_at_point(&self, point: (X, Y)) -> Option<&V> {
+		self.get_key_value_at_point(point).map(|(_, value)| value)
+	}
+
+	/// Returns the rectangle containing the given point and a reference to
+	/// its value, if there is one.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{Rect, RectMap};
+	///
+	/// let mut map = RectMap::new();
+	/// map.insert_strict(Rect::new(ii(2, 4), ii(-3, 3)), true).unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.get_key_value_at_point((2, 0)),
+	/// 	Some((&Rect::new(ii(2, 4), ii(-3, 3)), &true))
+	/// );
+	/// assert_eq!(map.get_key_value_at_point((2, 4)), None);
+	/// ```
+	pub fn get_key_value_at_point(
+		&self,
+		(x, y): (X, Y),
+	) -> Option<(&Rect<X, Y>, &V)> {
+		let corner = self.slabs.get_at_point(x)?.get_at_point(y)?;
+		let (rect, value) = &self.rects[corner];
+		Some((rect, value))
+	}
+
+	/// Inserts a rectangle and its value into the map, unless it overlaps
+	/// any rectangles already in the map, in which case an
+	/// [`OverlapError`] containing the first one found is returned instead.
+	///
+	/// Use [`RectMap::overlapping()`] if you need all of them.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{OverlapError, Rect, RectMap};
+	///
+	/// let mut map = RectMap::new();
+	///
+	/// assert_eq!(map.insert_strict(Rect::new(ii(0, 4), ii(0, 4)), 1), Ok(()));
+	/// assert_eq!(
+	/// 	map.insert_strict(Rect::new(ii(3, 8), ii(4, 8)), 2),
+	/// 	Err(OverlapError {
+	/// 		interval: Rect::new(ii(3, 8), ii(4, 8)),
+	/// 		conflict: Rect::new(ii(0, 4), ii(0, 4)),
+	/// 		value: 2,
+	/// 	})
+	/// );
+	/// assert_eq!(map.insert_strict(Rect::new(ii(5, 8), ii(0, 8)), 3), Ok(()));
+	/// ```
+	pub fn insert_strict(
+		&mut self,
+		rect: Rect<X, Y>,
+		value: V,
+	) -> Result<(), OverlapError<Rect<X, Y>, V>> {
+		let conflict = self
+			.slabs
+			.overlapping(rect.x)
+			.find_map(|(_, column)| column.overlapping(rect.y).next())
+			.map(|(_, corner)| self.rects[corner].0);
+		if let Some(conflict) = conflict {
+			return Err(OverlapError {
+				interval: rect,
+				conflict,
+				value,
+			});
+		}
+
+		self.insert_unchecked(rect, value);
+
+		Ok(())
+	}
+	fn insert_unchecked(&mut self, rect: Rect<X, Y>, value: V) {
+		let corner = rect.corner();
+
+		//every part of the x-axis covered by the rectangle, whether it is
+		//already in a slab or not, gets the rectangle added to its slab
+		let gaps = self.slabs.gaps_trimmed(rect.x).collect::<Vec<_>>();
+		let mut pieces = self.slabs.cut(rect.x).collect::<Vec<_>>();
+		pieces.extend(gaps.into_iter().map(|gap| (gap, NoditMap::new())));
+
+		for (slab, mut column) in pieces {
+			column.insert_unchecked(rect.y, corner);
+			let _ = self
+				.slabs
+				.insert_merge_touching_if_values_equal(slab, column);
+		}
+
+		self.rects.insert(corner, (rect, value));
+	}
+
+	/// Removes every rectangle in the map which overlaps the given
+	/// rectangle and returns them with their values, in the same order as
+	/// [`RectMap::iter()`].
+	pub fn remove_overlapping(
+		&mut self,
+		rect: Rect<X, Y>,
+	) -> impl Iterator<Item = (Rect<X, Y>, V)> {
+		let mut result = Vec::new();
+
+		for corner in self.overlapping_corners(rect) {
+			result.push(self.remove(corner));
+		}
+
+		result.into_iter()
+	}
+	fn remove(&mut self, corner: (X, Y)) -> (Rect<X, Y>, V) {
+		let (rect, value) = self.rects.remove(&corner).unwrap();
+
+		let pieces = self.slabs.cut(rect.x).collect::<Vec<_>>();
+		for (slab, mut column) in pieces {
+			let _ = column.remove_overlapping(rect.y);
+			if !column.is_empty() {
+				let _ = self
+					.slabs
+					.insert_merge_touching_if_values_equal(slab, column);
+			}
+		}
+
+		(rect, value)
+	}
+
+	/// Removes the part of the map inside the given rectangle and returns
+	/// the removed parts of each rectangle with their values, in the same
+	/// order as [`RectMap::iter()`] of the rectangles they came from.
+	///
+	/// Each rectangle which is only partly inside the given rectangle is
+	/// replaced by the at most four remainders from
+	/// [`Rect::difference()`], which each get a clone of its value.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{Rect, RectMap};
+	///
+	/// let mut map = RectMap::new();
+	/// map.insert_strict(Rect::new(ii(0, 9), ii(0, 9)), 'a').unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.cut(Rect::new(ii(3, 5), ii(3, 20))).collect::<Vec<_>>(),
+	/// 	[(Rect::new(ii(3, 5), ii(3, 9)), 'a')]
+	/// );
+	/// assert_eq!(
+	/// 	map.iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(&Rect::new(ii(0, 2), ii(0, 9)), &'a'),
+	/// 		(&Rect::new(ii(3, 5), ii(0, 2)), &'a'),
+	/// 		(&Rect::new(ii(6, 9), ii(0, 9)), &'a'),
+	/// 	]
+	/// );
+	/// ```
+	pub fn cut(
+		&mut self,
+		rect: Rect<X, Y>,
+	) -> impl Iterator<Item = (Rect<X, Y>, V)>
+	where
+		V: Clone,
+	{
+		let mut result = Vec::new();
+
+		for (removed, value) in self.remove_overlapping(rect) {
+			for remainder in removed.difference(&rect) {
+				self.insert_unchecked(remainder, value.clone());
+			}
+			//the rectangle was overlapping so the intersection exists
+			result.push((removed.intersection(&rect).unwrap(), value));
+		}
+
+		result.into_iter()
+	}
+
+	/// Returns the free space inside the given rectangle, which isn't
+	/// covered by any rectangles in the map, as non-overlapping
+	/// rectangles.
+	///
+	/// The free space is split into columns along the x-axis wherever the
+	/// rectangles crossing it change, and neighbouring columns with the
+	/// same free y-interval are merged. The rectangles are returned in
+	/// ascending order of their lowest corners, by x and then y.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::{Rect, RectMap};
+	///
+	/// let mut map = RectMap::new();
+	/// map.insert_strict(Rect::new(ii(2, 3), ii(2, 3)), ()).unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.gaps(Rect::new(ii(0, 5), ii(0, 5))).collect::<Vec<_>>(),
+	/// 	[
+	/// 		Rect::new(ii(0, 1), ii(0, 5)),
+	/// 		Rect::new(ii(2, 3), ii(0, 1)),
+	/// 		Rect::new(ii(2, 3), ii(4, 5)),
+	/// 		Rect::new(ii(4, 5), ii(0, 5)),
+	/// 	]
+	/// );
+	/// assert_eq!(map.gaps(Rect::new(ii(2, 3), ii(2, 3))).count(), 0);
+	/// ```
+	pub fn gaps(&self, rect: Rect<X, Y>) -> impl Iterator<Item = Rect<X, Y>> {
+		let mut columns = self
+			.slabs
+			.overlapping(rect.x)
+			.map(|(slab, column)| {
+				//the slab overlaps so the intersection exists
+				let x = slab.intersection(&rect.x).unwrap();
+				(x, column.gaps_trimmed(rect.y).collect::<Vec<_>>())
+			})
+			.chain(
+				self.slabs
+					.gaps_trimmed(rect.x)
+					.map(|x| (x, Vec::from([rect.y]))),
+			)
+			.collect::<Vec<_>>();
+		columns.sort_by_key(|(x, _)| x.start());
+
+		let mut result: Vec<Rect<X, Y>> = Vec::new();
+		//the index in result of the last gap with each y-interval, which
+		//can be extended if the next column has the same gap
+		let mut last_with_y: BTreeMap<(Y, Y), usize> = BTreeMap::new();
+		for (x, gaps) in columns {
+			for y in gaps {
+				let key = (y.start(), y.end());
+				match last_with_y.get(&key) {
+					Some(&index) if result[index].x.touches(&x) => {
+						result[index].x.end = x.end();
+					}
+					_ => {
+						last_with_y.insert(key, result.len());
+						result.push(Rect { x, y });
+					}
+				}
+			}
+		}
+		result.sort_by_key(|gap| gap.corner());
+
+		result.into_iter()
+	}
+
+	/// Allocates a `RectMap` and fills it with the rectangles and values
+	/// from the given array using [`RectMap::insert_strict()`], stopping at
+	/// the first [`OverlapError`].
+	pub fn from_slice_strict<const N: usize>(
+		slice: [(Rect<X, Y>, V); N],
+	) -> Result<Self, OverlapError<Rect<X, Y>, V>> {
+		Self::from_iter_strict(slice.into_iter())
+	}
+
+	/// Allocates a `RectMap` and fills it with the rectangles and values
+	/// from the given iterator using [`RectMap::insert_strict()`], stopping
+	/// at the first [`OverlapError`].
+	pub fn from_iter_strict(
+		iter: impl Iterator<Item = (Rect<X, Y>, V)>,
+	) -> Result<Self, OverlapError<Rect<X, Y>, V>> {
+		let mut map = RectMap::new();
+		for (rect, value) in iter {
+			map.insert_strict(rect, value)?;
+		}
+		Ok(map)
+	}
+}
+
+impl<X, Y, V> Default for RectMap<X, Y, V> {
+	fn default() -> Self {
+		RectMap::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::interval::ii;
+
+	/// Every point of a small grid with the rectangle covering it, checked
+	/// against the map by brute force.
+	fn check(map: &RectMap<i8, i8, u8>) {
+		let rects = map.iter().collect::<Vec<_>>();
+		for x in -2..12 {
+			for y in -2..12 {
+				let covering = rects
+					.iter()
+					.filter(|(rect, _)| rect.contains_point((x, y)))
+					.collect::<Vec<_>>();
+				assert!(covering.len() <= 1);
+				assert_eq!(
+					map.get_key_value_at_point((x, y)),
+					covering.first().copied().copied()
+				);
+			}
+		}
+
+		let everything = Rect::new(ii(-2, 11), ii(-2, 11));
+		let gaps = map.gaps(everything).collect::<Vec<_>>();
+		for x in -2..12 {
+			for y in -2..12 {
+				let in_gaps = gaps
+					.iter()
+					.filter(|gap| gap.contains_point((x, y)))
+					.count();
+				let in_map = usize::from(map.get_at_point((x, y)).is_some());
+				assert_eq!(in_gaps + in_map, 1);
+			}
+		}
+	}
+
+	#[test]
+	fn insert_strict_tests() {
+		let mut map = RectMap::new();
+		let rects = [
+			Rect::new(ii(0, 4), ii(0, 4)),
+			Rect::new(ii(5, 9), ii(0, 2)),
+			Rect::new(ii(5, 9), ii(3, 9)),
+			Rect::new(ii(0, 2), ii(5, 9)),
+		];
+		for (value, rect) in rects.into_iter().enumerate() {
+			map.insert_strict(rect, value as u8).unwrap();
+			check(&map);
+		}
+
+		assert_eq!(
+			map.insert_strict(Rect::new(ii(2, 5), ii(2, 5)), 9),
+			Err(OverlapError {
+				interval: Rect::new(ii(2, 5), ii(2, 5)),
+				conflict: rects[0],
+				value: 9,
+			})
+		);
+		assert_eq!(map.len(), 4);
+		assert_eq!(map.overlaps(Rect::new(ii(3, 4), ii(5, 9))), false);
+		map.insert_strict(Rect::new(ii(3, 4), ii(5, 9)), 9).unwrap();
+		check(&map);
+		assert_eq!(map.gaps(Rect::new(ii(0, 9), ii(0, 9))).count(), 0);
+	}
+
+	#[test]
+	fn cut_tests() {
+		let whole = Rect::new(ii(0, 9), ii(0, 9));
+		let cuts = [
+			Rect::new(ii(3, 5), ii(3, 5)),
+			Rect::new(ii(-5, 20), ii(4, 4)),
+			Rect::new(ii(0, 0), ii(0, 0)),
+			Rect::new(ii(9, 20), ii(-5, 20)),
+			Rect::new(ii(20, 30), ii(20, 30)),
+		];
+
+		let mut map = RectMap::new();
+		map.insert_strict(whole, 0).unwrap();
+		for cut in cuts {
+			let before = map.clone();
+			let removed = map.cut(cut).collect::<Vec<_>>();
+			check(&map);
+
+			for (rect, value) in removed.iter() {
+				assert!(cut.intersection(rect) == Some(*rect));
+				assert_eq!(*value, 0);
+			}
+			for x in -2..12 {
+				for y in -2..12 {
+					let expected = before
+						.get_at_point((x, y))
+						.filter(|_| !cut.contains_point((x, y)));
+					assert_eq!(map.get_at_point((x, y)), expected);
+					assert_eq!(
+						removed
+							.iter()
+							.any(|(rect, _)| rect.contains_point((x, y))),
+						before.get_at_point((x, y)).is_some()
+							&& cut.contains_point((x, y))
+					);
+				}
+			}
+		}
+
+		let len = map.len();
+		assert_eq!(map.remove_overlapping(whole).count(), len);
+		assert!(map.is_empty());
+		assert_eq!(map, RectMap::new());
+	}
+}
@@ -0,0 +1,990 @@
+//! A module containing naive reference implementations of the
+//! data-structures in this crate and [`arbitrary::Arbitrary`]
+//! implementations for them, for use in differential property testing.
+//!
+//! The reference implementations store their entries in a plain [`Vec`]
+//! and implement every method with linear scans, so they are slow but
+//! simple enough to be obviously correct. Running the same sequence of
+//! operations on a real data-structure and its reference implementation
+//! and comparing the results after each operation is an easy way to test
+//! wrappers around the data-structures in this crate.
+//!
+//! This module is only available with the `testing` feature enabled.
+//!
+//! # Examples
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use nodit::testing::ReferenceNoditMap;
+//! use nodit::{Interval, NoditMap};
+//!
+//! let mut u =
+//! 	Unstructured::new(&[4, 8, 1, 2, 6, 7, 3, 9, 0, 1, 5, 4]);
+//!
+//! let mut map: NoditMap<u8, Interval<u8>, bool> = NoditMap::new();
+//! let mut reference = ReferenceNoditMap::new();
+//!
+//! while !u.is_empty() {
+//! 	let interval = Interval::arbitrary(&mut u).unwrap();
+//! 	let value = bool::arbitrary(&mut u).unwrap();
+//!
+//! 	assert!(
+//! 		map.insert_overwrite(interval, value)
+//! 			.eq(reference.insert_overwrite(interval, value))
+//! 	);
+//! 	assert!(map.iter().eq(reference.iter()));
+//! 	assert_eq!(map.check_invariants(), Ok(()));
+//! }
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::gqdit::IdType;
+use crate::zosdit::map::NonZeroOverlapError;
+use crate::{
+	Gqdit, InclusiveInterval, Interval, IntervalType, NoditMap, NoditSet,
+	OverlapError, PointType, ZosditMap,
+};
+
+type Entries<I, V> = Vec<(Interval<I>, V)>;
+
+/// A naive reference implementation of [`NoditMap`] with
+/// [`Interval`] keys.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceNoditMap<I, V> {
+	entries: Entries<I, V>,
+}
+
+impl<I, V> ReferenceNoditMap<I, V>
+where
+	I: PointType,
+{
+	/// Makes a new, empty `ReferenceNoditMap`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// See [`NoditMap::len()`].
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// See [`NoditMap::is_empty()`].
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// See [`NoditMap::iter()`].
+	pub fn iter(&self) -> impl Iterator<Item = (&Interval<I>, &V)> {
+		self.entries
+			.iter()
+			.map(|(interval, value)| (interval, value))
+	}
+
+	/// See [`NoditMap::overlaps()`].
+	pub fn overlaps(&self, interval: Interval<I>) -> bool {
+		self.overlapping(interval).next().is_some()
+	}
+
+	/// See [`NoditMap::overlapping()`].
+	pub fn overlapping(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = (&Interval<I>, &V)> {
+		self.iter()
+			.filter(move |(inner, _)| inner.overlaps(&interval))
+	}
+
+	/// See [`NoditMap::get_at_point()`].
+	pub fn get_at_point(&self, point: I) -> Option<&V> {
+		self.iter()
+			.find(|(interval, _)| interval.contains_point(point))
+			.map(|(_, value)| value)
+	}
+
+	/// See [`NoditMap::contains_point()`].
+	pub fn contains_point(&self, point: I) -> bool {
+		self.get_at_point(point).is_some()
+	}
+
+	/// See [`NoditMap::remove_overlapping()`].
+	pub fn remove_overlapping(
+		&mut self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = (Interval<I>, V)> {
+		let (removed, kept): (Vec<_>, Vec<_>) = self
+			.entries
+			.drain(..)
+			.partition(|(inner, _)| inner.overlaps(&interval));
+		self.entries = kept;
+
+		removed.into_iter()
+	}
+
+	/// See [`NoditMap::cut()`].
+	pub fn cut(
+		&mut self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = (Interval<I>, V)>
+	where
+		V: Clone,
+	{
+		let (entries, result) = cut_entries(&self.entries, interval);
+		self.entries = entries;
+
+		result.into_iter()
+	}
+
+	/// See [`NoditMap::gaps_untrimmed()`].
+	pub fn gaps_untrimmed(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = Interval<I>> {
+		complement(self.entries.iter().map(|(inner, _)| *inner))
+			.into_iter()
+			.filter(move |gap| gap.overlaps(&interval))
+	}
+
+	/// See [`NoditMap::gaps_trimmed()`].
+	pub fn gaps_trimmed(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = Interval<I>> {
+		self.gaps_untrimmed(interval)
+			.map(move |gap| gap.intersection(&interval).unwrap())
+	}
+
+	/// See [`NoditMap::insert_strict()`].
+	pub fn insert_strict(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> core::result::Result<(), OverlapError<Interval<I>, V>> {
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		self.insert_sorted(interval, value);
+
+		Ok(())
+	}
+
+	/// See [`NoditMap::insert_merge_touching()`].
+	pub fn insert_merge_touching(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> core::result::Result<Interval<I>, OverlapError<Interval<I>, V>> {
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		Ok(self.insert_merge_where(interval, value, |inner, _| {
+			touches(*inner, interval)
+		}))
+	}
+
+	/// See [`NoditMap::insert_merge_touching_if_values_equal()`].
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> core::result::Result<Interval<I>, OverlapError<Interval<I>, V>>
+	where
+		V: Eq,
+	{
+		if self.overlaps(interval) {
+			return Err(self.overlap_error(interval, value));
+		}
+
+		let (touching, kept): (Vec<_>, Vec<_>) =
+			self.entries.drain(..).partition(|(inner, other_value)| {
+				touches(*inner, interval) && *other_value == value
+			});
+		self.entries = kept;
+
+		let merged = touching
+			.iter()
+			.fold(interval, |merged, (inner, _)| hull(merged, *inner));
+		self.insert_sorted(merged, value);
+
+		Ok(merged)
+	}
+
+	/// See [`NoditMap::insert_merge_overlapping()`].
+	pub fn insert_merge_overlapping(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> Interval<I> {
+		self.insert_merge_where(interval, value, |inner, _| {
+			inner.overlaps(&interval)
+		})
+	}
+
+	/// See [`NoditMap::insert_merge_touching_or_overlapping()`].
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> Interval<I> {
+		self.insert_merge_where(interval, value, |inner, _| {
+			inner.overlaps(&interval) || touches(*inner, interval)
+		})
+	}
+
+	/// See [`NoditMap::insert_overwrite()`].
+	pub fn insert_overwrite(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> impl Iterator<Item = (Interval<I>, V)>
+	where
+		V: Clone,
+	{
+		let cut = self.cut(interval);
+		self.insert_sorted(interval, value);
+
+		cut
+	}
+
+	fn insert_merge_where(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+		should_merge: impl Fn(&Interval<I>, &V) -> bool,
+	) -> Interval<I> {
+		let (merging, kept): (Vec<_>, Vec<_>) = self
+			.entries
+			.drain(..)
+			.partition(|(inner, value)| should_merge(inner, value));
+		self.entries = kept;
+
+		let merged = merging
+			.iter()
+			.fold(interval, |merged, (inner, _)| hull(merged, *inner));
+		self.insert_sorted(merged, value);
+
+		merged
+	}
+
+	fn insert_sorted(&mut self, interval: Interval<I>, value: V) {
+		self.entries.push((interval, value));
+		self.entries.sort_by_key(|(inner, _)| inner.start());
+	}
+
+	fn overlap_error(
+		&self,
+		interval: Interval<I>,
+		value: V,
+	) -> OverlapError<Interval<I>, V> {
+		OverlapError {
+			interval,
+			conflict: *self.overlapping(interval).next().unwrap().0,
+			value,
+		}
+	}
+}
+
+impl<I, V> Default for ReferenceNoditMap<I, V> {
+	fn default() -> Self {
+		ReferenceNoditMap {
+			entries: Vec::new(),
+		}
+	}
+}
+
+/// A naive reference implementation of [`ZosditMap`] with
+/// [`Interval`] keys.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceZosditMap<I, V> {
+	entries: Entries<I, V>,
+}
+
+impl<I, V> ReferenceZosditMap<I, V>
+where
+	I: PointType,
+{
+	/// Makes a new, empty `ReferenceZosditMap`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// See [`ZosditMap::len()`].
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// See [`ZosditMap::is_empty()`].
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// See [`ZosditMap::iter()`].
+	pub fn iter(&self) -> impl Iterator<Item = (&Interval<I>, &V)> {
+		self.entries
+			.iter()
+			.map(|(interval, value)| (interval, value))
+	}
+
+	/// See [`ZosditMap::overlapping()`].
+	pub fn overlapping(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = (&Interval<I>, &V)> {
+		self.iter()
+			.filter(move |(inner, _)| inner.overlaps(&interval))
+	}
+
+	/// See [`ZosditMap::get_last_value_at_point()`].
+	pub fn get_last_value_at_point(&self, point: I) -> Option<&V> {
+		self.iter()
+			.filter(|(interval, _)| interval.contains_point(point))
+			.map(|(_, value)| value)
+			.last()
+	}
+
+	/// See [`ZosditMap::remove_last_value_at_point()`].
+	pub fn remove_last_value_at_point(&mut self, point: I) -> Option<V> {
+		let position = self
+			.entries
+			.iter()
+			.rposition(|(interval, _)| interval.contains_point(point))?;
+
+		Some(self.entries.remove(position).1)
+	}
+
+	/// See [`ZosditMap::is_zero_overlap()`].
+	pub fn is_zero_overlap(&self, interval: Interval<I>) -> bool {
+		self.entries
+			.iter()
+			.all(|(inner, _)| zero_overlap(*inner, interval))
+	}
+
+	/// See [`ZosditMap::insert_strict_back()`].
+	pub fn insert_strict_back(
+		&mut self,
+		interval: Interval<I>,
+		value: V,
+	) -> core::result::Result<(), NonZeroOverlapError<Interval<I>, V>> {
+		if !self.is_zero_overlap(interval) {
+			let conflict = self
+				.iter()
+				.map(|(inner, _)| *inner)
+				.find(|inner| !zero_overlap(*inner, interval))
+				.unwrap();
+
+			return Err(NonZeroOverlapError {
+				interval,
+				conflict,
+				value,
+			});
+		}
+
+		self.entries.push((interval, value));
+		self.sort();
+
+		Ok(())
+	}
+
+	/// See [`ZosditMap::cut()`].
+	pub fn cut(
+		&mut self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = (Interval<I>, V)>
+	where
+		V: Clone,
+	{
+		let (entries, result) = cut_entries(&self.entries, interval);
+		self.entries = entries;
+		self.sort();
+
+		result.into_iter()
+	}
+
+	fn sort(&mut self) {
+		//the sort is stable which keeps the values of identical singular
+		//intervals in insertion order
+		self.entries
+			.sort_by_key(|(inner, _)| (inner.start(), inner.end()));
+	}
+}
+
+impl<I, V> Default for ReferenceZosditMap<I, V> {
+	fn default() -> Self {
+		ReferenceZosditMap {
+			entries: Vec::new(),
+		}
+	}
+}
+
+/// A naive reference implementation of [`Gqdit`] with [`Interval`]
+/// keys.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceGqdit<I, D> {
+	entries: Vec<(D, Interval<I>)>,
+}
+
+impl<I, D> ReferenceGqdit<I, D>
+where
+	I: PointType,
+	D: IdType,
+{
+	/// Makes a new, empty `ReferenceGqdit`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// See [`Gqdit::gaps_no_identifier()`].
+	pub fn gaps_no_identifier(
+		&self,
+		interval: Interval<I>,
+	) -> Vec<Interval<I>> {
+		gaps_overlapping(self.entries.iter().map(|(_, inner)| *inner), interval)
+	}
+
+	/// See [`Gqdit::gaps_with_identifier()`].
+	pub fn gaps_with_identifier(
+		&self,
+		identifier: D,
+		interval: Interval<I>,
+	) -> Vec<Interval<I>> {
+		gaps_overlapping(
+			self.entries
+				.iter()
+				.filter(|(other, _)| *other != identifier)
+				.map(|(_, inner)| *inner),
+			interval,
+		)
+	}
+
+	/// See [`Gqdit::cut_with_identifiers()`].
+	pub fn cut_with_identifiers(
+		&mut self,
+		identifiers: BTreeSet<D>,
+		interval: Interval<I>,
+	) {
+		let mut entries = Vec::new();
+		for (identifier, inner) in self.entries.drain(..) {
+			if identifiers.contains(&identifier) {
+				let (outside, _) = cut_entries(&[(inner, ())], interval);
+				entries.extend(
+					outside.into_iter().map(|(outer, _)| (identifier, outer)),
+				);
+			} else {
+				entries.push((identifier, inner));
+			}
+		}
+		self.entries = entries;
+	}
+
+	/// See [`Gqdit::cut_all_identifiers()`].
+	pub fn cut_all_identifiers(&mut self, interval: Interval<I>) {
+		let identifiers = self
+			.entries
+			.iter()
+			.map(|(identifier, _)| *identifier)
+			.collect();
+		self.cut_with_identifiers(identifiers, interval);
+	}
+
+	/// See [`Gqdit::insert()`].
+	pub fn insert(&mut self, identifiers: BTreeSet<D>, interval: Interval<I>) {
+		self.entries.extend(
+			identifiers
+				.into_iter()
+				.map(|identifier| (identifier, interval)),
+		);
+	}
+
+	/// See [`Gqdit::append()`].
+	pub fn append(&mut self, other: &mut Self) {
+		self.entries.append(&mut other.entries);
+	}
+
+	/// See [`Gqdit::identifiers_at_point()`].
+	pub fn identifiers_at_point(&self, point: I) -> BTreeSet<D> {
+		self.entries
+			.iter()
+			.filter(|(_, interval)| interval.contains_point(point))
+			.map(|(identifier, _)| *identifier)
+			.collect()
+	}
+}
+
+impl<I, D> Default for ReferenceGqdit<I, D> {
+	fn default() -> Self {
+		ReferenceGqdit {
+			entries: Vec::new(),
+		}
+	}
+}
+
+/// Cuts `interval` out of every entry, returning the remaining entries and
+/// the cut parts in order.
+fn cut_entries<I, V>(
+	entries: &[(Interval<I>, V)],
+	interval: Interval<I>,
+) -> (Entries<I, V>, Entries<I, V>)
+where
+	I: PointType,
+	V: Clone,
+{
+	let mut remaining = Vec::new();
+	let mut cut = Vec::new();
+
+	for (inner, value) in entries {
+		match inner.intersection(&interval) {
+			Some(inside) => {
+				if inner.start() < interval.start() {
+					remaining.push((
+						Interval {
+							start: inner.start(),
+							end: interval.start().down().unwrap(),
+						},
+						value.clone(),
+					));
+				}
+				if inner.end() > interval.end() {
+					remaining.push((
+						Interval {
+							start: interval.end().up().unwrap(),
+							end: inner.end(),
+						},
+						value.clone(),
+					));
+				}
+				cut.push((inside, value.clone()));
+			}
+			None => remaining.push((*inner, value.clone())),
+		}
+	}
+
+	(remaining, cut)
+}
+
+/// Returns the maximal gaps between the given possibly overlapping
+/// intervals which overlap `interval`.
+fn gaps_overlapping<I>(
+	intervals: impl Iterator<Item = Interval<I>>,
+	interval: Interval<I>,
+) -> Vec<Interval<I>>
+where
+	I: PointType,
+{
+	complement(intervals)
+		.into_iter()
+		.filter(|gap| gap.overlaps(&interval))
+		.collect()
+}
+
+/// Returns the maximal gaps between the given possibly overlapping
+/// intervals over the whole domain, in ascending order.
+fn complement<I>(
+	intervals: impl Iterator<Item = Interval<I>>,
+) -> Vec<Interval<I>>
+where
+	I: PointType,
+{
+	let mut intervals = intervals.collect::<Vec<_>>();
+	intervals.sort_by_key(|interval| interval.start());
+
+	let mut gaps = Vec::new();
+	//the first point not yet covered or known to be a gap, or `None` if
+	//every point has been
+	let mut next = Some(I::MIN);
+	for interval in intervals {
+		let Some(current) = next else {
+			break;
+		};
+
+		if interval.start() > current {
+			gaps.push(Interval {
+				start: current,
+				end: interval.start().down().unwrap(),
+			});
+		}
+		next = interval.end().up().map(|after| max(after, current));
+	}
+
+	if let Some(current) = next {
+		gaps.push(Interval {
+			start: current,
+			end: I::MAX,
+		});
+	}
+
+	gaps
+}
+
+fn touches<I>(a: Interval<I>, b: Interval<I>) -> bool
+where
+	I: PointType,
+{
+	a.end().up() == Some(b.start()) || b.end().up() == Some(a.start())
+}
+
+fn zero_overlap<I>(a: Interval<I>, b: Interval<I>) -> bool
+where
+	I: PointType,
+{
+	!a.overlaps(&b) || a.end() == b.start() || b.end() == a.start()
+}
+
+fn hull<I>(a: Interval<I>, b: Interval<I>) -> Interval<I>
+where
+	I: PointType,
+{
+	Interval {
+		start: min(a.start(), b.start()),
+		end: max(a.end(), b.end()),
+	}
+}
+
+impl<'a, I> Arbitrary<'a> for Interval<I>
+where
+	I: PointType + Arbitrary<'a>,
+{
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let a = I::arbitrary(u)?;
+		let b = I::arbitrary(u)?;
+
+		Ok(Interval {
+			start: min(a, b),
+			end: max(a, b),
+		})
+	}
+
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		<(I, I)>::size_hint(depth)
+	}
+}
+
+/// Inserts the arbitrary entries with [`NoditMap::insert_overwrite()`] so
+/// that later entries take priority.
+impl<'a, I, K, V> Arbitrary<'a> for NoditMap<I, K, V>
+where
+	I: PointType + Arbitrary<'a>,
+	K: IntervalType<I>,
+	V: Clone + Arbitrary<'a>,
+{
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut map = NoditMap::new();
+		for entry in u.arbitrary_iter::<(Interval<I>, V)>()? {
+			let (interval, value) = entry?;
+			let _ = map.insert_overwrite(K::from(interval), value);
+		}
+
+		Ok(map)
+	}
+}
+
+/// Inserts the arbitrary intervals with
+/// [`NoditSet::insert_merge_touching_or_overlapping()`].
+impl<'a, I, K> Arbitrary<'a> for NoditSet<I, K>
+where
+	I: PointType + Arbitrary<'a>,
+	K: IntervalType<I>,
+{
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut set = NoditSet::new();
+		for interval in u.arbitrary_iter::<Interval<I>>()? {
+			set.insert_merge_touching_or_overlapping(K::from(interval?));
+		}
+
+		Ok(set)
+	}
+}
+
+/// Inserts the arbitrary entries with [`ZosditMap::insert_strict_back()`],
+/// skipping any which don't zero-overlap the map.
+impl<'a, I, K, V> Arbitrary<'a> for ZosditMap<I, K, V>
+where
+	I: PointType + Arbitrary<'a>,
+	K: IntervalType<I>,
+	V: Arbitrary<'a>,
+{
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut map = ZosditMap::new();
+		for entry in u.arbitrary_iter::<(Interval<I>, V)>()? {
+			let (interval, value) = entry?;
+			let _ = map.insert_strict_back(K::from(interval), value);
+		}
+
+		Ok(map)
+	}
+}
+
+/// Inserts the arbitrary entries with [`Gqdit::insert()`].
+impl<'a, I, K, D> Arbitrary<'a> for Gqdit<I, K, D>
+where
+	I: PointType + Arbitrary<'a>,
+	K: IntervalType<I>,
+	D: IdType + Arbitrary<'a>,
+{
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut map = Gqdit::new();
+		for entry in u.arbitrary_iter::<(BTreeSet<D>, Interval<I>)>()? {
+			let (identifiers, interval) = entry?;
+			map.insert(identifiers, K::from(interval));
+		}
+
+		Ok(map)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	/// Generates some deterministic pseudo-random bytes for an
+	/// [`Unstructured`].
+	fn bytes(seed: u64) -> Vec<u8> {
+		let mut state = seed;
+		(0..512)
+			.map(|_| {
+				state = state
+					.wrapping_mul(6364136223846793005)
+					.wrapping_add(1442695040888963407);
+				(state >> 33) as u8
+			})
+			.collect()
+	}
+
+	#[test]
+	fn nodit_map_differential_tests() {
+		for seed in 0..200 {
+			let bytes = bytes(seed);
+			let mut u = Unstructured::new(&bytes);
+
+			let mut map: NoditMap<u8, Interval<u8>, u8> = NoditMap::new();
+			let mut reference = ReferenceNoditMap::new();
+
+			while !u.is_empty() {
+				let interval = Interval::arbitrary(&mut u).unwrap();
+				let value = u8::arbitrary(&mut u).unwrap() % 2;
+
+				match u8::arbitrary(&mut u).unwrap() % 9 {
+					0 => assert_eq!(
+						map.insert_strict(interval, value),
+						reference.insert_strict(interval, value)
+					),
+					1 => assert_eq!(
+						map.insert_merge_touching(interval, value),
+						reference.insert_merge_touching(interval, value)
+					),
+					2 => assert_eq!(
+						map.insert_merge_touching_if_values_equal(
+							interval, value
+						),
+						reference.insert_merge_touching_if_values_equal(
+							interval, value
+						)
+					),
+					3 => assert_eq!(
+						map.insert_merge_overlapping(interval, value),
+						reference.insert_merge_overlapping(interval, value)
+					),
+					4 => assert_eq!(
+						map.insert_merge_touching_or_overlapping(
+							interval, value
+						),
+						reference.insert_merge_touching_or_overlapping(
+							interval, value
+						)
+					),
+					5 => assert_eq!(
+						map.insert_overwrite(interval, value)
+							.collect::<Vec<_>>(),
+						reference
+							.insert_overwrite(interval, value)
+							.collect::<Vec<_>>()
+					),
+					6 => assert_eq!(
+						map.cut(interval).collect::<Vec<_>>(),
+						reference.cut(interval).collect::<Vec<_>>()
+					),
+					7 => assert_eq!(
+						map.remove_overlapping(interval).collect::<Vec<_>>(),
+						reference
+							.remove_overlapping(interval)
+							.collect::<Vec<_>>()
+					),
+					_ => {
+						assert_eq!(
+							map.gaps_untrimmed(interval).collect::<Vec<_>>(),
+							reference
+								.gaps_untrimmed(interval)
+								.collect::<Vec<_>>()
+						);
+						assert_eq!(
+							map.gaps_trimmed(interval).collect::<Vec<_>>(),
+							reference
+								.gaps_trimmed(interval)
+								.collect::<Vec<_>>()
+						);
+					}
+				}
+
+				assert_eq!(map.check_invariants(), Ok(()));
+				assert_eq!(
+					map.iter().collect::<Vec<_>>(),
+					reference.iter().collect::<Vec<_>>()
+				);
+				assert_eq!(
+					map.get_at_point(interval.start()),
+					reference.get_at_point(interval.start())
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn zosdit_map_differential_tests() {
+		for seed in 0..200 {
+			let bytes = bytes(seed);
+			let mut u = Unstructured::new(&bytes);
+
+			let mut map: ZosditMap<u8, Interval<u8>, u8> = ZosditMap::new();
+			let mut reference = ReferenceZosditMap::new();
+
+			while !u.is_empty() {
+				//small points so that zero-overlapping intervals are common
+				let a = u8::arbitrary(&mut u).unwrap() % 16;
+				let b = u8::arbitrary(&mut u).unwrap() % 16;
+				let interval = Interval {
+					start: min(a, b),
+					end: max(a, b),
+				};
+				let value = u8::arbitrary(&mut u).unwrap();
+
+				match u8::arbitrary(&mut u).unwrap() % 4 {
+					0 | 1 => assert_eq!(
+						map.insert_strict_back(interval, value),
+						reference.insert_strict_back(interval, value)
+					),
+					2 => assert_eq!(
+						map.cut(interval).collect::<Vec<_>>(),
+						reference.cut(interval).collect::<Vec<_>>()
+					),
+					_ => assert_eq!(
+						map.remove_last_value_at_point(interval.start()),
+						reference.remove_last_value_at_point(interval.start())
+					),
+				}
+
+				assert_eq!(map.check_invariants(), Ok(()));
+				assert_eq!(map.len(), reference.len());
+				assert_eq!(
+					map.iter().collect::<Vec<_>>(),
+					reference.iter().collect::<Vec<_>>()
+				);
+				assert_eq!(
+					map.overlapping(interval).collect::<Vec<_>>(),
+					reference.overlapping(interval).collect::<Vec<_>>()
+				);
+				assert_eq!(
+					map.is_zero_overlap(interval),
+					reference.is_zero_overlap(interval)
+				);
+				assert_eq!(
+					map.get_last_value_at_point(interval.end()),
+					reference.get_last_value_at_point(interval.end())
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn gqdit_differential_tests() {
+		for seed in 0..200 {
+			let bytes = bytes(seed);
+			let mut u = Unstructured::new(&bytes);
+
+			let mut map: Gqdit<u8, Interval<u8>, u8> = Gqdit::new();
+			let mut reference = ReferenceGqdit::new();
+
+			while !u.is_empty() {
+				let interval = Interval::arbitrary(&mut u).unwrap();
+				let identifiers = BTreeSet::from([
+					u8::arbitrary(&mut u).unwrap() % 4,
+					u8::arbitrary(&mut u).unwrap() % 4,
+				]);
+				let identifier = u8::arbitrary(&mut u).unwrap() % 4;
+
+				match u8::arbitrary(&mut u).unwrap() % 4 {
+					0 | 1 => {
+						map.insert(identifiers.clone(), interval);
+						reference.insert(identifiers, interval);
+					}
+					2 => {
+						map.cut_with_identifiers(identifiers.clone(), interval);
+						reference.cut_with_identifiers(identifiers, interval);
+					}
+					_ => {
+						map.cut_all_identifiers(interval);
+						reference.cut_all_identifiers(interval);
+					}
+				}
+
+				assert_eq!(map.check_invariants(), Ok(()));
+				assert_eq!(
+					map.gaps_no_identifier(interval),
+					reference.gaps_no_identifier(interval)
+				);
+				assert_eq!(
+					map.gaps_with_identifier(identifier, interval),
+					reference.gaps_with_identifier(identifier, interval)
+				);
+				assert_eq!(
+					map.identifiers_at_point(interval.start()),
+					reference.identifiers_at_point(interval.start())
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn arbitrary_tests() {
+		for seed in 0..200 {
+			let bytes = bytes(seed);
+
+			let map = NoditMap::<i8, Interval<i8>, bool>::arbitrary(
+				&mut Unstructured::new(&bytes),
+			)
+			.unwrap();
+			assert_eq!(map.check_invariants(), Ok(()));
+
+			let set = NoditSet::<i8, Interval<i8>>::arbitrary(
+				&mut Unstructured::new(&bytes),
+			)
+			.unwrap();
+			assert_eq!(set.check_invariants(), Ok(()));
+
+			let map = ZosditMap::<i8, Interval<i8>, bool>::arbitrary(
+				&mut Unstructured::new(&bytes),
+			)
+			.unwrap();
+			assert_eq!(map.check_invariants(), Ok(()));
+
+			let map = Gqdit::<i8, Interval<i8>, u8>::arbitrary(
+				&mut Unstructured::new(&bytes),
+			)
+			.unwrap();
+			assert_eq!(map.check_invariants(), Ok(()));
+		}
+	}
+}
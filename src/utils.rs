@@ -1,6 +1,9 @@
 use core::cmp::Ordering;
 
-use crate::{InclusiveInterval, Interval, IntervalType, PointType};
+use crate::{
+	InclusiveInterval, Interval, IntervalType, IntoInterval, InvariantError,
+	PointType,
+};
 
 pub(crate) fn cmp_point_with_interval<I, K>(point: I, interval: K) -> Ordering
 where
@@ -77,6 +80,36 @@ where
 	}
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) enum Nesting {
+	Disjoint,
+	Equal,
+	LeftContainsRight,
+	RightContainsLeft,
+	Crossing,
+}
+pub(crate) fn nesting<I, A, B>(a: A, b: B) -> Nesting
+where
+	I: PointType,
+	A: IntervalType<I>,
+	B: IntervalType<I>,
+{
+	let a_contains_b =
+		contains_point(a, b.start()) && contains_point(a, b.end());
+	let b_contains_a =
+		contains_point(b, a.start()) && contains_point(b, a.end());
+	match (a_contains_b, b_contains_a) {
+		(true, true) => Nesting::Equal,
+		(true, false) => Nesting::LeftContainsRight,
+		(false, true) => Nesting::RightContainsLeft,
+		(false, false) => match config(a, b) {
+			Config::LeftFirstNonOverlapping
+			| Config::RightFirstNonOverlapping => Nesting::Disjoint,
+			_ => Nesting::Crossing,
+		},
+	}
+}
+
 pub(crate) fn contains_point<I, K>(interval: K, point: I) -> bool
 where
 	I: PointType,
@@ -110,6 +143,9 @@ where
 				end: base.end(),
 			});
 		}
+		//in the LeftFirst configs cut starts after base so it can't start
+		//at I::MIN, and in RightFirstPartialOverlap cut ends before base so
+		//it can't end at I::MAX
 		Config::LeftFirstPartialOverlap => {
 			result.before_cut = Some(Interval {
 				start: base.start(),
@@ -246,6 +282,17 @@ where
 	}
 }
 
+/// Converts the interval given to a query or cut method into an
+/// [`Interval`], returning `None` if it is empty so that the method can
+/// treat it as a query which doesn't overlap anything.
+pub(crate) fn query_interval<Q, I, M>(interval: Q) -> Option<Interval<I>>
+where
+	I: PointType,
+	Q: IntoInterval<I, M>,
+{
+	interval.into_interval().ok()
+}
+
 pub(crate) fn invalid_interval_panic<Q, I>(interval: Q)
 where
 	I: PointType,
@@ -257,3 +304,244 @@ where
 		);
 	}
 }
+
+/// Checks that the given intervals are valid, in ascending order and
+/// don't overlap, returning the number of intervals.
+///
+/// If `zero_overlap` is `true` then consecutive intervals may share a
+/// single point, as in a `ZosditMap`.
+pub(crate) fn check_ordered_intervals<I, K>(
+	intervals: impl Iterator<Item = K>,
+	zero_overlap: bool,
+) -> Result<usize, InvariantError<I>>
+where
+	I: PointType,
+	K: IntervalType<I>,
+{
+	let mut counted = 0;
+	let mut previous: Option<Interval<I>> = None;
+
+	for interval in intervals {
+		let interval = Interval {
+			start: interval.start(),
+			end: interval.end(),
+		};
+
+		if !interval.is_valid() {
+			return Err(InvariantError::InvalidInterval { interval });
+		}
+
+		if let Some(first) = previous {
+			if (first.start, first.end) >= (interval.start, interval.end) {
+				return Err(InvariantError::Unsorted {
+					first,
+					second: interval,
+				});
+			}
+
+			let overlaps = if zero_overlap {
+				interval.start < first.end
+			} else {
+				interval.start <= first.end
+			};
+			if overlaps {
+				return Err(InvariantError::Overlap {
+					first,
+					second: interval,
+				});
+			}
+		}
+
+		counted += 1;
+		previous = Some(interval);
+	}
+
+	Ok(counted)
+}
+
+/// Extends the interval by one point on either side, where possible, so
+/// that it overlaps any intervals which touch it.
+pub(crate) fn expand<I, K>(interval: K) -> K
+where
+	I: PointType,
+	K: IntervalType<I>,
+{
+	K::from(Interval {
+		start: interval.start().down().unwrap_or(interval.start()),
+		end: interval.end().up().unwrap_or(interval.end()),
+	})
+}
+
+/// Walks two ascending sequences of non-overlapping intervals at the
+/// same time, yielding every maximal segment of their common refinement
+/// which is covered by at least one of them along with the data of the
+/// interval(s) covering it.
+///
+/// Since it only looks at the front interval of each sequence it runs
+/// in one linear pass without allocating.
+pub(crate) struct Refinement<I, A, B, X, Y>
+where
+	A: Iterator<Item = (Interval<I>, X)>,
+	B: Iterator<Item = (Interval<I>, Y)>,
+{
+	a: A,
+	b: B,
+	a_current: Option<(Interval<I>, X)>,
+	b_current: Option<(Interval<I>, Y)>,
+}
+pub(crate) fn refinement<I, A, B, X, Y>(
+	a: A,
+	b: B,
+) -> Refinement<I, A, B, X, Y>
+where
+	A: Iterator<Item = (Interval<I>, X)>,
+	B: Iterator<Item = (Interval<I>, Y)>,
+{
+	Refinement {
+		a,
+		b,
+		a_current: None,
+		b_current: None,
+	}
+}
+impl<I, A, B, X, Y> Iterator for Refinement<I, A, B, X, Y>
+where
+	I: PointType,
+	A: Iterator<Item = (Interval<I>, X)>,
+	B: Iterator<Item = (Interval<I>, Y)>,
+	X: Copy,
+	Y: Copy,
+{
+	type Item = (Interval<I>, Option<X>, Option<Y>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.a_current.is_none() {
+			self.a_current = self.a.next();
+		}
+		if self.b_current.is_none() {
+			self.b_current = self.b.next();
+		}
+
+		match (self.a_current, self.b_current) {
+			(None, None) => None,
+			(Some((a, x)), None) => {
+				self.a_current = None;
+				Some((a, Some(x), None))
+			}
+			(None, Some((b, y))) => {
+				self.b_current = None;
+				Some((b, None, Some(y)))
+			}
+			(Some((a, x)), Some((b, y))) => {
+				let start = I::min(a.start, b.start);
+				let in_a = a.start == start;
+				let in_b = b.start == start;
+
+				//the later start can't be I::MIN so down() can't fail
+				let end = match (in_a, in_b) {
+					(true, false) => I::min(a.end, b.start.down().unwrap()),
+					(false, true) => I::min(b.end, a.start.down().unwrap()),
+					_ => I::min(a.end, b.end),
+				};
+
+				if in_a {
+					self.a_current = trim_front(a, end).map(|a| (a, x));
+				}
+				if in_b {
+					self.b_current = trim_front(b, end).map(|b| (b, y));
+				}
+
+				Some((
+					Interval { start, end },
+					in_a.then_some(x),
+					in_b.then_some(y),
+				))
+			}
+		}
+	}
+}
+/// Removes every point up to and including `end` from the front of
+/// `interval`, `end` must be contained within `interval`.
+fn trim_front<I>(interval: Interval<I>, end: I) -> Option<Interval<I>>
+where
+	I: PointType,
+{
+	if end == interval.end {
+		None
+	} else {
+		//end < interval.end so it can't be I::MAX
+		Some(Interval {
+			start: end.up().unwrap(),
+			end: interval.end,
+		})
+	}
+}
+
+/// The error returned by the serde visitors when the interval at `index`
+/// in the input overlaps an interval already inserted before it.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_overlap_error<E, K, V>(
+	index: usize,
+	error: crate::OverlapError<K, V>,
+) -> E
+where
+	E: serde::de::Error,
+	K: core::fmt::Display,
+{
+	E::custom(format_args!(
+		"the interval {} at index {index} overlaps {}",
+		error.interval, error.conflict
+	))
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+	use alloc::vec::Vec;
+	use core::fmt::Debug;
+
+	use crate::{DiscreteFinite, Interval, PointType};
+
+	/// Points near the edges and the middle of the `u8` domain.
+	pub(crate) const U8_EDGES: [u8; 8] = [0, 1, 2, 127, 128, 253, 254, 255];
+	/// Points near the edges and around zero of the `i8` domain.
+	pub(crate) const I8_EDGES: [i8; 7] =
+		[i8::MIN, -127, -1, 0, 1, 126, i8::MAX];
+
+	/// Every interval between a pair of `edges`.
+	pub(crate) fn edge_intervals<I>(edges: &[I]) -> Vec<Interval<I>>
+	where
+		I: PointType,
+	{
+		let mut intervals = Vec::new();
+		for start in edges {
+			for end in edges.iter().filter(|end| start <= *end) {
+				intervals.push(Interval {
+					start: *start,
+					end: *end,
+				});
+			}
+		}
+		intervals
+	}
+
+	/// Walks the whole domain of `I` up from `MIN`, checking that it is
+	/// strictly increasing and matches walking down from `MAX`.
+	pub(crate) fn walk_domain<I>() -> Vec<I>
+	where
+		I: Ord + Copy + Debug + DiscreteFinite,
+	{
+		let mut up = Vec::from([I::MIN]);
+		while let Some(next) = up.last().unwrap().up() {
+			up.push(next);
+		}
+		let mut down = Vec::from([I::MAX]);
+		while let Some(next) = down.last().unwrap().down() {
+			down.push(next);
+		}
+		down.reverse();
+
+		assert!(up.windows(2).all(|pair| pair[0] < pair[1]));
+		assert_eq!(up, down);
+		up
+	}
+}
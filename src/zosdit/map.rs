@@ -7,6 +7,7 @@
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::cmp::Ordering;
+use core::fmt;
 use core::marker::PhantomData;
 
 use btree_monstrousity::btree_map::SearchBoundCustom;
@@ -14,12 +15,12 @@ use btree_monstrousity::BTreeMap;
 use smallvec::SmallVec;
 
 use crate::utils::{
-	cut_interval, exclusive_comp_generator, inclusive_comp_generator,
-	invalid_interval_panic,
+	check_ordered_intervals, cut_interval, exclusive_comp_generator,
+	inclusive_comp_generator, invalid_interval_panic, query_interval,
 };
 #[cfg(doc)]
 use crate::NoditMap;
-use crate::{IntervalType, PointType};
+use crate::{Interval, IntervalType, IntoInterval, InvariantError, PointType};
 
 type ValueStore<V> = SmallVec<[V; 2]>;
 
@@ -68,13 +69,40 @@ pub struct ZosditMap<I, K, V> {
 }
 
 /// The error returned when inserting a interval that non-zero-overlaps another interval when it
-/// should not have. Contains the value that was not inserted.
+/// should not have. Contains the interval and value that were not inserted, along with the
+/// first interval they non-zero-overlapped.
+///
+/// The error only stores the first non-zero-overlapped interval so that it stays cheap to create,
+/// use [`ZosditMap::overlapping()`] with `interval` if you need the others.
 #[derive(PartialEq, Debug)]
-pub struct NonZeroOverlapError<V> {
+pub struct NonZeroOverlapError<K, V> {
+	/// The interval which was not inserted, because of the overlap error.
+	pub interval: K,
+	/// The first interval already in the map which `interval` non-zero-overlapped.
+	pub conflict: K,
 	/// The value which was not inserted, because of the overlap error.
 	pub value: V,
 }
 
+impl<K, V> fmt::Display for NonZeroOverlapError<K, V>
+where
+	K: fmt::Display,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"interval {} non-zero-overlaps the existing interval {}",
+			self.interval, self.conflict
+		)
+	}
+}
+impl<K, V> core::error::Error for NonZeroOverlapError<K, V>
+where
+	K: fmt::Debug + fmt::Display,
+	V: fmt::Debug,
+{
+}
+
 impl<I, K, V> ZosditMap<I, K, V>
 where
 	I: PointType,
@@ -179,7 +207,9 @@ where
 			SearchBoundCustom::Included,
 		);
 
-		if cursor.key().is_none() {
+		//the cursor is on the first interval which doesn't end at or before
+		//the point, so an interval ending at the point will be just before
+		if !cursor.key().is_some_and(|key| key.contains_point(point)) {
 			cursor.move_prev();
 		}
 
@@ -226,13 +256,14 @@ where
 			SearchBoundCustom::Included,
 		);
 
-		if cursor.key().is_none() {
+		if !cursor.key().is_some_and(|key| key.contains_point(point)) {
 			cursor.move_prev();
 		}
 
 		if let Some((key, value)) = cursor.key_value_mut() {
 			if key.contains_point(point) {
 				let last = value.pop().unwrap();
+				self.len -= 1;
 
 				if value.is_empty() {
 					cursor.remove_current();
@@ -280,11 +311,23 @@ where
 		&mut self,
 		interval: K,
 		value: V,
-	) -> Result<(), NonZeroOverlapError<V>> {
+	) -> Result<(), NonZeroOverlapError<K, V>> {
 		invalid_interval_panic(interval);
 
 		if !self.is_zero_overlap(interval) {
-			Err(NonZeroOverlapError { value })
+			let conflict = self
+				.non_zero_overlapping(Interval {
+					start: interval.start(),
+					end: interval.end(),
+				})
+				.next()
+				.expect("the interval non-zero-overlaps the map");
+
+			Err(NonZeroOverlapError {
+				interval,
+				conflict: *conflict,
+				value,
+			})
 		} else {
 			self.inner
 				.entry(interval, |inner_interval, new_interval| {
@@ -325,12 +368,6 @@ where
 	/// Returns `true` if the given interval zero-overlaps the intervals in
 	/// the map, and `false` if not.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ii;
@@ -351,12 +388,18 @@ where
 	/// assert_eq!(map.is_zero_overlap(ii(4, 4)), false);
 	/// assert_eq!(map.is_zero_overlap(ii(4, 12)), false);
 	/// ```
-	pub fn is_zero_overlap<Q>(&self, interval: Q) -> bool
+	pub fn is_zero_overlap<Q, M>(&self, interval: Q) -> bool
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
+		query_interval(interval).map_or(true, |interval| {
+			self.non_zero_overlapping(interval).next().is_none()
+		})
+	}
+	fn non_zero_overlapping(
+		&self,
+		interval: Interval<I>,
+	) -> impl Iterator<Item = &K> {
 		//i had to draw all the different combinations of intervals on a piece of paper to find
 		//this elegant solution, there are a surprising amount of different scenarios when you
 		//start considering zero-sized intervals and things
@@ -368,8 +411,7 @@ where
 				exclusive_comp_generator(interval.end(), Ordering::Less),
 				SearchBoundCustom::Included,
 			)
-			.next()
-			.is_none()
+			.map(|(key, _)| key)
 	}
 
 	/// The same as [`NoditMap::cut()`] except it flattens the `SmallVec`s of values into the
@@ -377,12 +419,6 @@ where
 	///
 	/// See [`NoditMap::cut()`] for more details.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ee, ii};
@@ -416,21 +452,26 @@ where
 	/// assert_eq!(base.len(), 2);
 	/// assert_eq!(base, after_cut);
 	/// ```
-	pub fn cut<'a, Q>(&'a mut self, interval: Q) -> impl Iterator<Item = (K, V)>
+	pub fn cut<'a, Q, M>(&'a mut self, interval: Q) -> impl Iterator<Item = (K, V)>
 	where
-		Q: IntervalType<I> + 'a,
+		Q: IntoInterval<I, M> + 'a,
 		V: Clone,
 	{
-		invalid_interval_panic(interval);
+		let Some(interval) = query_interval(interval) else {
+			return Vec::new().into_iter();
+		};
 
 		let mut result = Vec::new();
+		let mut remaining = Vec::new();
 
 		let mut cursor = self.inner.upper_bound_mut(
 			exclusive_comp_generator(interval.start(), Ordering::Less),
 			SearchBoundCustom::Included,
 		);
 
-		if cursor.key().is_none() {
+		//the cursor is on the last interval which doesn't start after the
+		//start of the cut, which may end before it
+		if !cursor.key().is_some_and(|key| key.overlaps(&interval)) {
 			cursor.move_next();
 		}
 
@@ -443,13 +484,19 @@ where
 
 			let cut_result = cut_interval(key, interval);
 
+			//the remaining parts can be singular intervals identical to
+			//singular intervals already in the map so they are merged in
+			//afterwards, the part before the cut comes after any values
+			//already there and the part after the cut before them
 			if let Some(before_cut) = cut_result.before_cut {
-				cursor.insert_before(K::from(before_cut), value_store.clone());
-				self.len += value_store.len();
+				remaining.push((
+					K::from(before_cut),
+					value_store.clone(),
+					false,
+				));
 			}
 			if let Some(after_cut) = cut_result.after_cut {
-				self.len += value_store.len();
-				cursor.insert_before(K::from(after_cut), value_store.clone());
+				remaining.push((K::from(after_cut), value_store.clone(), true));
 			}
 
 			self.len -= value_store.len();
@@ -460,6 +507,10 @@ where
 			);
 		}
 
+		for (interval, value_store, at_front) in remaining {
+			self.insert_value_store_merging(interval, value_store, at_front);
+		}
+
 		result.into_iter()
 	}
 
@@ -468,12 +519,6 @@ where
 	///
 	/// See [`NoditMap::overlapping()`] for more details.
 	///
-	/// # Panics
-	///
-	/// Panics if the given interval is an invalid interval. See [`Invalid
-	/// Intervals`](https://docs.rs/nodit/latest/nodit/index.html#invalid-intervals)
-	/// for more details.
-	///
 	/// # Examples
 	/// ```
 	/// use nodit::interval::{ee, ii};
@@ -498,18 +543,21 @@ where
 	/// 	]
 	/// );
 	/// ```
-	pub fn overlapping<Q>(&self, interval: Q) -> impl Iterator<Item = (&K, &V)>
+	pub fn overlapping<Q, M>(&self, interval: Q) -> impl Iterator<Item = (&K, &V)>
 	where
-		Q: IntervalType<I>,
+		Q: IntoInterval<I, M>,
 	{
-		invalid_interval_panic(interval);
-
-		let overlapping = self.inner.range(
-			inclusive_comp_generator(interval.start(), Ordering::Less),
-			SearchBoundCustom::Included,
-			inclusive_comp_generator(interval.end(), Ordering::Greater),
-			SearchBoundCustom::Included,
-		);
+		let overlapping = query_interval(interval)
+			.map(|interval| {
+				self.inner.range(
+					inclusive_comp_generator(interval.start(), Ordering::Less),
+					SearchBoundCustom::Included,
+					inclusive_comp_generator(interval.end(), Ordering::Greater),
+					SearchBoundCustom::Included,
+				)
+			})
+			.into_iter()
+			.flatten();
 
 		overlapping.flat_map(|(interval, value_store)| {
 			value_store.iter().map(move |value| (interval, value))
@@ -570,7 +618,7 @@ where
 	/// ```
 	pub fn from_slice_strict_back<const N: usize>(
 		slice: [(K, V); N],
-	) -> Result<ZosditMap<I, K, V>, NonZeroOverlapError<V>> {
+	) -> Result<ZosditMap<I, K, V>, NonZeroOverlapError<K, V>> {
 		ZosditMap::from_iter_strict_back(slice.into_iter())
 	}
 
@@ -589,7 +637,7 @@ where
 	/// # Examples
 	/// ```
 	/// use nodit::interval::ie;
-	/// use nodit::ZosditMap;
+	/// use nodit::{InclusiveInterval, ZosditMap};
 	///
 	/// let slice = [(ie(1, 4), -2), (ie(4, 8), -4), (ie(8, 100), -6)];
 	///
@@ -602,13 +650,213 @@ where
 	/// ```
 	pub fn from_iter_strict_back(
 		iter: impl Iterator<Item = (K, V)>,
-	) -> Result<ZosditMap<I, K, V>, NonZeroOverlapError<V>> {
+	) -> Result<ZosditMap<I, K, V>, NonZeroOverlapError<K, V>> {
 		let mut map = ZosditMap::new();
 		for (interval, value) in iter {
 			map.insert_strict_back(interval, value)?;
 		}
 		Ok(map)
 	}
+
+	/// The same as [`NoditMap::map_values()`] except it maps every value
+	/// in the `SmallVec`s, keeping their order.
+	///
+	/// See [`NoditMap::map_values()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::ZosditMap;
+	///
+	/// let map = ZosditMap::from_slice_strict_back([
+	/// 	(ii(0, 4), -2),
+	/// 	(ii(4, 4), -4),
+	/// 	(ii(4, 4), -6),
+	/// ])
+	/// .unwrap();
+	///
+	/// let map = map.map_values(|value| value * 2);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ii(0, 4), -4), (ii(4, 4), -8), (ii(4, 4), -12)]
+	/// );
+	/// ```
+	pub fn map_values<W, F>(self, mut f: F) -> ZosditMap<I, K, W>
+	where
+		F: FnMut(V) -> W,
+	{
+		self.filter_map_values(|value| Some(f(value)))
+	}
+
+	/// The same as [`NoditMap::filter_map_values()`] except it filters
+	/// and maps every value in the `SmallVec`s, keeping their order.
+	///
+	/// Intervals whose `SmallVec` ends up empty are removed from the
+	/// map.
+	///
+	/// See [`NoditMap::filter_map_values()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::ZosditMap;
+	///
+	/// let map = ZosditMap::from_slice_strict_back([
+	/// 	(ii(0, 4), -2),
+	/// 	(ii(4, 4), -3),
+	/// 	(ii(4, 4), -6),
+	/// 	(ii(4, 8), -7),
+	/// ])
+	/// .unwrap();
+	///
+	/// let map = map.filter_map_values(|value| (value % 2 == 0).then_some(value));
+	///
+	/// assert_eq!(map.len(), 2);
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ii(0, 4), -2), (ii(4, 4), -6)]
+	/// );
+	/// ```
+	pub fn filter_map_values<W, F>(self, mut f: F) -> ZosditMap<I, K, W>
+	where
+		F: FnMut(V) -> Option<W>,
+	{
+		let mut map = ZosditMap::new();
+		for (interval, value_store) in self.inner {
+			let value_store: ValueStore<W> =
+				value_store.into_iter().filter_map(&mut f).collect();
+
+			if !value_store.is_empty() {
+				map.insert_value_store_unchecked(interval, value_store);
+			}
+		}
+		map
+	}
+
+	/// The same as [`NoditMap::try_map_values()`] except it maps every
+	/// value in the `SmallVec`s, keeping their order.
+	///
+	/// See [`NoditMap::try_map_values()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::ZosditMap;
+	///
+	/// let map = ZosditMap::from_slice_strict_back([
+	/// 	(ii(0, 4), "2"),
+	/// 	(ii(4, 4), "4"),
+	/// 	(ii(4, 4), "x"),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert!(map.try_map_values(|value| value.parse::<u8>()).is_err());
+	/// ```
+	pub fn try_map_values<W, E, F>(
+		self,
+		mut f: F,
+	) -> Result<ZosditMap<I, K, W>, E>
+	where
+		F: FnMut(V) -> Result<W, E>,
+	{
+		let mut map = ZosditMap::new();
+		for (interval, value_store) in self.inner {
+			let value_store = value_store
+				.into_iter()
+				.map(&mut f)
+				.collect::<Result<ValueStore<W>, E>>()?;
+
+			map.insert_value_store_unchecked(interval, value_store);
+		}
+		Ok(map)
+	}
+	/// Checks that the internal invariants of the map hold, returning the
+	/// first [`InvariantError`] found if not.
+	///
+	/// The invariants are that every interval is valid and has at least
+	/// one value, the intervals are stored in ascending order without
+	/// overlapping by more than a single point, and the length of the map
+	/// matches the number of values.
+	///
+	/// See [`NoditMap::check_invariants()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use nodit::interval::ii;
+	/// use nodit::ZosditMap;
+	///
+	/// let map = ZosditMap::from_slice_strict_back([
+	/// 	(ii(0, 4), -2),
+	/// 	(ii(4, 4), -4),
+	/// 	(ii(4, 4), -6),
+	/// 	(ii(4, 8), -8),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.check_invariants(), Ok(()));
+	/// ```
+	pub fn check_invariants(&self) -> Result<(), InvariantError<I>> {
+		let mut counted = 0;
+		for (interval, value_store) in self.inner.iter() {
+			if value_store.is_empty() {
+				return Err(InvariantError::EmptyValueStore {
+					interval: Interval {
+						start: interval.start(),
+						end: interval.end(),
+					},
+				});
+			}
+			counted += value_store.len();
+		}
+
+		check_ordered_intervals(self.inner.iter().map(|(key, _)| *key), true)?;
+
+		if counted != self.len {
+			return Err(InvariantError::LenMismatch {
+				len: self.len,
+				counted,
+			});
+		}
+
+		Ok(())
+	}
+	fn insert_value_store_merging(
+		&mut self,
+		interval: K,
+		mut value_store: ValueStore<V>,
+		at_front: bool,
+	) {
+		match self.inner.get_mut(|inner_interval: &K| {
+			(interval.start(), interval.end())
+				.cmp(&(inner_interval.start(), inner_interval.end()))
+		}) {
+			Some(existing) => {
+				self.len += value_store.len();
+				if at_front {
+					value_store.extend(existing.drain(..));
+					*existing = value_store;
+				} else {
+					existing.extend(value_store);
+				}
+			}
+			None => self.insert_value_store_unchecked(interval, value_store),
+		}
+	}
+	fn insert_value_store_unchecked(
+		&mut self,
+		interval: K,
+		value_store: ValueStore<V>,
+	) {
+		self.len += value_store.len();
+		//singular intervals share their start with the following
+		//interval so we have to compare the ends too
+		self.inner
+			.insert(interval, value_store, |inner_interval, new_interval| {
+				(new_interval.start(), new_interval.end())
+					.cmp(&(inner_interval.start(), inner_interval.end()))
+			});
+	}
 }
 
 impl<I, K, V> Default for ZosditMap<I, K, V> {
@@ -639,6 +887,7 @@ where
 
 #[cfg(feature = "serde")]
 mod serde {
+	use core::fmt;
 	use core::marker::PhantomData;
 
 	use serde::de::{SeqAccess, Visitor};
@@ -668,7 +917,7 @@ mod serde {
 	impl<'de, I, K, V> Deserialize<'de> for ZosditMap<I, K, V>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 		V: Deserialize<'de>,
 	{
 		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -692,7 +941,7 @@ mod serde {
 	impl<'de, I, K, V> Visitor<'de> for ZosditMapVisitor<I, K, V>
 	where
 		I: PointType,
-		K: IntervalType<I> + Deserialize<'de>,
+		K: IntervalType<I> + fmt::Display + Deserialize<'de>,
 		V: Deserialize<'de>,
 	{
 		type Value = ZosditMap<I, K, V>;
@@ -709,10 +958,15 @@ mod serde {
 			A: SeqAccess<'de>,
 		{
 			let mut map = ZosditMap::new();
+			let mut index = 0;
 			while let Some((interval, value)) = access.next_element()? {
-				map.insert_strict_back(interval, value).or(Err(
-					serde::de::Error::custom("intervals non-zero-overlap"),
-				))?;
+				map.insert_strict_back(interval, value).map_err(|error| {
+					serde::de::Error::custom(format_args!(
+						"the interval {} at index {index} non-zero-overlaps {}",
+						error.interval, error.conflict
+					))
+				})?;
+				index += 1;
 			}
 			Ok(map)
 		}
@@ -784,8 +1038,23 @@ mod tests {
 		map.insert_strict_back(ii(0_u8, u8::MAX), -4_i8).unwrap();
 		assert_eq!(map.len(), 2);
 
-		let _ = map.insert_strict_back(ii(9_u8, 10), -4_i8);
+		assert_eq!(
+			map.insert_strict_back(ii(9_u8, 10), -4_i8),
+			Err(NonZeroOverlapError {
+				interval: ii(9, 10),
+				conflict: ii(0, u8::MAX),
+				value: -4,
+			})
+		);
 		assert_eq!(map.len(), 2);
+
+		map.insert_strict_back(ii(u8::MAX, u8::MAX), 2).unwrap();
+		assert_eq!(
+			map.insert_strict_back(ii(0, u8::MAX), 6)
+				.unwrap_err()
+				.conflict,
+			ii(0, u8::MAX)
+		);
 	}
 
 	#[test]
@@ -803,6 +1072,61 @@ mod tests {
 		assert_eq!(map.get_last_value_at_point(8_u8), Some(&-3));
 		assert_eq!(map.get_last_value_at_point(10_u8), Some(&-3));
 		assert_eq!(map.get_last_value_at_point(u8::MAX), Some(&-3));
+
+		let map = ZosditMap::from_slice_strict_back([
+			(ii(1_u8, 2), -1_i8),
+			(ii(9, 11), -2),
+			(ii(11, 11), -3),
+		])
+		.unwrap();
+
+		//intervals ending at the point before a later interval
+		assert_eq!(map.get_last_value_at_point(2), Some(&-1));
+		assert_eq!(map.get_last_value_at_point(11), Some(&-3));
+	}
+
+	#[test]
+	fn remove_last_value_at_point_tests() {
+		let mut map = ZosditMap::from_slice_strict_back([
+			(ii(1_u8, 2), -1_i8),
+			(ii(9, 11), -2),
+			(ii(11, 11), -3),
+		])
+		.unwrap();
+
+		//intervals ending at the point before a later interval
+		assert_eq!(map.remove_last_value_at_point(2), Some(-1));
+		assert_eq!(map.len(), 2);
+		assert_eq!(map.remove_last_value_at_point(11), Some(-3));
+		assert_eq!(map.remove_last_value_at_point(11), Some(-2));
+		assert_eq!(map.remove_last_value_at_point(11), None);
+		assert_eq!(map.len(), 0);
+		assert_eq!(map.iter().next(), None);
+	}
+
+	#[test]
+	fn filter_map_values_tests() {
+		let map = ZosditMap::from_slice_strict_back([
+			(ii(0_u8, 4), 1_i8),
+			(ii(4, 4), 2),
+			(ii(4, 4), 3),
+			(ii(4, 4), 4),
+			(ii(4, 8), 5),
+		])
+		.unwrap();
+
+		let odd = map.clone().filter_map_values(|x| (x % 2 == 1).then_some(x));
+		assert_eq!(odd.len(), 3);
+		assert_eq!(
+			odd.iter().collect::<Vec<_>>(),
+			vec![(&ii(0, 4), &1), (&ii(4, 4), &3), (&ii(4, 8), &5)]
+		);
+		assert_eq!(odd.get_last_value_at_point(4), Some(&5));
+
+		let none = map.filter_map_values(|_| None::<i8>);
+		assert_eq!(none.len(), 0);
+		assert!(none.is_empty());
+		assert_eq!(none.iter().next(), None);
 	}
 
 	#[test]
@@ -833,5 +1157,44 @@ mod tests {
 			vec![(ii(0, 0), -8), (ii(0, u8::MAX), -4)],
 			"invalid cut"
 		);
+
+		let mut map = ZosditMap::from_slice_strict_back([
+			(ii(1_u8, 2), -1_i8),
+			(ii(9, 11), -2),
+			(ii(11, 11), -3),
+		])
+		.unwrap();
+
+		//the interval before the cut ends before it and the remaining
+		//singular interval joins the identical one already in the map
+		assert_eq!(map.cut(ii(6, 10)).collect::<Vec<_>>(), [(ii(9, 10), -2)]);
+		assert_eq!(map.len(), 3);
+		assert_eq!(
+			map.iter().collect::<Vec<_>>(),
+			[(&ii(1, 2), &-1), (&ii(11, 11), &-2), (&ii(11, 11), &-3)]
+		);
+		assert_eq!(map.get_last_value_at_point(11), Some(&-3));
+	}
+
+	#[test]
+	fn check_invariants_tests() {
+		let mut map = ZosditMap::from_slice_strict_back([
+			(ii(1_u8, 2), -1_i8),
+			(ii(9, 11), -2),
+			(ii(11, 11), -3),
+			(ii(14, 14), -4),
+		])
+		.unwrap();
+		assert_eq!(map.check_invariants(), Ok(()));
+
+		assert_eq!(map.cut(ii(6, 10)).count(), 1);
+		assert_eq!(map.remove_last_value_at_point(11), Some(-3));
+		assert_eq!(map.check_invariants(), Ok(()));
+
+		map.len += 1;
+		assert_eq!(
+			map.check_invariants(),
+			Err(InvariantError::LenMismatch { len: 4, counted: 3 })
+		);
 	}
 }